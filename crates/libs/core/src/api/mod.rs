@@ -8,8 +8,11 @@ use mssf_com::{
     FabricCommon::{
         IFabricAsyncOperationCallback, IFabricAsyncOperationContext, IFabricStringResult,
     },
-    FabricRuntime::IFabricStoreEventHandler,
-    FabricTypes::{FABRIC_CLIENT_ROLE, FABRIC_LOCAL_STORE_KIND, FABRIC_REPLICATOR_SETTINGS},
+    FabricRuntime::{IFabricSecondaryEventHandler, IFabricStoreEventHandler},
+    FabricTypes::{
+        FABRIC_CLIENT_ROLE, FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE, FABRIC_LOCAL_STORE_KIND,
+        FABRIC_REPLICATOR_SETTINGS,
+    },
 };
 use windows_core::{Interface, Param};
 
@@ -136,6 +139,23 @@ pub struct ApiTable {
             keyvaluestore: *mut *mut core::ffi::c_void,
         ) -> crate::HRESULT,
     >,
+    #[allow(clippy::type_complexity)]
+    fabric_create_key_value_store_replica2_fn: libloading::Symbol<
+        'static,
+        unsafe extern "system" fn(
+            riid: *const windows_core::GUID,
+            storename: windows_core::PCWSTR,
+            partitionid: windows_core::GUID,
+            replicaid: i64,
+            replicatorsettings: *const FABRIC_REPLICATOR_SETTINGS,
+            localstorekind: FABRIC_LOCAL_STORE_KIND,
+            localstoresettings: *const core::ffi::c_void,
+            storeeventhandler: *mut core::ffi::c_void,
+            secondaryeventhandler: *mut core::ffi::c_void,
+            notificationmode: FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE,
+            keyvaluestore: *mut *mut core::ffi::c_void,
+        ) -> crate::HRESULT,
+    >,
 }
 
 impl ApiTable {
@@ -172,6 +192,10 @@ impl ApiTable {
                 &lib_table.fabric_runtime,
                 "FabricCreateKeyValueStoreReplica",
             ),
+            fabric_create_key_value_store_replica2_fn: load_fn(
+                &lib_table.fabric_runtime,
+                "FabricCreateKeyValueStoreReplica2",
+            ),
         }
     }
 
@@ -320,4 +344,37 @@ impl ApiTable {
         .ok()?;
         Ok(unsafe { T::from_raw(result) })
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn fabric_create_key_value_store_replica2<T: Interface>(
+        &self,
+        storename: windows_core::PCWSTR,
+        partitionid: windows_core::GUID,
+        replicaid: i64,
+        replicatorsettings: *const FABRIC_REPLICATOR_SETTINGS,
+        localstorekind: FABRIC_LOCAL_STORE_KIND,
+        localstoresettings: *const core::ffi::c_void,
+        storeeventhandler: Option<&IFabricStoreEventHandler>,
+        secondaryeventhandler: Option<&IFabricSecondaryEventHandler>,
+        notificationmode: FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE,
+    ) -> crate::WinResult<T> {
+        let mut result = std::ptr::null_mut::<core::ffi::c_void>();
+        unsafe {
+            (self.fabric_create_key_value_store_replica2_fn)(
+                &T::IID,
+                storename,
+                partitionid,
+                replicaid,
+                replicatorsettings,
+                localstorekind,
+                localstoresettings,
+                storeeventhandler.param().abi(),
+                secondaryeventhandler.param().abi(),
+                notificationmode,
+                std::ptr::addr_of_mut!(result),
+            )
+        }
+        .ok()?;
+        Ok(unsafe { T::from_raw(result) })
+    }
 }
@@ -0,0 +1,377 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{
+        IFabricApplicationManagementClient10, IFabricApplicationUpgradeProgressResult2,
+    },
+    FabricTypes::{
+        FABRIC_APPLICATION_DESCRIPTION, FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        FABRIC_APPLICATION_UPGRADE_DESCRIPTION, FABRIC_DELETE_APPLICATION_DESCRIPTION,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION,
+        FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION, FABRIC_URI,
+    },
+};
+
+use crate::{
+    WString,
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        ApplicationDescription, ApplicationUpdateDescription, ApplicationUpgradeDescription,
+        ApplicationUpgradeProgress, DeleteApplicationDescription,
+        ProvisionApplicationTypeDescription, UnprovisionApplicationTypeDescription, Uri,
+    },
+};
+
+/// Application Management Client
+/// Manages the lifecycle of application types and applications.
+#[derive(Debug, Clone)]
+pub struct ApplicationManagementClient {
+    com: IFabricApplicationManagementClient10,
+}
+
+impl From<IFabricApplicationManagementClient10> for ApplicationManagementClient {
+    fn from(com: IFabricApplicationManagementClient10) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ApplicationManagementClient> for IFabricApplicationManagementClient10 {
+    fn from(value: ApplicationManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+impl ApplicationManagementClient {
+    fn provision_application_type_internal(
+        &self,
+        desc: &FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginProvisionApplicationType2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndProvisionApplicationType2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn unprovision_application_type_internal(
+        &self,
+        desc: &FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUnprovisionApplicationType2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUnprovisionApplicationType2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn create_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn upgrade_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_UPGRADE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpgradeApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpgradeApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_upgrade_progress_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricApplicationUpgradeProgressResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationUpgradeProgress(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationUpgradeProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_next_application_upgrade_domain_internal(
+        &self,
+        name: FABRIC_URI,
+        next_upgrade_domain: &WString,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveNextApplicationUpgradeDomain2(
+                    name,
+                    next_upgrade_domain.as_pcwstr(),
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndMoveNextApplicationUpgradeDomain2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn rollback_application_upgrade_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRollbackApplicationUpgrade(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRollbackApplicationUpgrade(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_application_internal(
+        &self,
+        desc: &FABRIC_DELETE_APPLICATION_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteApplication2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteApplication2(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl ApplicationManagementClient {
+    /// Provisions an application type from a package uploaded to the image store.
+    pub async fn provision_application_type(
+        &self,
+        desc: &ProvisionApplicationTypeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.provision_application_type_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Unprovisions an application type version.
+    /// All applications of the version need to be deleted first.
+    pub async fn unprovision_application_type(
+        &self,
+        desc: &UnprovisionApplicationTypeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw = desc.get_raw();
+            self.unprovision_application_type_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Creates an application of a provisioned application type.
+    pub async fn create_application(
+        &self,
+        desc: &ApplicationDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.create_application_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Updates the capacity settings of an application.
+    pub async fn update_application(
+        &self,
+        desc: &ApplicationUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.update_application_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Starts upgrading an application.
+    /// The call returns once the upgrade is accepted,
+    /// use get_application_upgrade_progress to track it.
+    pub async fn upgrade_application(
+        &self,
+        desc: &ApplicationUpgradeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.upgrade_application_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    pub async fn get_application_upgrade_progress(
+        &self,
+        application_name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationUpgradeProgress> {
+        let com = self
+            .get_application_upgrade_progress_internal(
+                application_name.as_raw(),
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        ApplicationUpgradeProgress::try_from(&com)
+    }
+
+    /// Moves an upgrade in manual mode to the next upgrade domain.
+    /// next_upgrade_domain is from the upgrade progress.
+    pub async fn move_next_upgrade_domain(
+        &self,
+        application_name: &Uri,
+        next_upgrade_domain: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.move_next_application_upgrade_domain_internal(
+            application_name.as_raw(),
+            next_upgrade_domain,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Rolls back the current upgrade of an application.
+    pub async fn rollback_application_upgrade(
+        &self,
+        application_name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.rollback_application_upgrade_internal(
+            application_name.as_raw(),
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Deletes an application and all its services.
+    pub async fn delete_application(
+        &self,
+        desc: &DeleteApplicationDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw = desc.get_raw();
+            self.delete_application_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+}
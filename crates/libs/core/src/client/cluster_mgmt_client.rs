@@ -0,0 +1,429 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricClusterManagementClient16, IFabricUpgradeProgressResult2},
+    FabricCommon::IFabricStringResult,
+    FabricTypes::{FABRIC_NODE_DEACTIVATION_INTENT, FABRIC_UPGRADE_DESCRIPTION},
+};
+
+use crate::{
+    GUID, WString,
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    strings::StringResult,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{FabricUpgradeDescription, FabricUpgradeProgress, NodeDeactivationIntent},
+};
+
+/// Cluster Management Client
+/// Manages nodes, partitions recovery and the fabric upgrade of the cluster.
+#[derive(Debug, Clone)]
+pub struct ClusterManagementClient {
+    com: IFabricClusterManagementClient16,
+}
+
+impl From<IFabricClusterManagementClient16> for ClusterManagementClient {
+    fn from(com: IFabricClusterManagementClient16) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ClusterManagementClient> for IFabricClusterManagementClient16 {
+    fn from(value: ClusterManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+impl ClusterManagementClient {
+    fn deactivate_node_internal(
+        &self,
+        node_name: &WString,
+        intent: FABRIC_NODE_DEACTIVATION_INTENT,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeactivateNode(
+                    node_name.as_pcwstr(),
+                    intent,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndDeactivateNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn activate_node_internal(
+        &self,
+        node_name: &WString,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginActivateNode(node_name.as_pcwstr(), timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndActivateNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn node_state_removed_internal(
+        &self,
+        node_name: &WString,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginNodeStateRemoved(node_name.as_pcwstr(), timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndNodeStateRemoved(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn recover_partitions_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginRecoverPartitions(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndRecoverPartitions(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn recover_partition_internal(
+        &self,
+        partition_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRecoverPartition(partition_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRecoverPartition(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_cluster_manifest_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricStringResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetClusterManifest(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndGetClusterManifest(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_cluster_configuration_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricStringResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterConfiguration(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterConfiguration(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn provision_fabric_internal(
+        &self,
+        code_file_path: &WString,
+        cluster_manifest_file_path: &WString,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginProvisionFabric(
+                    code_file_path.as_pcwstr(),
+                    cluster_manifest_file_path.as_pcwstr(),
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndProvisionFabric(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn upgrade_fabric_internal(
+        &self,
+        desc: &FABRIC_UPGRADE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpgradeFabric(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpgradeFabric(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_fabric_upgrade_progress_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricUpgradeProgressResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetFabricUpgradeProgress(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetFabricUpgradeProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_next_fabric_upgrade_domain_internal(
+        &self,
+        next_upgrade_domain: &WString,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveNextFabricUpgradeDomain2(
+                    next_upgrade_domain.as_pcwstr(),
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndMoveNextFabricUpgradeDomain2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn rollback_fabric_upgrade_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRollbackFabricUpgrade(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRollbackFabricUpgrade(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl ClusterManagementClient {
+    /// Deactivates a node. Replicas are moved off the node according to the intent.
+    /// The call returns once the request is accepted, the node status shows
+    /// the deactivation progress.
+    pub async fn deactivate_node(
+        &self,
+        node_name: &WString,
+        intent: NodeDeactivationIntent,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.deactivate_node_internal(
+            node_name,
+            intent.into(),
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Activates a deactivated node.
+    pub async fn activate_node(
+        &self,
+        node_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.activate_node_internal(
+            node_name,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Notifies the cluster that the state of a down node is lost,
+    /// so its replicas can be rebuilt elsewhere.
+    /// Only call this when the node is not coming back with its data.
+    pub async fn node_state_removed(
+        &self,
+        node_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.node_state_removed_internal(
+            node_name,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Recovers all partitions stuck in quorum loss.
+    pub async fn recover_partitions(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.recover_partitions_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await?
+    }
+
+    /// Recovers a partition stuck in quorum loss.
+    pub async fn recover_partition(
+        &self,
+        partition_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.recover_partition_internal(
+            partition_id,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Gets the cluster manifest xml.
+    pub async fn get_cluster_manifest(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<WString> {
+        self.get_cluster_manifest_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await?
+            .map(|s| StringResult::from(&s).into_inner())
+    }
+
+    /// Gets the cluster configuration json of a standalone cluster.
+    pub async fn get_cluster_configuration(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<WString> {
+        self.get_cluster_configuration_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await?
+            .map(|s| StringResult::from(&s).into_inner())
+    }
+
+    /// Provisions fabric code and/or cluster manifest from the image store.
+    /// Either path can be empty, but not both.
+    pub async fn provision_fabric(
+        &self,
+        code_file_path: &WString,
+        cluster_manifest_file_path: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.provision_fabric_internal(
+            code_file_path,
+            cluster_manifest_file_path,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Starts upgrading the cluster to a provisioned code and/or config version.
+    /// The call returns once the upgrade is accepted,
+    /// use get_fabric_upgrade_progress to track it.
+    pub async fn upgrade_fabric(
+        &self,
+        desc: &FabricUpgradeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.upgrade_fabric_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await?
+    }
+
+    pub async fn get_fabric_upgrade_progress(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<FabricUpgradeProgress> {
+        let com = self
+            .get_fabric_upgrade_progress_internal(
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        FabricUpgradeProgress::try_from(&com)
+    }
+
+    /// Moves a fabric upgrade in manual mode to the next upgrade domain.
+    /// next_upgrade_domain is from the upgrade progress.
+    pub async fn move_next_fabric_upgrade_domain(
+        &self,
+        next_upgrade_domain: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.move_next_fabric_upgrade_domain_internal(
+            next_upgrade_domain,
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    /// Rolls back the current fabric upgrade.
+    pub async fn rollback_fabric_upgrade(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.rollback_fabric_upgrade_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await?
+    }
+}
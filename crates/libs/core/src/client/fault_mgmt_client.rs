@@ -0,0 +1,258 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{
+        IFabricFaultManagementClient3, IFabricMovePrimaryResult, IFabricMoveSecondaryResult,
+        IFabricRestartDeployedCodePackageResult, IFabricRestartNodeResult, IFabricStartNodeResult,
+        IFabricStopNodeResult,
+    },
+    FabricTypes::{
+        FABRIC_MOVE_PRIMARY_DESCRIPTION2, FABRIC_MOVE_SECONDARY_DESCRIPTION2,
+        FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2, FABRIC_RESTART_NODE_DESCRIPTION2,
+        FABRIC_START_NODE_DESCRIPTION2, FABRIC_STOP_NODE_DESCRIPTION2,
+    },
+};
+
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        DeployedCodePackageResult, MovePrimaryDescription, MovePrimaryResult,
+        MoveSecondaryDescription, MoveSecondaryResult, NodeResult,
+        RestartDeployedCodePackageDescription, RestartNodeDescription, StartNodeDescription,
+        StopNodeDescription,
+    },
+};
+
+/// Fault Management Client
+/// Injects faults into nodes, code packages and replicas for testing.
+#[derive(Debug, Clone)]
+pub struct FaultManagementClient {
+    com: IFabricFaultManagementClient3,
+}
+
+impl From<IFabricFaultManagementClient3> for FaultManagementClient {
+    fn from(com: IFabricFaultManagementClient3) -> Self {
+        Self { com }
+    }
+}
+
+impl From<FaultManagementClient> for IFabricFaultManagementClient3 {
+    fn from(value: FaultManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+impl FaultManagementClient {
+    fn restart_node_internal(
+        &self,
+        desc: &FABRIC_RESTART_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricRestartNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginRestartNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndRestartNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_node_internal(
+        &self,
+        desc: &FABRIC_START_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricStartNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStartNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStartNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn stop_node_internal(
+        &self,
+        desc: &FABRIC_STOP_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricStopNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStopNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStopNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn restart_deployed_code_package_internal(
+        &self,
+        desc: &FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricRestartDeployedCodePackageResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRestartDeployedCodePackage(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRestartDeployedCodePackage(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_primary_internal(
+        &self,
+        desc: &FABRIC_MOVE_PRIMARY_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricMovePrimaryResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginMovePrimary(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndMovePrimary(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_secondary_internal(
+        &self,
+        desc: &FABRIC_MOVE_SECONDARY_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricMoveSecondaryResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveSecondary(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndMoveSecondary(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl FaultManagementClient {
+    /// Restarts a node. The node instance id in the description is checked
+    /// against the current instance unless it is zero.
+    pub async fn restart_node(
+        &self,
+        desc: &RestartNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.restart_node_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(NodeResult::from(res))
+    }
+
+    /// Starts a node stopped by stop_node.
+    pub async fn start_node(
+        &self,
+        desc: &StartNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_node_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(NodeResult::from(res))
+    }
+
+    /// Stops a node. The node stays down until start_node is called.
+    pub async fn stop_node(
+        &self,
+        desc: &StopNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.stop_node_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(NodeResult::from(res))
+    }
+
+    /// Restarts a code package deployed on a node.
+    pub async fn restart_deployed_code_package(
+        &self,
+        desc: &RestartDeployedCodePackageDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedCodePackageResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.restart_deployed_code_package_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(DeployedCodePackageResult::from(res))
+    }
+
+    /// Moves the primary replica of a partition to the given node.
+    /// An empty node name lets the cluster pick the node.
+    pub async fn move_primary(
+        &self,
+        desc: &MovePrimaryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<MovePrimaryResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.move_primary_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(MovePrimaryResult::from(res))
+    }
+
+    /// Moves a secondary replica of a partition from the current node to the new node.
+    /// An empty new node name lets the cluster pick the node.
+    pub async fn move_secondary(
+        &self,
+        desc: &MoveSecondaryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<MoveSecondaryResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.move_secondary_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_Result().as_ref().unwrap() };
+        Ok(MoveSecondaryResult::from(res))
+    }
+}
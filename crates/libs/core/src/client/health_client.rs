@@ -6,18 +6,21 @@
 use std::time::Duration;
 
 use mssf_com::{
-    FabricClient::{IFabricHealthClient4, IFabricNodeHealthResult},
+    FabricClient::{
+        IFabricGetClusterHealthChunkResult, IFabricHealthClient4, IFabricNodeHealthResult,
+    },
     FabricTypes::{
-        FABRIC_APPLICATION_HEALTH_REPORT, FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION,
-        FABRIC_CLUSTER_HEALTH_REPORT, FABRIC_DEPLOYED_APPLICATION_HEALTH_REPORT,
-        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_REPORT, FABRIC_HEALTH_INFORMATION,
-        FABRIC_HEALTH_REPORT, FABRIC_HEALTH_REPORT_KIND_APPLICATION,
+        FABRIC_APPLICATION_HEALTH_REPORT, FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION,
+        FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION, FABRIC_CLUSTER_HEALTH_REPORT,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_REPORT, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_REPORT,
+        FABRIC_HEALTH_INFORMATION, FABRIC_HEALTH_REPORT, FABRIC_HEALTH_REPORT_KIND_APPLICATION,
         FABRIC_HEALTH_REPORT_KIND_CLUSTER, FABRIC_HEALTH_REPORT_KIND_DEPLOYED_APPLICATION,
         FABRIC_HEALTH_REPORT_KIND_DEPLOYED_SERVICE_PACKAGE, FABRIC_HEALTH_REPORT_KIND_INVALID,
         FABRIC_HEALTH_REPORT_KIND_NODE, FABRIC_HEALTH_REPORT_KIND_PARTITION,
         FABRIC_HEALTH_REPORT_KIND_SERVICE, FABRIC_HEALTH_REPORT_KIND_STATEFUL_SERVICE_REPLICA,
-        FABRIC_HEALTH_REPORT_KIND_STATELESS_SERVICE_INSTANCE, FABRIC_NODE_HEALTH_QUERY_DESCRIPTION,
-        FABRIC_NODE_HEALTH_REPORT, FABRIC_PARTITION_HEALTH_REPORT, FABRIC_SERVICE_HEALTH_REPORT,
+        FABRIC_HEALTH_REPORT_KIND_STATELESS_SERVICE_INSTANCE, FABRIC_HEALTH_REPORT_SEND_OPTIONS,
+        FABRIC_NODE_HEALTH_QUERY_DESCRIPTION, FABRIC_NODE_HEALTH_REPORT,
+        FABRIC_PARTITION_HEALTH_REPORT, FABRIC_SERVICE_HEALTH_REPORT,
         FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_REPORT,
         FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_REPORT, FABRIC_URI,
     },
@@ -27,7 +30,10 @@ use crate::{
     mem::{BoxPool, GetRawWithBoxPool},
     runtime::executor::BoxedCancelToken,
     sync::{FabricReceiver, fabric_begin_end_proxy},
-    types::{ClusterHealth, HealthReport, NodeHealthQueryDescription, NodeHealthResult},
+    types::{
+        ClusterHealth, ClusterHealthChunk, ClusterHealthChunkQueryDescription, HealthReport,
+        HealthReportSendOption, NodeHealthQueryDescription, NodeHealthResult,
+    },
 };
 
 /// Provides functionality to perform health related operations, like report and query health.
@@ -60,13 +66,31 @@ impl HealthClient {
     /// Read more about [connecting to a cluster using the FabricClient APIs](https://learn.microsoft.com/en-us/azure/service-fabric/service-fabric-connect-to-secure-cluster).
     /// For more information about health reporting, see [Service Fabric health monitoring](https://learn.microsoft.com/en-us/azure/service-fabric/service-fabric-health-introduction).
     pub fn report_health(&self, health_report: &HealthReport) -> crate::Result<()> {
+        self.report_health_with_options(health_report, None)
+    }
+
+    /// Reports health on a Service Fabric entity with send options.
+    /// With `immediate` set, the report is sent to the health store right away
+    /// instead of being batched by the health client.
+    /// This is useful for reports that must be seen before the process exits,
+    /// but sending many immediate reports increases the load on the cluster.
+    pub fn report_health_with_options(
+        &self,
+        health_report: &HealthReport,
+        send_options: Option<&HealthReportSendOption>,
+    ) -> crate::Result<()> {
+        let send_options = send_options.map(FABRIC_HEALTH_REPORT_SEND_OPTIONS::from);
+        let send = |report: &FABRIC_HEALTH_REPORT| match send_options.as_ref() {
+            Some(opt) => unsafe { self.com.ReportHealth2(report, opt) },
+            None => unsafe { self.com.ReportHealth(report) },
+        };
         match health_report {
             HealthReport::Invalid => {
                 let fabric_health_report = FABRIC_HEALTH_REPORT {
                     Kind: FABRIC_HEALTH_REPORT_KIND_INVALID,
                     Value: std::ptr::null_mut(),
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::StatefulServiceReplica(health_report) => {
                 let fabric_health_info =
@@ -81,7 +105,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_STATEFUL_SERVICE_REPLICA,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::StatelessServiceInstance(health_report) => {
                 let fabric_health_info =
@@ -96,7 +120,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_STATELESS_SERVICE_INSTANCE,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::Partition(health_report) => {
                 let fabric_health_info =
@@ -110,7 +134,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_PARTITION,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::Node(health_report) => {
                 let fabric_health_info =
@@ -124,7 +148,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_NODE,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::Service(health_report) => {
                 let fabric_health_info =
@@ -138,7 +162,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_SERVICE,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::Application(health_report) => {
                 let fabric_health_info =
@@ -152,7 +176,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_APPLICATION,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::DeployedApplication(health_report) => {
                 let fabric_health_info =
@@ -167,7 +191,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_DEPLOYED_APPLICATION,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::DeployedServicePackage(health_report) => {
                 let fabric_health_info =
@@ -183,7 +207,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_DEPLOYED_SERVICE_PACKAGE,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
             HealthReport::Cluster(health_report) => {
                 let fabric_health_info =
@@ -196,7 +220,7 @@ impl HealthClient {
                     Kind: FABRIC_HEALTH_REPORT_KIND_CLUSTER,
                     Value: &fabric_health_report_value as *const _ as *mut _,
                 };
-                unsafe { self.com.ReportHealth(&fabric_health_report) }
+                send(&fabric_health_report)
             }
         }.map_err(crate::Error::from)
     }
@@ -237,6 +261,23 @@ impl HealthClient {
         )
     }
 
+    fn get_cluster_health_chunk_internal(
+        &self,
+        desc: &FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetClusterHealthChunkResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterHealthChunk(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterHealthChunk(ctx) },
+            cancellation_token,
+        )
+    }
+
     fn get_application_health_internal(
         &self,
         desc: &mssf_com::FabricTypes::FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION,
@@ -301,6 +342,42 @@ impl HealthClient {
             cancellation_token,
         )
     }
+
+    fn get_deployed_application_health_internal(
+        &self,
+        desc: &mssf_com::FabricTypes::FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<mssf_com::FabricClient::IFabricDeployedApplicationHealthResult>>
+    {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedApplicationHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedApplicationHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+    fn get_deployed_service_package_health_internal(
+        &self,
+        desc: &mssf_com::FabricTypes::FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<
+        crate::Result<mssf_com::FabricClient::IFabricDeployedServicePackageHealthResult>,
+    > {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedServicePackageHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedServicePackageHealth2(ctx) },
+            cancellation_token,
+        )
+    }
 }
 
 impl HealthClient {
@@ -343,6 +420,27 @@ impl HealthClient {
         Ok(ClusterHealth::from(&com))
     }
 
+    /// Gets the health states of the cluster and of the entities matching
+    /// the filters in the description, in a single call.
+    pub async fn get_cluster_health_chunk(
+        &self,
+        desc: &ClusterHealthChunkQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ClusterHealthChunk> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let desc_raw = desc.get_raw_with_pool(&mut pool);
+            self.get_cluster_health_chunk_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ClusterHealthChunk::from(&com))
+    }
+
     /// Gets the health of an application.
     pub async fn get_application_health(
         &self,
@@ -421,4 +519,44 @@ impl HealthClient {
         .await??;
         Ok(crate::types::ReplicaHealthResult::from(&com))
     }
+
+    /// Gets the health of an application deployed on a node.
+    pub async fn get_deployed_application_health(
+        &self,
+        desc: &crate::types::DeployedApplicationHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<crate::types::DeployedApplicationHealth> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let desc_raw = desc.get_raw_with_pool(&mut pool);
+            self.get_deployed_application_health_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(crate::types::DeployedApplicationHealth::from(&com))
+    }
+
+    /// Gets the health of a service package deployed on a node.
+    pub async fn get_deployed_service_package_health(
+        &self,
+        desc: &crate::types::DeployedServicePackageHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<crate::types::DeployedServicePackageHealth> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let desc_raw = desc.get_raw_with_pool(&mut pool);
+            self.get_deployed_service_package_health_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(crate::types::DeployedServicePackageHealth::from(&com))
+    }
 }
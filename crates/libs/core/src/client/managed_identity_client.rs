@@ -0,0 +1,87 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::FabricClient::{
+    IFabricManagedIdentityClient, IFabricManagedIdentityTokenServiceDescription,
+};
+
+use crate::{
+    PCWSTR, WString,
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::ManagedIdentityTokenServiceDescription,
+};
+
+/// Managed Identity Client
+/// SF exposes no API to fetch managed identity tokens. This client only gets
+/// the description of the token service, which issues the tokens.
+#[derive(Debug, Clone)]
+pub struct ManagedIdentityClient {
+    com: IFabricManagedIdentityClient,
+}
+
+impl ManagedIdentityClient {
+    pub fn get_com(&self) -> IFabricManagedIdentityClient {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl ManagedIdentityClient {
+    fn get_token_service_description_internal(
+        &self,
+        node_name: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricManagedIdentityTokenServiceDescription>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetTokenServiceDescription(node_name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetTokenServiceDescription(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricManagedIdentityClient> for ManagedIdentityClient {
+    fn from(com: IFabricManagedIdentityClient) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ManagedIdentityClient> for IFabricManagedIdentityClient {
+    fn from(value: ManagedIdentityClient) -> Self {
+        value.com
+    }
+}
+
+// public implementation block - tokio required
+
+impl ManagedIdentityClient {
+    /// Gets the token service description of the node.
+    /// Services running on the node request tokens for a resource from
+    /// `application_token_endpoint`.
+    pub async fn get_token_service_description(
+        &self,
+        node_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ManagedIdentityTokenServiceDescription> {
+        let com = self
+            .get_token_service_description_internal(
+                node_name.as_pcwstr(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+            .await??;
+        Ok(ManagedIdentityTokenServiceDescription::from(&com))
+    }
+}
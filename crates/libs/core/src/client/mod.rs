@@ -10,9 +10,12 @@ use crate::{
 use connection::{ClientConnectionEventHandlerBridge, LambdaClientConnectionNotificationHandler};
 use health_client::HealthClient;
 use mssf_com::FabricClient::{
-    IFabricClientConnectionEventHandler, IFabricClientSettings2, IFabricHealthClient4,
-    IFabricPropertyManagementClient2, IFabricQueryClient13, IFabricServiceManagementClient8,
-    IFabricServiceNotificationEventHandler,
+    IFabricApplicationManagementClient10, IFabricClientConnectionEventHandler,
+    IFabricClientSettings2, IFabricClusterManagementClient16, IFabricFaultManagementClient3,
+    IFabricHealthClient4, IFabricManagedIdentityClient, IFabricPropertyManagementClient2,
+    IFabricQueryClient13, IFabricRepairManagementClient2, IFabricSecretStoreClient,
+    IFabricServiceGroupManagementClient4, IFabricServiceManagementClient8,
+    IFabricServiceNotificationEventHandler, IFabricTestManagementClient5,
 };
 use notification::{
     LambdaServiceNotificationHandler, ServiceNotificationEventHandler,
@@ -21,20 +24,34 @@ use notification::{
 
 use crate::types::ClientRole;
 
-use self::{query_client::QueryClient, svc_mgmt_client::ServiceManagementClient};
+use self::{
+    app_mgmt_client::ApplicationManagementClient, cluster_mgmt_client::ClusterManagementClient,
+    fault_mgmt_client::FaultManagementClient, managed_identity_client::ManagedIdentityClient,
+    query_client::QueryClient, repair_mgmt_client::RepairManagementClient,
+    secret_store_client::SecretStoreClient, svc_group_mgmt_client::ServiceGroupManagementClient,
+    svc_mgmt_client::ServiceManagementClient, test_mgmt_client::TestManagementClient,
+};
 
 mod connection;
 mod notification;
 
 // Export public client modules
+pub mod app_mgmt_client;
+pub mod cluster_mgmt_client;
+pub mod fault_mgmt_client;
 pub mod health_client;
+pub mod managed_identity_client;
 mod property_client;
 pub mod query_client;
+pub mod repair_mgmt_client;
+pub mod secret_store_client;
+pub mod svc_group_mgmt_client;
 pub mod svc_mgmt_client;
+pub mod test_mgmt_client;
 // reexport
 pub use connection::{ClaimsRetrievalMetadata, GatewayInformationResult};
 pub use notification::ServiceNotification;
-pub use property_client::PropertyManagementClient;
+pub use property_client::{PropertyEnumerationStream, PropertyManagementClient};
 
 #[cfg(test)]
 mod tests;
@@ -289,6 +306,7 @@ pub struct FabricClient {
     service_client: ServiceManagementClient,
     query_client: QueryClient,
     health_client: HealthClient,
+    settings: IFabricClientSettings2,
 }
 
 impl FabricClient {
@@ -308,11 +326,13 @@ impl FabricClient {
             .unwrap();
         let com_query_client = com.clone().cast::<IFabricQueryClient13>().unwrap();
         let com_health_client = com.clone().cast::<IFabricHealthClient4>().unwrap();
+        let settings = com.clone().cast::<IFabricClientSettings2>().unwrap();
         Self {
             property_client: PropertyManagementClient::from(com_property_client),
             service_client: ServiceManagementClient::from(com_service_client),
             query_client: QueryClient::from(com_query_client),
             health_client: HealthClient::from(com_health_client),
+            settings,
         }
     }
 
@@ -331,8 +351,73 @@ impl FabricClient {
         &self.service_client
     }
 
+    /// Get the client for managing service groups.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_service_group_manager(&self) -> crate::Result<ServiceGroupManagementClient> {
+        let com = self
+            .settings
+            .cast::<IFabricServiceGroupManagementClient4>()?;
+        Ok(ServiceGroupManagementClient::from(com))
+    }
+
     /// Get the client for get/set Service Fabric health properties.
     pub fn get_health_manager(&self) -> &HealthClient {
         &self.health_client
     }
+
+    /// Get the client for managing application types and applications.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_application_manager(&self) -> crate::Result<ApplicationManagementClient> {
+        let com = self
+            .settings
+            .cast::<IFabricApplicationManagementClient10>()?;
+        Ok(ApplicationManagementClient::from(com))
+    }
+
+    /// Get the client for managing nodes and the cluster upgrade.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_cluster_manager(&self) -> crate::Result<ClusterManagementClient> {
+        let com = self.settings.cast::<IFabricClusterManagementClient16>()?;
+        Ok(ClusterManagementClient::from(com))
+    }
+
+    /// Get the client for injecting faults into nodes, code packages and replicas.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_fault_manager(&self) -> crate::Result<FaultManagementClient> {
+        let com = self.settings.cast::<IFabricFaultManagementClient3>()?;
+        Ok(FaultManagementClient::from(com))
+    }
+
+    /// Get the client for partition test commands, node transitions and Chaos.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_test_manager(&self) -> crate::Result<TestManagementClient> {
+        let com = self.settings.cast::<IFabricTestManagementClient5>()?;
+        Ok(TestManagementClient::from(com))
+    }
+
+    /// Get the client for creating and driving repair tasks.
+    /// Fails if the Service Fabric runtime does not support the client interface.
+    pub fn get_repair_manager(&self) -> crate::Result<RepairManagementClient> {
+        let com = self.settings.cast::<IFabricRepairManagementClient2>()?;
+        Ok(RepairManagementClient::from(com))
+    }
+
+    /// Get the client for the central secret store.
+    /// Fails if the Service Fabric runtime does not support the secret store.
+    pub fn get_secret_store_client(&self) -> crate::Result<SecretStoreClient> {
+        let com = self.settings.cast::<IFabricSecretStoreClient>()?;
+        Ok(SecretStoreClient::from(com))
+    }
+
+    /// Get the client for the managed identity token service.
+    /// Fails if the Service Fabric runtime does not support managed identity.
+    pub fn get_managed_identity_client(&self) -> crate::Result<ManagedIdentityClient> {
+        let com = self.settings.cast::<IFabricManagedIdentityClient>()?;
+        Ok(ManagedIdentityClient::from(com))
+    }
+
+    /// Get the settings currently in effect for this client.
+    pub fn get_settings(&self) -> crate::Result<FabricClientSettings> {
+        FabricClientSettings::get(&self.settings)
+    }
 }
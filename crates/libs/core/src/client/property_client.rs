@@ -3,13 +3,24 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use std::time::Duration;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, ready},
+    time::Duration,
+};
+
+use futures_core::Stream;
 
 use crate::{
     WString,
+    mem::{BoxPool, GetRawWithBoxPool},
     runtime::executor::BoxedCancelToken,
-    sync::{FabricReceiver, fabric_begin_end_proxy},
-    types::{NameEnumerationResult, PropertyMetadataResult, PropertyValueResult, Uri},
+    sync::{FabricReceiver, SimpleCancelToken, fabric_begin_end_proxy},
+    types::{
+        NameEnumerationResult, PropertyBatchOperation, PropertyBatchResult,
+        PropertyEnumerationResult, PropertyMetadataResult, PropertyValueResult, Uri,
+    },
 };
 use mssf_com::{
     FabricClient::{
@@ -309,9 +320,6 @@ impl PropertyManagementClient {
         )
     }
 
-    // TODO: implement this
-    // Batch operations are not supported yet.
-    #[allow(dead_code)]
     fn submit_property_batch_internal(
         &self,
         name: &Uri,
@@ -335,8 +343,6 @@ impl PropertyManagementClient {
         )
     }
 
-    // TODO: implement this
-    #[allow(dead_code)]
     fn enumerate_properties_internal(
         &self,
         name: &Uri,
@@ -621,4 +627,147 @@ impl PropertyManagementClient {
         .await?
         .map(PropertyValueResult::from_com)
     }
+
+    /// Submits a batch of property operations on a SF name.
+    /// The batch is atomic. If an operation fails, for example a check operation,
+    /// the batch is not applied and the result contains the failed operation index.
+    pub async fn submit_property_batch(
+        &self,
+        name: &Uri,
+        operations: &[PropertyBatchOperation],
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PropertyBatchResult> {
+        let rx = {
+            let mut pool = BoxPool::new();
+            let batch = operations
+                .iter()
+                .map(|op| op.get_raw_with_pool(&mut pool))
+                .collect::<Vec<_>>();
+            self.submit_property_batch_internal(
+                name,
+                &batch,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        };
+        rx.await?
+            .map(|(index, com)| PropertyBatchResult::from_com(index, com))
+    }
+
+    /// Enumerates properties of a SF name, one page at a time.
+    /// Pass the previous page to continue the enumeration,
+    /// until the enumeration status has no more data.
+    pub async fn enumerate_properties(
+        &self,
+        name: &Uri,
+        include_values: bool,
+        prev: Option<&PropertyEnumerationResult>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PropertyEnumerationResult> {
+        self.enumerate_properties_internal(
+            name,
+            include_values,
+            prev.map(|x| x.as_com()),
+            timeout.as_millis().try_into()?,
+            cancellation_token,
+        )
+        .await?
+        .map(PropertyEnumerationResult::from_com)
+    }
+
+    /// Enumerates all properties of a SF name as a stream.
+    /// Pages are fetched as the stream is consumed, each with the given timeout.
+    /// Values are only included if include_values is set.
+    /// The stream ends after the first error.
+    pub fn enumerate_properties_stream(
+        &self,
+        name: &Uri,
+        include_values: bool,
+        timeout: Duration,
+    ) -> PropertyEnumerationStream {
+        PropertyEnumerationStream {
+            client: self.clone(),
+            name: name.clone(),
+            include_values,
+            timeout,
+            pending: None,
+            page: None,
+            index: 0,
+            done: false,
+        }
+    }
+}
+
+type PropertyPageFuture =
+    Pin<Box<dyn Future<Output = crate::Result<PropertyEnumerationResult>> + Send>>;
+
+/// Stream of all properties under a SF name.
+/// Created by [`PropertyManagementClient::enumerate_properties_stream`].
+/// Dropping the stream cancels the pending page request.
+pub struct PropertyEnumerationStream {
+    client: PropertyManagementClient,
+    name: Uri,
+    include_values: bool,
+    timeout: Duration,
+    pending: Option<PropertyPageFuture>,
+    page: Option<PropertyEnumerationResult>,
+    // next property index in the current page.
+    index: u32,
+    done: bool,
+}
+
+impl Stream for PropertyEnumerationStream {
+    type Item = crate::Result<PropertyValueResult>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            if let Some(page) = &this.page {
+                if this.index < page.get_property_count() {
+                    let item = page.get_property(this.index);
+                    this.index += 1;
+                    return Poll::Ready(Some(item));
+                }
+                if !page.get_enumeration_status().has_more_data() {
+                    this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+            let fut = this.pending.get_or_insert_with(|| {
+                let client = this.client.clone();
+                let name = this.name.clone();
+                let include_values = this.include_values;
+                let timeout = this.timeout;
+                let prev = this.page.clone();
+                Box::pin(async move {
+                    client
+                        .enumerate_properties(
+                            &name,
+                            include_values,
+                            prev.as_ref(),
+                            timeout,
+                            Some(SimpleCancelToken::new_boxed()),
+                        )
+                        .await
+                })
+            });
+            let res = ready!(fut.as_mut().poll(cx));
+            this.pending = None;
+            match res {
+                Ok(page) => {
+                    this.page = Some(page);
+                    this.index = 0;
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
 }
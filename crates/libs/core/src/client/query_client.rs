@@ -6,27 +6,56 @@ use std::time::Duration;
 
 use mssf_com::{
     FabricClient::{
-        IFabricGetApplicationListResult2, IFabricGetDeployedServiceReplicaDetailResult,
-        IFabricGetNodeListResult2, IFabricGetPartitionListResult2,
-        IFabricGetPartitionLoadInformationResult, IFabricGetReplicaListResult2,
+        IFabricGetApplicationListResult2, IFabricGetApplicationNameResult,
+        IFabricGetApplicationTypePagedListResult, IFabricGetClusterLoadInformationResult,
+        IFabricGetDeployedApplicationPagedListResult, IFabricGetDeployedCodePackageListResult,
+        IFabricGetDeployedReplicaListResult, IFabricGetDeployedServicePackagePagedListResult,
+        IFabricGetDeployedServiceReplicaDetailResult, IFabricGetNodeListResult2,
+        IFabricGetNodeLoadInformationResult, IFabricGetPartitionListResult2,
+        IFabricGetPartitionLoadInformationResult, IFabricGetProvisionedCodeVersionListResult,
+        IFabricGetProvisionedConfigVersionListResult, IFabricGetReplicaListResult2,
+        IFabricGetReplicaLoadInformationResult, IFabricGetServiceNameResult,
+        IFabricGetServiceTypeListResult, IFabricGetUnplacedReplicaInformationResult,
         IFabricQueryClient13,
     },
     FabricTypes::{
-        FABRIC_APPLICATION_QUERY_DESCRIPTION,
-        FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION, FABRIC_NODE_QUERY_DESCRIPTION,
+        FABRIC_APPLICATION_NAME_QUERY_DESCRIPTION, FABRIC_APPLICATION_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION, FABRIC_NODE_QUERY_DESCRIPTION,
+        FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION,
+        FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION,
         FABRIC_PARTITION_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION,
+        FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION, FABRIC_SERVICE_NAME_QUERY_DESCRIPTION,
         FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION, FABRIC_SERVICE_QUERY_DESCRIPTION,
-        FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION, FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION,
+        FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION,
+        PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION,
     },
 };
+use windows_core::GUID;
 
-use crate::mem::{BoxPool, GetRawWithBoxPool};
+use crate::mem::{BoxPool, GetRaw, GetRawWithBoxPool};
 
 use crate::types::{
+    ApplicationTypeListResult, ApplicationTypeQueryDescription, ClusterLoadInformation,
+    DeployedApplicationListResult, DeployedApplicationQueryDescription,
+    DeployedCodePackageListResult, DeployedCodePackageQueryDescription,
+    DeployedServicePackageListResult, DeployedServicePackageQueryDescription,
     DeployedServiceReplicaDetailQueryDescription, DeployedServiceReplicaDetailQueryResult,
-    GetPartitionLoadInformationResult, NodeListResult, NodeQueryDescription,
-    PartitionLoadInformationQueryDescription, ServicePartitionList,
-    ServicePartitionQueryDescription, ServiceReplicaList, ServiceReplicaQueryDescription,
+    DeployedServiceReplicaListResult, DeployedServiceReplicaQueryDescription,
+    GetPartitionLoadInformationResult, NodeListResult, NodeLoadInformation,
+    NodeLoadInformationQueryDescription, NodeQueryDescription,
+    PartitionLoadInformationQueryDescription, ProvisionedFabricCodeVersionList,
+    ProvisionedFabricCodeVersionQueryDescription, ProvisionedFabricConfigVersionList,
+    ProvisionedFabricConfigVersionQueryDescription, ReplicaLoadInformation,
+    ReplicaLoadInformationQueryDescription, ServicePartitionList, ServicePartitionQueryDescription,
+    ServiceReplicaList, ServiceReplicaQueryDescription, ServiceTypeListResult,
+    ServiceTypeQueryDescription, UnplacedReplicaInformation,
+    UnplacedReplicaInformationQueryDescription, Uri,
 };
 use crate::{
     runtime::executor::BoxedCancelToken,
@@ -163,6 +192,247 @@ impl QueryClient {
             cancellation_token,
         )
     }
+
+    fn get_application_type_paged_list_internal(
+        &self,
+        desc: &PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetApplicationTypePagedListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationTypePagedList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationTypePagedList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_type_list_internal(
+        &self,
+        desc: &FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetServiceTypeListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceTypeList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceTypeList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_application_paged_list_internal(
+        &self,
+        desc: &FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetDeployedApplicationPagedListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedApplicationPagedList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedApplicationPagedList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_service_package_paged_list_internal(
+        &self,
+        desc: &FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetDeployedServicePackagePagedListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedServicePackagePagedList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedServicePackagePagedList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_code_package_list_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetDeployedCodePackageListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedCodePackageList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedCodePackageList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_replica_list_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetDeployedReplicaListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedReplicaList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedReplicaList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_node_load_information_internal(
+        &self,
+        desc: &FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetNodeLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetNodeLoadInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetNodeLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_cluster_load_information_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetClusterLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterLoadInformation(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_replica_load_information_internal(
+        &self,
+        desc: &FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetReplicaLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetReplicaLoadInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetReplicaLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_unplaced_replica_information_internal(
+        &self,
+        desc: &FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetUnplacedReplicaInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetUnplacedReplicaInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetUnplacedReplicaInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_provisioned_fabric_code_version_list_internal(
+        &self,
+        desc: &FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetProvisionedCodeVersionListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetProvisionedFabricCodeVersionList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetProvisionedFabricCodeVersionList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_provisioned_fabric_config_version_list_internal(
+        &self,
+        desc: &FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetProvisionedConfigVersionListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetProvisionedFabricConfigVersionList(
+                    desc,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndGetProvisionedFabricConfigVersionList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_name_internal(
+        &self,
+        desc: &FABRIC_SERVICE_NAME_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetServiceNameResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceName(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceName(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_name_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_NAME_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetApplicationNameResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationName(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationName(ctx) },
+            cancellation_token,
+        )
+    }
 }
 
 impl From<IFabricQueryClient13> for QueryClient {
@@ -290,4 +560,254 @@ impl QueryClient {
         .await??;
         Ok(DeployedServiceReplicaDetailQueryResult::new(com))
     }
+
+    /// Lists provisioned application types. Paged.
+    pub async fn get_application_type_paged_list(
+        &self,
+        desc: &ApplicationTypeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationTypeListResult> {
+        let com = {
+            let raw = desc.get_raw();
+            self.get_application_type_paged_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ApplicationTypeListResult::from(&com))
+    }
+
+    pub async fn get_service_type_list(
+        &self,
+        desc: &ServiceTypeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceTypeListResult> {
+        let com = {
+            let raw: FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION = desc.into();
+            self.get_service_type_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ServiceTypeListResult::from(&com))
+    }
+
+    pub async fn get_deployed_application_paged_list(
+        &self,
+        desc: &DeployedApplicationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedApplicationListResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.get_deployed_application_paged_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(DeployedApplicationListResult::from(&com))
+    }
+
+    pub async fn get_deployed_service_package_paged_list(
+        &self,
+        desc: &DeployedServicePackageQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedServicePackageListResult> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.get_deployed_service_package_paged_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(DeployedServicePackageListResult::from(&com))
+    }
+
+    pub async fn get_deployed_code_package_list(
+        &self,
+        desc: &DeployedCodePackageQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedCodePackageListResult> {
+        let com = {
+            let raw: FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION = desc.into();
+            self.get_deployed_code_package_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(DeployedCodePackageListResult::from(&com))
+    }
+
+    pub async fn get_deployed_replica_list(
+        &self,
+        desc: &DeployedServiceReplicaQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedServiceReplicaListResult> {
+        let com = {
+            let raw: FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION = desc.into();
+            self.get_deployed_replica_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(DeployedServiceReplicaListResult::from(&com))
+    }
+
+    pub async fn get_node_load_information(
+        &self,
+        desc: &NodeLoadInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeLoadInformation> {
+        let com = {
+            let raw: FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            self.get_node_load_information_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(NodeLoadInformation::from(&com))
+    }
+
+    pub async fn get_cluster_load_information(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ClusterLoadInformation> {
+        let com = self
+            .get_cluster_load_information_internal(timeout.as_millis() as u32, cancellation_token)
+            .await??;
+        Ok(ClusterLoadInformation::from(&com))
+    }
+
+    pub async fn get_replica_load_information(
+        &self,
+        desc: &ReplicaLoadInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ReplicaLoadInformation> {
+        let com = {
+            let raw: FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            self.get_replica_load_information_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ReplicaLoadInformation::from(&com))
+    }
+
+    pub async fn get_unplaced_replica_information(
+        &self,
+        desc: &UnplacedReplicaInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<UnplacedReplicaInformation> {
+        let com = {
+            let raw: FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            self.get_unplaced_replica_information_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(UnplacedReplicaInformation::from(&com))
+    }
+
+    pub async fn get_provisioned_fabric_code_version_list(
+        &self,
+        desc: &ProvisionedFabricCodeVersionQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ProvisionedFabricCodeVersionList> {
+        let com = {
+            let raw: FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION = desc.into();
+            self.get_provisioned_fabric_code_version_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ProvisionedFabricCodeVersionList::from(&com))
+    }
+
+    pub async fn get_provisioned_fabric_config_version_list(
+        &self,
+        desc: &ProvisionedFabricConfigVersionQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ProvisionedFabricConfigVersionList> {
+        let com = {
+            let raw: FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION = desc.into();
+            self.get_provisioned_fabric_config_version_list_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(ProvisionedFabricConfigVersionList::from(&com))
+    }
+
+    /// Resolves the name of the service that owns the partition.
+    pub async fn get_service_name(
+        &self,
+        partition_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Uri> {
+        let com = {
+            let raw = FABRIC_SERVICE_NAME_QUERY_DESCRIPTION {
+                PartitionId: partition_id,
+                Reserved: std::ptr::null_mut(),
+            };
+            self.get_service_name_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_ServiceName().as_ref().unwrap() };
+        Ok(Uri::from(res.ServiceName))
+    }
+
+    /// Resolves the name of the application that owns the service.
+    pub async fn get_application_name(
+        &self,
+        service_name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Uri> {
+        let com = {
+            let raw = FABRIC_APPLICATION_NAME_QUERY_DESCRIPTION {
+                ServiceName: service_name.as_raw(),
+                Reserved: std::ptr::null_mut(),
+            };
+            self.get_application_name_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await??;
+        let res = unsafe { com.get_ApplicationName().as_ref().unwrap() };
+        Ok(Uri::from(res.ApplicationName))
+    }
 }
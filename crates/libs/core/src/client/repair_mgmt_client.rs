@@ -0,0 +1,311 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricGetRepairTaskListResult, IFabricRepairManagementClient2},
+    FabricTypes::{
+        FABRIC_REPAIR_APPROVE_DESCRIPTION, FABRIC_REPAIR_CANCEL_DESCRIPTION,
+        FABRIC_REPAIR_DELETE_DESCRIPTION, FABRIC_REPAIR_TASK,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION, FABRIC_REPAIR_TASK_QUERY_DESCRIPTION,
+    },
+};
+
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        RepairApproveDescription, RepairCancelDescription, RepairDeleteDescription, RepairTask,
+        RepairTaskHealthPolicyUpdateDescription, RepairTaskQueryDescription,
+    },
+};
+
+/// Repair Management Client
+/// Creates repair tasks and drives them through their states for a repair executor.
+#[derive(Debug, Clone)]
+pub struct RepairManagementClient {
+    com: IFabricRepairManagementClient2,
+}
+
+impl From<IFabricRepairManagementClient2> for RepairManagementClient {
+    fn from(com: IFabricRepairManagementClient2) -> Self {
+        Self { com }
+    }
+}
+
+impl From<RepairManagementClient> for IFabricRepairManagementClient2 {
+    fn from(value: RepairManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+impl RepairManagementClient {
+    fn create_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn cancel_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_CANCEL_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCancelRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCancelRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn force_approve_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_APPROVE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginForceApproveRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndForceApproveRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_DELETE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_repair_execution_state_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateRepairExecutionState(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateRepairExecutionState(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_repair_task_list_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricGetRepairTaskListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetRepairTaskList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetRepairTaskList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_repair_task_health_policy_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateRepairTaskHealthPolicy(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateRepairTaskHealthPolicy(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl RepairManagementClient {
+    /// Creates a repair task in the Created state.
+    /// Returns the version of the new task.
+    pub async fn create_repair_task(
+        &self,
+        desc: &RepairTask,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.create_repair_task_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Requests cancellation of a repair task.
+    /// Returns the new version of the task.
+    pub async fn cancel_repair_task(
+        &self,
+        desc: &RepairCancelDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.cancel_repair_task_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Approves a repair task without waiting for the cluster to prepare.
+    /// Returns the new version of the task.
+    pub async fn force_approve_repair_task(
+        &self,
+        desc: &RepairApproveDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.force_approve_repair_task_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Deletes a completed repair task.
+    pub async fn delete_repair_task(
+        &self,
+        desc: &RepairDeleteDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.delete_repair_task_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Updates the state, executor state and result of a repair task.
+    /// Used by the repair executor to move the task along.
+    /// Returns the new version of the task.
+    pub async fn update_repair_execution_state(
+        &self,
+        desc: &RepairTask,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.update_repair_execution_state_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Updates the health check settings of a repair task.
+    /// Returns the new version of the task.
+    pub async fn update_repair_task_health_policy(
+        &self,
+        desc: &RepairTaskHealthPolicyUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.update_repair_task_health_policy_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the repair tasks matching the filters.
+    pub async fn get_repair_task_list(
+        &self,
+        desc: &RepairTaskQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Vec<RepairTask>> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.get_repair_task_list_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await??;
+        let list = unsafe { com.get_Tasks().as_ref() };
+        Ok(list
+            .map(|l| crate::iter::vec_from_raw_com(l.Count as usize, l.Items))
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,195 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricSecretReferencesResult, IFabricSecretStoreClient, IFabricSecretsResult},
+    FabricTypes::{FABRIC_SECRET_LIST, FABRIC_SECRET_REFERENCE_LIST},
+};
+
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{Secret, SecretList, SecretReference, SecretReferenceList},
+};
+
+/// Secret Store Client
+/// Manages secrets in the central secret store of the cluster.
+#[derive(Debug, Clone)]
+pub struct SecretStoreClient {
+    com: IFabricSecretStoreClient,
+}
+
+impl SecretStoreClient {
+    pub fn get_com(&self) -> IFabricSecretStoreClient {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl SecretStoreClient {
+    fn get_secrets_internal(
+        &self,
+        refs: &FABRIC_SECRET_REFERENCE_LIST,
+        include_value: bool,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricSecretsResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetSecrets(refs, include_value, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetSecrets(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn set_secrets_internal(
+        &self,
+        secrets: &FABRIC_SECRET_LIST,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricSecretsResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginSetSecrets(secrets, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndSetSecrets(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn remove_secrets_internal(
+        &self,
+        refs: &FABRIC_SECRET_REFERENCE_LIST,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricSecretReferencesResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRemoveSecrets(refs, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRemoveSecrets(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_secret_versions_internal(
+        &self,
+        refs: &FABRIC_SECRET_REFERENCE_LIST,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricSecretReferencesResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetSecretVersions(refs, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetSecretVersions(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricSecretStoreClient> for SecretStoreClient {
+    fn from(com: IFabricSecretStoreClient) -> Self {
+        Self { com }
+    }
+}
+
+impl From<SecretStoreClient> for IFabricSecretStoreClient {
+    fn from(value: SecretStoreClient) -> Self {
+        value.com
+    }
+}
+
+// public implementation block - tokio required
+
+impl SecretStoreClient {
+    /// Gets the referenced secrets. A reference without version returns the latest version.
+    /// The secret values are only returned if `include_value` is set.
+    pub async fn get_secrets(
+        &self,
+        refs: &[SecretReference],
+        include_value: bool,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<SecretList> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = refs.get_raw_with_pool(&mut pool);
+            self.get_secrets_internal(
+                &raw,
+                include_value,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(SecretList::from(&com))
+    }
+
+    /// Stores the secrets. Each secret needs a name and a version,
+    /// and an existing version cannot be overwritten with a different value.
+    /// Returns the stored secrets without values.
+    pub async fn set_secrets(
+        &self,
+        secrets: &[Secret],
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<SecretList> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = secrets.get_raw_with_pool(&mut pool);
+            self.set_secrets_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await??;
+        Ok(SecretList::from(&com))
+    }
+
+    /// Removes the referenced secrets. A reference without version removes all versions.
+    /// Returns the references that were removed.
+    pub async fn remove_secrets(
+        &self,
+        refs: &[SecretReference],
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<SecretReferenceList> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = refs.get_raw_with_pool(&mut pool);
+            self.remove_secrets_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await??;
+        Ok(SecretReferenceList::from(&com))
+    }
+
+    /// Lists the versions of the referenced secrets.
+    /// Use references without version to list all versions of each secret.
+    pub async fn get_secret_versions(
+        &self,
+        refs: &[SecretReference],
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<SecretReferenceList> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = refs.get_raw_with_pool(&mut pool);
+            self.get_secret_versions_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await??;
+        Ok(SecretReferenceList::from(&com))
+    }
+}
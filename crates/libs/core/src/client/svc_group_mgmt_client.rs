@@ -0,0 +1,230 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricServiceGroupDescriptionResult, IFabricServiceGroupManagementClient4},
+    FabricTypes::{
+        FABRIC_SERVICE_GROUP_DESCRIPTION, FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION,
+        FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION, FABRIC_URI,
+    },
+};
+
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        ServiceGroupDescription, ServiceGroupDescriptionResult,
+        ServiceGroupFromTemplateDescription, ServiceGroupUpdateDescription, Uri,
+    },
+};
+
+/// Service Group Management Client
+#[derive(Debug, Clone)]
+pub struct ServiceGroupManagementClient {
+    com: IFabricServiceGroupManagementClient4,
+}
+
+impl ServiceGroupManagementClient {
+    pub fn get_com(&self) -> IFabricServiceGroupManagementClient4 {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl ServiceGroupManagementClient {
+    fn create_service_group_internal(
+        &self,
+        desc: &FABRIC_SERVICE_GROUP_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateServiceGroup(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn create_service_group_from_template_internal(
+        &self,
+        desc: &FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateServiceGroupFromTemplate2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateServiceGroupFromTemplate2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_service_group_internal(
+        &self,
+        name: FABRIC_URI,
+        desc: &FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateServiceGroup(name, desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_service_group_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteServiceGroup(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_group_description_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricServiceGroupDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceGroupDescription(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceGroupDescription(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricServiceGroupManagementClient4> for ServiceGroupManagementClient {
+    fn from(com: IFabricServiceGroupManagementClient4) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ServiceGroupManagementClient> for IFabricServiceGroupManagementClient4 {
+    fn from(value: ServiceGroupManagementClient) -> Self {
+        value.com
+    }
+}
+
+// public implementation block - tokio required
+
+impl ServiceGroupManagementClient {
+    /// Creates a service group with its members.
+    /// All members are created together and share the partitions of the group.
+    pub async fn create_service_group(
+        &self,
+        desc: &ServiceGroupDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.create_service_group_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+    }
+
+    /// Creates a service group from a template in the application manifest.
+    pub async fn create_service_group_from_template(
+        &self,
+        desc: &ServiceGroupFromTemplateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.create_service_group_from_template_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Updates the service group as a whole, e.g. instance count or replica set size.
+    /// Members cannot be added or removed after creation.
+    pub async fn update_service_group(
+        &self,
+        name: &Uri,
+        desc: &ServiceGroupUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.update_service_group_internal(
+                name.as_raw(),
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Deletes the service group and all its members.
+    pub async fn delete_service_group(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.delete_service_group_internal(
+            name.as_raw(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+    }
+
+    pub async fn get_service_group_description(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceGroupDescriptionResult> {
+        let com = self
+            .get_service_group_description_internal(
+                name.as_raw(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+            .await??;
+        Ok(ServiceGroupDescriptionResult::from(&com))
+    }
+}
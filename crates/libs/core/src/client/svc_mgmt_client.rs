@@ -9,23 +9,29 @@ use crate::{
     PCWSTR, WString,
     mem::{BoxPool, GetRaw, GetRawWithBoxPool},
     runtime::executor::BoxedCancelToken,
-    types::Uri,
+    strings::StringResult,
+    types::{ServiceDescription, ServiceFromTemplateDescription, Uri},
 };
 use mssf_com::{
-    FabricClient::{IFabricResolvedServicePartitionResult, IFabricServiceManagementClient8},
+    FabricClient::{
+        IFabricResolvedServicePartitionResult, IFabricServiceDescriptionResult,
+        IFabricServiceManagementClient8,
+    },
+    FabricCommon::IFabricStringResult,
     FabricTypes::{
         FABRIC_DELETE_SERVICE_DESCRIPTION, FABRIC_PARTITION_KEY_TYPE,
         FABRIC_PARTITION_KEY_TYPE_INT64, FABRIC_PARTITION_KEY_TYPE_INVALID,
         FABRIC_PARTITION_KEY_TYPE_NONE, FABRIC_PARTITION_KEY_TYPE_STRING,
         FABRIC_REMOVE_REPLICA_DESCRIPTION, FABRIC_RESOLVED_SERVICE_ENDPOINT,
         FABRIC_RESTART_REPLICA_DESCRIPTION, FABRIC_SERVICE_DESCRIPTION,
-        FABRIC_SERVICE_ENDPOINT_ROLE, FABRIC_SERVICE_NOTIFICATION_FILTER_DESCRIPTION,
-        FABRIC_SERVICE_PARTITION_KIND, FABRIC_SERVICE_PARTITION_KIND_INT64_RANGE,
-        FABRIC_SERVICE_PARTITION_KIND_INVALID, FABRIC_SERVICE_PARTITION_KIND_NAMED,
-        FABRIC_SERVICE_PARTITION_KIND_SINGLETON, FABRIC_SERVICE_ROLE_INVALID,
-        FABRIC_SERVICE_ROLE_STATEFUL_AUXILIARY, FABRIC_SERVICE_ROLE_STATEFUL_PRIMARY,
-        FABRIC_SERVICE_ROLE_STATEFUL_PRIMARY_AUXILIARY, FABRIC_SERVICE_ROLE_STATEFUL_SECONDARY,
-        FABRIC_SERVICE_ROLE_STATELESS, FABRIC_SERVICE_UPDATE_DESCRIPTION, FABRIC_URI,
+        FABRIC_SERVICE_ENDPOINT_ROLE, FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION,
+        FABRIC_SERVICE_NOTIFICATION_FILTER_DESCRIPTION, FABRIC_SERVICE_PARTITION_KIND,
+        FABRIC_SERVICE_PARTITION_KIND_INT64_RANGE, FABRIC_SERVICE_PARTITION_KIND_INVALID,
+        FABRIC_SERVICE_PARTITION_KIND_NAMED, FABRIC_SERVICE_PARTITION_KIND_SINGLETON,
+        FABRIC_SERVICE_ROLE_INVALID, FABRIC_SERVICE_ROLE_STATEFUL_AUXILIARY,
+        FABRIC_SERVICE_ROLE_STATEFUL_PRIMARY, FABRIC_SERVICE_ROLE_STATEFUL_PRIMARY_AUXILIARY,
+        FABRIC_SERVICE_ROLE_STATEFUL_SECONDARY, FABRIC_SERVICE_ROLE_STATELESS,
+        FABRIC_SERVICE_UPDATE_DESCRIPTION, FABRIC_URI,
     },
 };
 
@@ -216,6 +222,65 @@ impl ServiceManagementClient {
             cancellation_token,
         )
     }
+
+    fn create_service_from_template_internal(
+        &self,
+        desc: &FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateServiceFromTemplate2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateServiceFromTemplate2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_description_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricServiceDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceDescription(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceDescription(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_manifest_internal(
+        &self,
+        application_type_name: PCWSTR,
+        application_type_version: PCWSTR,
+        service_manifest_name: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricStringResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceManifest(
+                    application_type_name,
+                    application_type_version,
+                    service_manifest_name,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndGetServiceManifest(ctx) },
+            cancellation_token,
+        )
+    }
 }
 
 impl From<IFabricServiceManagementClient8> for ServiceManagementClient {
@@ -409,6 +474,63 @@ impl ServiceManagementClient {
         }
         .await?
     }
+
+    /// Creates a service from a service template in the application manifest.
+    pub async fn create_service_from_template(
+        &self,
+        desc: &ServiceFromTemplateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw = desc.get_raw();
+            self.create_service_from_template_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the description of a running service as stored in the cluster.
+    /// Fields not yet supported by `ServiceDescription`, i.e. metrics, correlations,
+    /// placement policies and scaling policies, are not read.
+    pub async fn get_service_description(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceDescription> {
+        let com = self
+            .get_service_description_internal(
+                name.as_raw(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+            .await??;
+        ServiceDescription::try_from(&com)
+    }
+
+    /// Gets the service manifest xml of a provisioned application type.
+    pub async fn get_service_manifest(
+        &self,
+        application_type_name: &WString,
+        application_type_version: &WString,
+        service_manifest_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<WString> {
+        self.get_service_manifest_internal(
+            application_type_name.as_pcwstr(),
+            application_type_version.as_pcwstr(),
+            service_manifest_name.as_pcwstr(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map(|s| StringResult::from(&s).into_inner())
+    }
 }
 
 // Handle to the registered service notification filter
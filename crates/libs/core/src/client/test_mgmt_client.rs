@@ -0,0 +1,790 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{
+    future::Future,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use mssf_com::{
+    FabricClient::{
+        IFabricChaosDescriptionResult, IFabricChaosReportResult,
+        IFabricChaosScheduleDescriptionResult, IFabricNodeTransitionProgressResult,
+        IFabricPartitionDataLossProgressResult, IFabricPartitionQuorumLossProgressResult,
+        IFabricPartitionRestartProgressResult, IFabricTestManagementClient5,
+    },
+    FabricTypes::{
+        FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION, FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION,
+        FABRIC_GET_CHAOS_REPORT_DESCRIPTION, FABRIC_NODE_TRANSITION_DESCRIPTION,
+        FABRIC_START_CHAOS_DESCRIPTION, FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION,
+        FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION, FABRIC_START_PARTITION_RESTART_DESCRIPTION,
+    },
+};
+
+use crate::{
+    ErrorCode, GUID,
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    runtime::executor::{BoxedCancelToken, Timer},
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        CancelTestCommandDescription, ChaosDescription, ChaosReport, ChaosScheduleDescription,
+        GetChaosReportDescription, NodeTransitionDescription, NodeTransitionProgress,
+        PartitionDataLossProgress, PartitionQuorumLossProgress, PartitionRestartProgress,
+        StartChaosDescription, StartPartitionDataLossDescription,
+        StartPartitionQuorumLossDescription, StartPartitionRestartDescription, TestCommandProgress,
+    },
+};
+
+/// Test Management Client
+/// Starts and tracks test commands like partition data loss, quorum loss,
+/// restart, node transitions, and controls Chaos.
+#[derive(Debug, Clone)]
+pub struct TestManagementClient {
+    com: IFabricTestManagementClient5,
+}
+
+impl From<IFabricTestManagementClient5> for TestManagementClient {
+    fn from(com: IFabricTestManagementClient5) -> Self {
+        Self { com }
+    }
+}
+
+impl From<TestManagementClient> for IFabricTestManagementClient5 {
+    fn from(value: TestManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+impl TestManagementClient {
+    fn start_partition_data_loss_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionDataLoss(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionDataLoss(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_data_loss_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricPartitionDataLossProgressResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionDataLossProgress(operation_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetPartitionDataLossProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_partition_quorum_loss_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionQuorumLoss(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionQuorumLoss(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_quorum_loss_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricPartitionQuorumLossProgressResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionQuorumLossProgress(
+                    operation_id,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndGetPartitionQuorumLossProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_partition_restart_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_RESTART_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionRestart(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionRestart(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_restart_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricPartitionRestartProgressResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionRestartProgress(operation_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetPartitionRestartProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_node_transition_internal(
+        &self,
+        desc: &FABRIC_NODE_TRANSITION_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartNodeTransition(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartNodeTransition(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_node_transition_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricNodeTransitionProgressResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetNodeTransitionProgress(operation_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetNodeTransitionProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn cancel_test_command_internal(
+        &self,
+        desc: &FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCancelTestCommand(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCancelTestCommand(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_chaos_internal(
+        &self,
+        desc: &FABRIC_START_CHAOS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStartChaos(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStartChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn stop_chaos_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStopChaos(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStopChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_report_internal(
+        &self,
+        desc: &FABRIC_GET_CHAOS_REPORT_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricChaosReportResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetChaosReport(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetChaosReport(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricChaosDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetChaos(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndGetChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_schedule_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<IFabricChaosScheduleDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetChaosSchedule(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndGetChaosSchedule(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn set_chaos_schedule_internal(
+        &self,
+        desc: &FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginSetChaosSchedule(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndSetChaosSchedule(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+/// Polls the progress every poll_interval until the command reaches a terminal state.
+/// Fails with FABRIC_E_TIMEOUT if the command is still running after timeout.
+async fn wait_for_progress<R, F, Fut>(
+    poll_interval: Duration,
+    timeout: Duration,
+    timer: &dyn Timer,
+    cancellation_token: Option<BoxedCancelToken>,
+    mut get_progress: F,
+) -> crate::Result<TestCommandProgress<R>>
+where
+    F: FnMut(Duration, Option<BoxedCancelToken>) -> Fut,
+    Fut: Future<Output = crate::Result<TestCommandProgress<R>>>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(ErrorCode::FABRIC_E_TIMEOUT.into());
+        }
+        let progress = get_progress(remaining, cancellation_token.clone()).await?;
+        if progress.state.is_terminal() {
+            return Ok(progress);
+        }
+        let mut sleep =
+            timer.sleep(poll_interval.min(deadline.saturating_duration_since(Instant::now())));
+        match &cancellation_token {
+            Some(token) => {
+                let mut wait = token.wait();
+                let cancelled = std::future::poll_fn(|cx| {
+                    if wait.as_mut().poll(cx).is_ready() {
+                        return Poll::Ready(true);
+                    }
+                    sleep.as_mut().poll(cx).map(|_| false)
+                })
+                .await;
+                if cancelled {
+                    return Err(ErrorCode::E_ABORT.into());
+                }
+            }
+            None => sleep.await,
+        }
+    }
+}
+
+impl TestManagementClient {
+    /// Starts a command inducing data loss on a partition.
+    /// Track it with get_partition_data_loss_progress or wait_for_partition_data_loss
+    /// using the operation id.
+    pub async fn start_partition_data_loss(
+        &self,
+        desc: &StartPartitionDataLossDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_partition_data_loss_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the progress of a data loss command started with the operation id.
+    pub async fn get_partition_data_loss_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionDataLossProgress> {
+        let com = self
+            .get_partition_data_loss_progress_internal(
+                operation_id,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        let res = unsafe { com.get_Progress().as_ref().unwrap() };
+        Ok(PartitionDataLossProgress::from(res))
+    }
+
+    /// Waits for the data loss command started with the operation id to complete, fault, or be cancelled.
+    /// The progress is polled with the timer every poll_interval.
+    pub async fn wait_for_partition_data_loss(
+        &self,
+        operation_id: GUID,
+        poll_interval: Duration,
+        timeout: Duration,
+        timer: &dyn Timer,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionDataLossProgress> {
+        wait_for_progress(
+            poll_interval,
+            timeout,
+            timer,
+            cancellation_token,
+            |timeout, token| self.get_partition_data_loss_progress(operation_id, timeout, token),
+        )
+        .await
+    }
+
+    /// Starts a command inducing quorum loss on a partition.
+    pub async fn start_partition_quorum_loss(
+        &self,
+        desc: &StartPartitionQuorumLossDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_partition_quorum_loss_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the progress of a quorum loss command started with the operation id.
+    pub async fn get_partition_quorum_loss_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionQuorumLossProgress> {
+        let com = self
+            .get_partition_quorum_loss_progress_internal(
+                operation_id,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        let res = unsafe { com.get_Progress().as_ref().unwrap() };
+        Ok(PartitionQuorumLossProgress::from(res))
+    }
+
+    /// Waits for the quorum loss command started with the operation id to complete, fault, or be cancelled.
+    /// The progress is polled with the timer every poll_interval.
+    pub async fn wait_for_partition_quorum_loss(
+        &self,
+        operation_id: GUID,
+        poll_interval: Duration,
+        timeout: Duration,
+        timer: &dyn Timer,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionQuorumLossProgress> {
+        wait_for_progress(
+            poll_interval,
+            timeout,
+            timer,
+            cancellation_token,
+            |timeout, token| self.get_partition_quorum_loss_progress(operation_id, timeout, token),
+        )
+        .await
+    }
+
+    /// Starts a command restarting replicas of a partition.
+    pub async fn start_partition_restart(
+        &self,
+        desc: &StartPartitionRestartDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_partition_restart_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the progress of a partition restart command started with the operation id.
+    pub async fn get_partition_restart_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionRestartProgress> {
+        let com = self
+            .get_partition_restart_progress_internal(
+                operation_id,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        let res = unsafe { com.get_Progress().as_ref().unwrap() };
+        Ok(PartitionRestartProgress::from(res))
+    }
+
+    /// Waits for the partition restart command started with the operation id to complete, fault, or be cancelled.
+    /// The progress is polled with the timer every poll_interval.
+    pub async fn wait_for_partition_restart(
+        &self,
+        operation_id: GUID,
+        poll_interval: Duration,
+        timeout: Duration,
+        timer: &dyn Timer,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionRestartProgress> {
+        wait_for_progress(
+            poll_interval,
+            timeout,
+            timer,
+            cancellation_token,
+            |timeout, token| self.get_partition_restart_progress(operation_id, timeout, token),
+        )
+        .await
+    }
+
+    /// Starts or stops a node.
+    /// Track it with get_node_transition_progress or wait_for_node_transition
+    /// using the operation id.
+    pub async fn start_node_transition(
+        &self,
+        desc: &NodeTransitionDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_node_transition_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Gets the progress of a node transition started with the operation id.
+    pub async fn get_node_transition_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeTransitionProgress> {
+        let com = self
+            .get_node_transition_progress_internal(
+                operation_id,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+            .await??;
+        let res = unsafe { com.get_Progress().as_ref().unwrap() };
+        Ok(NodeTransitionProgress::from(res))
+    }
+
+    /// Waits for the node transition started with the operation id to complete, fault, or be cancelled.
+    /// The progress is polled with the timer every poll_interval.
+    pub async fn wait_for_node_transition(
+        &self,
+        operation_id: GUID,
+        poll_interval: Duration,
+        timeout: Duration,
+        timer: &dyn Timer,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeTransitionProgress> {
+        wait_for_progress(
+            poll_interval,
+            timeout,
+            timer,
+            cancellation_token,
+            |timeout, token| self.get_node_transition_progress(operation_id, timeout, token),
+        )
+        .await
+    }
+
+    /// Cancels a running test command.
+    pub async fn cancel_test_command(
+        &self,
+        desc: &CancelTestCommandDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw = desc.get_raw();
+            self.cancel_test_command_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+
+    /// Starts Chaos in the cluster. Fails if Chaos is already running.
+    pub async fn start_chaos(
+        &self,
+        desc: &StartChaosDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.start_chaos_internal(&raw, timeout.as_millis().try_into()?, cancellation_token)
+        }
+        .await?
+    }
+
+    /// Stops Chaos if it is running.
+    pub async fn stop_chaos(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.stop_chaos_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await?
+    }
+
+    /// Gets the Chaos events in the time range, or the next batch of events
+    /// after the continuation token of a previous report.
+    pub async fn get_chaos_report(
+        &self,
+        desc: &GetChaosReportDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosReport> {
+        let com = {
+            let mut pool = BoxPool::new();
+            let raw = desc.get_raw_with_pool(&mut pool);
+            self.get_chaos_report_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await??;
+        let res = unsafe { com.get_ChaosReportResult().as_ref().unwrap() };
+        Ok(ChaosReport::from(res))
+    }
+
+    /// Gets the status of Chaos and the parameters it is running with.
+    pub async fn get_chaos(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosDescription> {
+        let com = self
+            .get_chaos_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await??;
+        let res = unsafe { com.get_ChaosDescriptionResult().as_ref().unwrap() };
+        Ok(ChaosDescription::from(res))
+    }
+
+    /// Gets the Chaos schedule.
+    pub async fn get_chaos_schedule(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosScheduleDescription> {
+        let com = self
+            .get_chaos_schedule_internal(timeout.as_millis().try_into()?, cancellation_token)
+            .await??;
+        let res = unsafe { com.get_ChaosScheduleDescriptionResult().as_ref().unwrap() };
+        Ok(ChaosScheduleDescription::from(res))
+    }
+
+    /// Sets the Chaos schedule.
+    /// The version in the description needs to match the current schedule version.
+    pub async fn set_chaos_schedule(
+        &self,
+        desc: &ChaosScheduleDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let mut pool = BoxPool::new();
+            let schedule = Box::new(desc.get_raw_with_pool(&mut pool));
+            let raw = FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION {
+                ChaosScheduleDescription: pool.push(schedule),
+                Reserved: std::ptr::null_mut(),
+            };
+            self.set_chaos_schedule_internal(
+                &raw,
+                timeout.as_millis().try_into()?,
+                cancellation_token,
+            )
+        }
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{pin::Pin, time::Duration};
+
+    use crate::{
+        ErrorCode,
+        runtime::executor::{EventFuture, Timer},
+        sync::SimpleCancelToken,
+        types::{TestCommandProgress, TestCommandProgressState},
+    };
+
+    use super::wait_for_progress;
+
+    struct ImmediateTimer;
+
+    impl Timer for ImmediateTimer {
+        fn sleep(&self, _: Duration) -> Pin<Box<dyn EventFuture>> {
+            Box::pin(std::future::ready(()))
+        }
+    }
+
+    struct PendingTimer;
+
+    impl Timer for PendingTimer {
+        fn sleep(&self, _: Duration) -> Pin<Box<dyn EventFuture>> {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    fn progress(state: TestCommandProgressState) -> TestCommandProgress<()> {
+        TestCommandProgress {
+            state,
+            result: (state == TestCommandProgressState::Completed).then_some(()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_progress() {
+        let mut polls = 0;
+        let res = wait_for_progress(
+            Duration::from_millis(10),
+            Duration::from_secs(10),
+            &ImmediateTimer,
+            None,
+            |_, _| {
+                polls += 1;
+                let state = if polls < 3 {
+                    TestCommandProgressState::Running
+                } else {
+                    TestCommandProgressState::Completed
+                };
+                std::future::ready(Ok(progress(state)))
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(polls, 3);
+        assert_eq!(res.state, TestCommandProgressState::Completed);
+        assert!(res.result.is_some());
+
+        let err = wait_for_progress(
+            Duration::from_millis(10),
+            Duration::ZERO,
+            &ImmediateTimer,
+            None,
+            |_, _| std::future::ready(Ok(progress(TestCommandProgressState::Running))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::FABRIC_E_TIMEOUT.into());
+
+        // Cancellation interrupts the wait between polls.
+        let token = SimpleCancelToken::new_boxed();
+        token.cancel();
+        let err = wait_for_progress(
+            Duration::from_secs(10),
+            Duration::from_secs(10),
+            &PendingTimer,
+            Some(token),
+            |_, _| std::future::ready(Ok(progress(TestCommandProgressState::Running))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::E_ABORT.into());
+    }
+}
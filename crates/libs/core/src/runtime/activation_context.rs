@@ -5,8 +5,9 @@
 
 use mssf_com::{
     FabricRuntime::{
-        IFabricCodePackage, IFabricCodePackageActivationContext6,
-        IFabricConfigurationPackageChangeHandler,
+        IFabricCodePackage, IFabricCodePackageActivationContext6, IFabricCodePackageChangeHandler,
+        IFabricConfigurationPackageChangeHandler, IFabricDataPackage,
+        IFabricDataPackageChangeHandler,
     },
     FabricTypes::{FABRIC_HEALTH_INFORMATION, FABRIC_HEALTH_REPORT_SEND_OPTIONS},
 };
@@ -19,11 +20,19 @@ use crate::{
 use super::{
     config::ConfigurationPackage,
     package_change::{
-        ConfigurationPackageChangeEvent,
+        CodePackageChangeEvent, ConfigurationPackageChangeEvent, DataPackageChangeEvent,
+        code::{
+            CodePackageChangeCallbackHandle, CodePackageChangeEventHandlerBridge,
+            LambdaCodePackageEventHandler,
+        },
         config::{
             ConfigurationPackageChangeCallbackHandle, ConfigurationPackageChangeEventHandlerBridge,
             LambdaConfigurationPackageEventHandler,
         },
+        data::{
+            DataPackageChangeCallbackHandle, DataPackageChangeEventHandlerBridge,
+            LambdaDataPackageEventHandler,
+        },
     },
 };
 
@@ -203,6 +212,67 @@ impl CodePackageActivationContext {
         };
         crate::strings::WStringList::from(&com).into_vec()
     }
+
+    pub fn get_data_package(&self, name: &WString) -> crate::Result<DataPackage> {
+        let com = unsafe { self.com_impl.GetDataPackage(name.as_pcwstr())? };
+        Ok(DataPackage::from(&com))
+    }
+
+    /// Register a data package change handler callback
+    /// Consider using [`AutoDataPackageChangeCallbackHandle::new`] instead of this directly.
+    ///
+    /// [`AutoDataPackageChangeCallbackHandle::new`]: super::package_change::data::AutoDataPackageChangeCallbackHandle::new
+    pub fn register_data_package_change_handler<T>(
+        &self,
+        handler: T,
+    ) -> crate::Result<DataPackageChangeCallbackHandle>
+    where
+        T: Fn(&DataPackageChangeEvent) + 'static,
+    {
+        let lambda_handler = LambdaDataPackageEventHandler::new(handler);
+        let bridge = DataPackageChangeEventHandlerBridge::new(lambda_handler);
+        let callback: IFabricDataPackageChangeHandler = bridge.into();
+        // SAFETY: bridge implements the required COM interface
+        let raw_handle = unsafe { self.com_impl.RegisterDataPackageChangeHandler(&callback) }?;
+        // SAFETY: raw_handle is a data package change handler id, not some other id.
+        Ok(unsafe { DataPackageChangeCallbackHandle::from(raw_handle) })
+    }
+
+    pub fn unregister_data_package_change_handler(
+        &self,
+        handle: DataPackageChangeCallbackHandle,
+    ) -> crate::Result<()> {
+        unsafe { self.com_impl.UnregisterDataPackageChangeHandler(handle.0) }
+            .map_err(crate::Error::from)
+    }
+
+    /// Register a code package change handler callback
+    /// Consider using [`AutoCodePackageChangeCallbackHandle::new`] instead of this directly.
+    ///
+    /// [`AutoCodePackageChangeCallbackHandle::new`]: super::package_change::code::AutoCodePackageChangeCallbackHandle::new
+    pub fn register_code_package_change_handler<T>(
+        &self,
+        handler: T,
+    ) -> crate::Result<CodePackageChangeCallbackHandle>
+    where
+        T: Fn(&CodePackageChangeEvent) + 'static,
+    {
+        let lambda_handler = LambdaCodePackageEventHandler::new(handler);
+        let bridge = CodePackageChangeEventHandlerBridge::new(lambda_handler);
+        let callback: IFabricCodePackageChangeHandler = bridge.into();
+        // SAFETY: bridge implements the required COM interface
+        let raw_handle = unsafe { self.com_impl.RegisterCodePackageChangeHandler(&callback) }?;
+        // SAFETY: raw_handle is a code package change handler id, not some other id.
+        Ok(unsafe { CodePackageChangeCallbackHandle::from(raw_handle) })
+    }
+
+    pub fn unregister_code_package_change_handler(
+        &self,
+        handle: CodePackageChangeCallbackHandle,
+    ) -> crate::Result<()> {
+        unsafe { self.com_impl.UnregisterCodePackageChangeHandler(handle.0) }
+            .map_err(crate::Error::from)
+    }
 }
 
 impl From<IFabricCodePackageActivationContext6> for CodePackageActivationContext {
@@ -243,3 +313,29 @@ impl From<&IFabricCodePackage> for CodePackage {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPackage {
+    // description section
+    pub name: WString,
+    pub version: WString,
+    pub service_manifest_name: WString,
+    pub service_manifest_version: WString,
+
+    /// Local folder the data package files are extracted to.
+    pub path: WString,
+}
+
+impl From<&IFabricDataPackage> for DataPackage {
+    fn from(value: &IFabricDataPackage) -> Self {
+        let desc = unsafe { value.get_Description().as_ref().unwrap() };
+        let path = unsafe { value.get_Path() };
+        Self {
+            name: desc.Name.into(),
+            version: desc.Version.into(),
+            service_manifest_name: desc.ServiceManifestName.into(),
+            service_manifest_version: desc.ServiceManifestVersion.into(),
+            path: WString::from(path),
+        }
+    }
+}
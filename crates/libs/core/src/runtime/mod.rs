@@ -20,16 +20,21 @@ pub mod package_change;
 
 pub mod runtime_wrapper;
 
+mod service_group;
+pub use service_group::{ServiceGroupFactoryBuilder, ServiceGroupPartition};
+
 mod stateful_traits;
 pub use stateful_traits::{
-    IPrimaryReplicator, IReplicator, IStatefulServiceFactory, IStatefulServicePartition,
-    IStatefulServiceReplica,
+    IOperationDataStream, IPrimaryReplicator, IReplicator, IStateProvider, IStatefulServiceFactory,
+    IStatefulServicePartition, IStatefulServiceReplica,
 };
 
 pub mod stateful_bridge;
 
 pub mod stateful_proxy;
 
+pub mod state_replicator;
+
 mod stateless_traits;
 pub use stateless_traits::{
     IStatelessServiceFactory, IStatelessServiceInstance, IStatelessServicePartition,
@@ -43,7 +48,9 @@ pub mod store;
 pub mod store_proxy;
 
 mod activation_context;
-pub use activation_context::{CodePackageActivationContext, CodePackageInfo};
+pub use activation_context::{
+    CodePackage, CodePackageActivationContext, CodePackageInfo, DataPackage,
+};
 
 // creates fabric runtime
 pub fn create_com_runtime() -> crate::Result<IFabricRuntime2> {
@@ -0,0 +1,151 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+//! Handle callbacks for code package changes
+use mssf_com::FabricRuntime::{
+    IFabricCodePackageChangeHandler, IFabricCodePackageChangeHandler_Impl,
+};
+
+use crate::runtime::{CodePackage, CodePackageActivationContext};
+
+use super::CodePackageChangeEvent;
+
+/// Rust trait to turn rust code into IFabricCodePackageChangeHandler.
+/// Not exposed to user
+pub trait CodePackageChangeEventHandler: 'static {
+    fn on_change(&self, change: &CodePackageChangeEvent);
+}
+
+// Bridge implementation for the change handler to turn rust code into SF com object.
+#[windows_core::implement(IFabricCodePackageChangeHandler)]
+#[allow(non_camel_case_types)] // Suppress lint for _Impl struct
+pub struct CodePackageChangeEventHandlerBridge<T>
+where
+    T: CodePackageChangeEventHandler,
+{
+    inner: T,
+}
+
+impl<T> CodePackageChangeEventHandlerBridge<T>
+where
+    T: CodePackageChangeEventHandler,
+{
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> IFabricCodePackageChangeHandler_Impl for CodePackageChangeEventHandlerBridge_Impl<T>
+where
+    T: CodePackageChangeEventHandler,
+{
+    fn OnPackageAdded(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackage>,
+    ) {
+        let new_package = CodePackage::from(package.unwrap());
+        let event = CodePackageChangeEvent::Addition { new_package };
+        self.inner.on_change(&event)
+    }
+
+    fn OnPackageRemoved(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackage>,
+    ) {
+        let previous_package = CodePackage::from(package.unwrap());
+        let event = CodePackageChangeEvent::Removal { previous_package };
+        self.inner.on_change(&event)
+    }
+
+    fn OnPackageModified(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        previouspackage: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackage>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackage>,
+    ) {
+        let new_package = CodePackage::from(package.unwrap());
+        let previous_package = CodePackage::from(previouspackage.unwrap());
+        let event = CodePackageChangeEvent::Modification {
+            previous_package,
+            new_package,
+        };
+        self.inner.on_change(&event)
+    }
+}
+
+/// Lambda implementation of CodePackageChangeEventHandler trait.
+/// Not exposed to user.
+pub(crate) struct LambdaCodePackageEventHandler<T>
+where
+    T: Fn(&CodePackageChangeEvent),
+{
+    f: T,
+}
+
+impl<T> LambdaCodePackageEventHandler<T>
+where
+    T: Fn(&CodePackageChangeEvent) + 'static,
+{
+    pub fn new(f: T) -> Self {
+        Self { f }
+    }
+}
+
+impl<T> CodePackageChangeEventHandler for LambdaCodePackageEventHandler<T>
+where
+    T: Fn(&CodePackageChangeEvent) + 'static,
+{
+    fn on_change(&self, change: &CodePackageChangeEvent) {
+        (self.f)(change)
+    }
+}
+
+/// An opaque id representing a registered Code Package Change callback
+#[derive(Debug)]
+pub struct CodePackageChangeCallbackHandle(pub(crate) i64);
+
+impl CodePackageChangeCallbackHandle {
+    /// # Safety
+    /// Caller ensures this is a registered callback id
+    pub const unsafe fn from(com: i64) -> Self {
+        Self(com)
+    }
+}
+
+/// This struct manages deregistering the Service Fabric Code Package Change callback
+/// when it leaves scope.
+#[derive(Debug)]
+pub struct AutoCodePackageChangeCallbackHandle {
+    /// Service Fabric Activation Context
+    activation_ctx: CodePackageActivationContext,
+    /// Handle to deregister on drop
+    handle: Option<CodePackageChangeCallbackHandle>,
+}
+
+impl AutoCodePackageChangeCallbackHandle {
+    /// Register a new handle for the provided lambda.
+    /// Clones (e.g. adjusts reference count) on activation_ctx
+    pub fn new<T>(activation_ctx: &CodePackageActivationContext, handler: T) -> crate::Result<Self>
+    where
+        T: Fn(&CodePackageChangeEvent) + 'static,
+    {
+        let handle = activation_ctx.register_code_package_change_handler(handler)?;
+        Ok(Self {
+            activation_ctx: activation_ctx.clone(),
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for AutoCodePackageChangeCallbackHandle {
+    fn drop(&mut self) {
+        if let Some(my_handle) = self.handle.take() {
+            self.activation_ctx
+                .unregister_code_package_change_handler(my_handle)
+                .expect("Unregistering handle should succeed.");
+        }
+    }
+}
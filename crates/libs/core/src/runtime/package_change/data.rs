@@ -0,0 +1,246 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+//! Handle callbacks for data package changes
+use mssf_com::FabricRuntime::{
+    IFabricDataPackageChangeHandler, IFabricDataPackageChangeHandler_Impl,
+};
+
+use crate::runtime::{CodePackageActivationContext, DataPackage};
+
+use super::DataPackageChangeEvent;
+
+/// Rust trait to turn rust code into IFabricDataPackageChangeHandler.
+/// Not exposed to user
+pub trait DataPackageChangeEventHandler: 'static {
+    fn on_change(&self, change: &DataPackageChangeEvent);
+}
+
+// Bridge implementation for the change handler to turn rust code into SF com object.
+#[windows_core::implement(IFabricDataPackageChangeHandler)]
+#[allow(non_camel_case_types)] // Suppress lint for _Impl struct
+pub struct DataPackageChangeEventHandlerBridge<T>
+where
+    T: DataPackageChangeEventHandler,
+{
+    inner: T,
+}
+
+impl<T> DataPackageChangeEventHandlerBridge<T>
+where
+    T: DataPackageChangeEventHandler,
+{
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> IFabricDataPackageChangeHandler_Impl for DataPackageChangeEventHandlerBridge_Impl<T>
+where
+    T: DataPackageChangeEventHandler,
+{
+    fn OnPackageAdded(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricDataPackage>,
+    ) {
+        let new_package = DataPackage::from(package.unwrap());
+        let event = DataPackageChangeEvent::Addition { new_package };
+        self.inner.on_change(&event)
+    }
+
+    fn OnPackageRemoved(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricDataPackage>,
+    ) {
+        let previous_package = DataPackage::from(package.unwrap());
+        let event = DataPackageChangeEvent::Removal { previous_package };
+        self.inner.on_change(&event)
+    }
+
+    fn OnPackageModified(
+        &self,
+        _source: windows_core::Ref<mssf_com::FabricRuntime::IFabricCodePackageActivationContext>,
+        previouspackage: windows_core::Ref<mssf_com::FabricRuntime::IFabricDataPackage>,
+        package: windows_core::Ref<mssf_com::FabricRuntime::IFabricDataPackage>,
+    ) {
+        let new_package = DataPackage::from(package.unwrap());
+        let previous_package = DataPackage::from(previouspackage.unwrap());
+        let event = DataPackageChangeEvent::Modification {
+            previous_package,
+            new_package,
+        };
+        self.inner.on_change(&event)
+    }
+}
+
+/// Lambda implementation of DataPackageChangeEventHandler trait.
+/// Not exposed to user.
+pub(crate) struct LambdaDataPackageEventHandler<T>
+where
+    T: Fn(&DataPackageChangeEvent),
+{
+    f: T,
+}
+
+impl<T> LambdaDataPackageEventHandler<T>
+where
+    T: Fn(&DataPackageChangeEvent) + 'static,
+{
+    pub fn new(f: T) -> Self {
+        Self { f }
+    }
+}
+
+impl<T> DataPackageChangeEventHandler for LambdaDataPackageEventHandler<T>
+where
+    T: Fn(&DataPackageChangeEvent) + 'static,
+{
+    fn on_change(&self, change: &DataPackageChangeEvent) {
+        (self.f)(change)
+    }
+}
+
+/// An opaque id representing a registered Data Package Change callback
+#[derive(Debug)]
+pub struct DataPackageChangeCallbackHandle(pub(crate) i64);
+
+impl DataPackageChangeCallbackHandle {
+    /// # Safety
+    /// Caller ensures this is a registered callback id
+    pub const unsafe fn from(com: i64) -> Self {
+        Self(com)
+    }
+}
+
+/// This struct manages deregistering the Service Fabric Data Package Change callback
+/// when it leaves scope.
+#[derive(Debug)]
+pub struct AutoDataPackageChangeCallbackHandle {
+    /// Service Fabric Activation Context
+    activation_ctx: CodePackageActivationContext,
+    /// Handle to deregister on drop
+    handle: Option<DataPackageChangeCallbackHandle>,
+}
+
+impl AutoDataPackageChangeCallbackHandle {
+    /// Register a new handle for the provided lambda.
+    /// Clones (e.g. adjusts reference count) on activation_ctx
+    pub fn new<T>(activation_ctx: &CodePackageActivationContext, handler: T) -> crate::Result<Self>
+    where
+        T: Fn(&DataPackageChangeEvent) + 'static,
+    {
+        let handle = activation_ctx.register_data_package_change_handler(handler)?;
+        Ok(Self {
+            activation_ctx: activation_ctx.clone(),
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for AutoDataPackageChangeCallbackHandle {
+    fn drop(&mut self) {
+        if let Some(my_handle) = self.handle.take() {
+            self.activation_ctx
+                .unregister_data_package_change_handler(my_handle)
+                .expect("Unregistering handle should succeed.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use mssf_com::{
+        FabricRuntime::{
+            IFabricCodePackageActivationContext, IFabricDataPackage, IFabricDataPackage_Impl,
+            IFabricDataPackageChangeHandler,
+        },
+        FabricTypes::FABRIC_DATA_PACKAGE_DESCRIPTION,
+    };
+    use windows_core::{PCWSTR, WString};
+
+    use super::{DataPackageChangeEventHandlerBridge, LambdaDataPackageEventHandler};
+    use crate::runtime::{DataPackage, package_change::PackageChangeEvent};
+
+    #[windows_core::implement(IFabricDataPackage)]
+    struct MockDataPackage {
+        name: WString,
+        version: WString,
+        manifest: WString,
+        path: WString,
+        desc: RefCell<FABRIC_DATA_PACKAGE_DESCRIPTION>,
+    }
+
+    impl MockDataPackage {
+        fn create(version: &str, path: &str) -> IFabricDataPackage {
+            Self {
+                name: "Models".into(),
+                version: version.into(),
+                manifest: "MyPkg".into(),
+                path: path.into(),
+                desc: Default::default(),
+            }
+            .into()
+        }
+    }
+
+    impl IFabricDataPackage_Impl for MockDataPackage_Impl {
+        fn get_Description(&self) -> *mut FABRIC_DATA_PACKAGE_DESCRIPTION {
+            *self.desc.borrow_mut() = FABRIC_DATA_PACKAGE_DESCRIPTION {
+                Name: self.name.as_pcwstr(),
+                Version: self.version.as_pcwstr(),
+                ServiceManifestName: self.manifest.as_pcwstr(),
+                ServiceManifestVersion: self.manifest.as_pcwstr(),
+                Reserved: std::ptr::null_mut(),
+            };
+            self.desc.as_ptr()
+        }
+
+        fn get_Path(&self) -> PCWSTR {
+            self.path.as_pcwstr()
+        }
+    }
+
+    #[test]
+    fn test_data_package_change_bridge() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let events2 = events.clone();
+        let handler: IFabricDataPackageChangeHandler = DataPackageChangeEventHandlerBridge::new(
+            LambdaDataPackageEventHandler::new(move |e| events2.borrow_mut().push(e.clone())),
+        )
+        .into();
+
+        let previous = MockDataPackage::create("1.0", "/data/Models.1.0");
+        let new = MockDataPackage::create("2.0", "/data/Models.2.0");
+        unsafe {
+            handler.OnPackageAdded(None::<&IFabricCodePackageActivationContext>, &previous);
+            handler.OnPackageModified(
+                None::<&IFabricCodePackageActivationContext>,
+                &previous,
+                &new,
+            );
+        }
+
+        let previous = DataPackage::from(&previous);
+        let new = DataPackage::from(&new);
+        assert_eq!(new.name, WString::from("Models"));
+        assert_eq!(new.service_manifest_name, WString::from("MyPkg"));
+        assert_eq!(new.path, WString::from("/data/Models.2.0"));
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                PackageChangeEvent::Addition {
+                    new_package: previous.clone()
+                },
+                PackageChangeEvent::Modification {
+                    previous_package: previous,
+                    new_package: new,
+                },
+            ]
+        );
+    }
+}
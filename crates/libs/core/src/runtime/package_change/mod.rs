@@ -4,7 +4,9 @@
 // ------------------------------------------------------------
 //! This module supports implementing callbacks when Service Fabric Packages are changed
 //!
+pub mod code;
 pub mod config;
+pub mod data;
 
 /// The ways a given Service Fabric Package (e.g. ConfigurationPackage or DataPackage) can change
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -15,3 +17,5 @@ pub enum PackageChangeEvent<T> {
 }
 
 pub type ConfigurationPackageChangeEvent = PackageChangeEvent<super::config::ConfigurationPackage>;
+pub type DataPackageChangeEvent = PackageChangeEvent<super::DataPackage>;
+pub type CodePackageChangeEvent = PackageChangeEvent<super::CodePackage>;
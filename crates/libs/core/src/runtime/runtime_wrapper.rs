@@ -5,9 +5,9 @@ use mssf_com::FabricRuntime::{
 };
 
 use super::{
-    create_com_runtime, executor::Executor, stateful_bridge::StatefulServiceFactoryBridge,
-    stateful_traits::IStatefulServiceFactory, stateless_bridge::StatelessServiceFactoryBridge,
-    stateless_traits::IStatelessServiceFactory,
+    ServiceGroupFactoryBuilder, create_com_runtime, executor::Executor,
+    stateful_bridge::StatefulServiceFactoryBridge, stateful_traits::IStatefulServiceFactory,
+    stateless_bridge::StatelessServiceFactoryBridge, stateless_traits::IStatelessServiceFactory,
 };
 pub struct Runtime<E>
 where
@@ -55,4 +55,26 @@ where
         }
         .map_err(crate::Error::from)
     }
+
+    /// Creates an empty builder for the member factories of a service group type.
+    pub fn create_service_group_factory_builder(
+        &self,
+    ) -> crate::Result<ServiceGroupFactoryBuilder<E>> {
+        let com = unsafe { self.com_impl.CreateServiceGroupFactoryBuilder() }?;
+        Ok(ServiceGroupFactoryBuilder::new(com, self.rt.clone()))
+    }
+
+    /// Registers the service group type with the member factories added to the builder.
+    pub fn register_service_group_factory(
+        &self,
+        groupservicetype: &WString,
+        builder: &ServiceGroupFactoryBuilder<E>,
+    ) -> crate::Result<()> {
+        let factory = builder.to_service_group_factory()?;
+        unsafe {
+            self.com_impl
+                .RegisterServiceGroupFactory(groupservicetype.as_pcwstr(), &factory)
+        }
+        .map_err(crate::Error::from)
+    }
 }
@@ -0,0 +1,132 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Service group support for the runtime.
+//! A service group hosts several member services in the same partition.
+//! Each member type gets its own factory, and members can reach their siblings
+//! through the [`ServiceGroupPartition`].
+
+use mssf_com::FabricRuntime::{
+    IFabricServiceGroupFactory, IFabricServiceGroupFactoryBuilder, IFabricServiceGroupPartition,
+    IFabricStatefulServiceFactory, IFabricStatelessServiceFactory,
+};
+
+use crate::{ErrorCode, Interface, WString, types::Uri};
+
+use super::{
+    IStatefulServiceFactory, IStatefulServicePartition, IStatelessServiceFactory,
+    IStatelessServicePartition, executor::Executor, stateful_bridge::StatefulServiceFactoryBridge,
+    stateless_bridge::StatelessServiceFactoryBridge,
+};
+
+/// Collects the factories of the member service types of a service group.
+/// Created by `Runtime::create_service_group_factory_builder` and registered
+/// with `Runtime::register_service_group_factory`.
+pub struct ServiceGroupFactoryBuilder<E>
+where
+    E: Executor,
+{
+    com: IFabricServiceGroupFactoryBuilder,
+    rt: E,
+}
+
+impl<E> ServiceGroupFactoryBuilder<E>
+where
+    E: Executor,
+{
+    pub(crate) fn new(com: IFabricServiceGroupFactoryBuilder, rt: E) -> Self {
+        Self { com, rt }
+    }
+
+    pub fn get_com(&self) -> &IFabricServiceGroupFactoryBuilder {
+        &self.com
+    }
+
+    /// Adds the factory for a stateless member service type.
+    pub fn add_stateless_service_factory(
+        &self,
+        member_service_type: &WString,
+        factory: Box<dyn IStatelessServiceFactory>,
+    ) -> crate::Result<()> {
+        let bridge: IFabricStatelessServiceFactory =
+            StatelessServiceFactoryBridge::create(factory, self.rt.clone()).into();
+        unsafe {
+            self.com
+                .AddStatelessServiceFactory(member_service_type.as_pcwstr(), &bridge)
+        }
+        .map_err(crate::Error::from)
+    }
+
+    /// Adds the factory for a stateful member service type.
+    pub fn add_stateful_service_factory(
+        &self,
+        member_service_type: &WString,
+        factory: Box<dyn IStatefulServiceFactory>,
+    ) -> crate::Result<()> {
+        let bridge: IFabricStatefulServiceFactory =
+            StatefulServiceFactoryBridge::create(factory, self.rt.clone()).into();
+        unsafe {
+            self.com
+                .AddStatefulServiceFactory(member_service_type.as_pcwstr(), &bridge)
+        }
+        .map_err(crate::Error::from)
+    }
+
+    /// Removes a previously added member factory.
+    pub fn remove_service_factory(&self, member_service_type: &WString) -> crate::Result<()> {
+        unsafe {
+            self.com
+                .RemoveServiceFactory(member_service_type.as_pcwstr())
+        }
+        .map_err(crate::Error::from)
+    }
+
+    pub(crate) fn to_service_group_factory(&self) -> crate::Result<IFabricServiceGroupFactory> {
+        unsafe { self.com.ToServiceGroupFactory() }.map_err(crate::Error::from)
+    }
+}
+
+/// Partition of a service group as seen by a member.
+/// Obtained from the partition passed to the member on open.
+#[derive(Debug, Clone)]
+pub struct ServiceGroupPartition {
+    com: IFabricServiceGroupPartition,
+}
+
+impl ServiceGroupPartition {
+    /// Fails if the partition does not belong to a service group member,
+    /// or if it is not backed by SF (e.g. a mock).
+    pub fn from_stateless(partition: &dyn IStatelessServicePartition) -> crate::Result<Self> {
+        let com = partition
+            .try_get_com()?
+            .cast::<IFabricServiceGroupPartition>()?;
+        Ok(Self { com })
+    }
+
+    /// Fails if the partition does not belong to a service group member,
+    /// or if it is not backed by SF (e.g. a mock).
+    pub fn from_stateful(partition: &dyn IStatefulServicePartition) -> crate::Result<Self> {
+        let com = partition
+            .try_get_com()?
+            .cast::<IFabricServiceGroupPartition>()?;
+        Ok(Self { com })
+    }
+
+    pub fn get_com(&self) -> &IFabricServiceGroupPartition {
+        &self.com
+    }
+
+    /// Resolves a sibling member by its full name, i.e. `fabric:/app/group#member`,
+    /// and returns the member service object as the requested COM interface.
+    /// For members implemented with this crate the object is the instance
+    /// or replica bridge, so custom interfaces are not available on it.
+    pub fn resolve_member<T: Interface>(&self, name: &Uri) -> crate::Result<T> {
+        let raw = unsafe { self.com.ResolveMember(name.as_raw(), &T::IID) }?;
+        if raw.is_null() {
+            return Err(ErrorCode::E_POINTER.into());
+        }
+        Ok(unsafe { T::from_raw(raw) })
+    }
+}
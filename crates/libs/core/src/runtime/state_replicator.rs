@@ -0,0 +1,361 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// state_replicator wraps the SF built-in replicator (IFabricStateReplicator) and
+// the operation types flowing through it.
+
+// windows::core::implement macro generates snake case types.
+#![allow(non_camel_case_types)]
+
+use crate::{
+    Interface,
+    runtime::{IOperationDataStream, executor::BoxedCancelToken},
+    sync::{FabricReceiver, OperationStreamAdapter, fabric_begin_end_proxy},
+    types::{FaultType, OperationMetadata, OperationType, ReplicatorSettings},
+};
+use mssf_com::{
+    FabricRuntime::{
+        IFabricOperation, IFabricOperationData, IFabricOperationData_Impl,
+        IFabricOperationDataStream, IFabricOperationStream, IFabricOperationStream2,
+        IFabricStateReplicator, IFabricStateReplicator2,
+    },
+    FabricTypes::FABRIC_OPERATION_DATA_BUFFER,
+};
+use windows_core::implement;
+
+/// Converts raw SF operation data buffers into slices.
+/// The slices borrow from the COM object owning the buffers.
+///
+/// # Safety
+/// buffers must point to count valid FABRIC_OPERATION_DATA_BUFFER items,
+/// which must outlive 'a.
+pub(crate) unsafe fn operation_data_buffers<'a>(
+    count: u32,
+    buffers: *const FABRIC_OPERATION_DATA_BUFFER,
+) -> Vec<&'a [u8]> {
+    if count == 0 || buffers.is_null() {
+        return vec![];
+    }
+    unsafe { std::slice::from_raw_parts(buffers, count as usize) }
+        .iter()
+        .map(|b| {
+            if b.BufferSize == 0 || b.Buffer.is_null() {
+                &[][..]
+            } else {
+                unsafe { std::slice::from_raw_parts(b.Buffer, b.BufferSize as usize) }
+            }
+        })
+        .collect()
+}
+
+/// Rust owned operation data passed to SF.
+/// Each inner Vec is a separate buffer in IFabricOperationData.
+#[implement(IFabricOperationData)]
+pub struct OperationData {
+    _data: Vec<Vec<u8>>,
+    // raw views pointing into data.
+    buffers: Vec<FABRIC_OPERATION_DATA_BUFFER>,
+}
+
+impl OperationData {
+    pub fn new(data: Vec<Vec<u8>>) -> Self {
+        let buffers = data
+            .iter()
+            .map(|d| FABRIC_OPERATION_DATA_BUFFER {
+                BufferSize: d.len() as u32,
+                Buffer: d.as_ptr() as *mut u8,
+            })
+            .collect();
+        Self {
+            _data: data,
+            buffers,
+        }
+    }
+}
+
+impl From<Vec<Vec<u8>>> for OperationData {
+    fn from(value: Vec<Vec<u8>>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Vec<u8>> for OperationData {
+    fn from(value: Vec<u8>) -> Self {
+        Self::new(vec![value])
+    }
+}
+
+impl IFabricOperationData_Impl for OperationData_Impl {
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn GetData(&self, count: *mut u32) -> crate::WinResult<*mut FABRIC_OPERATION_DATA_BUFFER> {
+        // SF reads the buffers without modification.
+        unsafe { *count = self.buffers.len() as u32 };
+        Ok(self.buffers.as_ptr() as *mut FABRIC_OPERATION_DATA_BUFFER)
+    }
+}
+
+/// Operation received from the replication or copy stream.
+/// The data is owned by SF and is valid as long as this object is alive.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    com_impl: IFabricOperation,
+}
+
+impl From<IFabricOperation> for Operation {
+    fn from(com_impl: IFabricOperation) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl Operation {
+    pub fn metadata(&self) -> crate::Result<OperationMetadata> {
+        unsafe { self.com_impl.get_Metadata().as_ref() }
+            .map(OperationMetadata::from)
+            .ok_or(crate::ErrorCode::E_POINTER.into())
+    }
+
+    pub fn operation_type(&self) -> crate::Result<OperationType> {
+        Ok(self.metadata()?.r#type)
+    }
+
+    /// The sequence number (LSN) of the operation.
+    pub fn sequence_number(&self) -> crate::Result<i64> {
+        Ok(self.metadata()?.sequence_number)
+    }
+
+    /// The atomic group the operation belongs to, if it is part of an atomic group.
+    pub fn atomic_group_id(&self) -> crate::Result<i64> {
+        Ok(self.metadata()?.atomic_group_id)
+    }
+
+    /// The data buffers of the operation, in the same order as they are replicated.
+    pub fn data(&self) -> crate::Result<Vec<&[u8]>> {
+        let mut count = 0;
+        let buffers = unsafe { self.com_impl.GetData(&mut count) }?;
+        Ok(unsafe { operation_data_buffers(count, buffers) })
+    }
+
+    /// Acknowledges the operation has been applied by the service.
+    /// Required when the replicator is created with require_service_ack, and
+    /// for all copy operations.
+    pub fn acknowledge(&self) -> crate::Result<()> {
+        unsafe { self.com_impl.Acknowledge() }.map_err(crate::Error::from)
+    }
+}
+
+/// Stream of operations on the secondary, returned from
+/// StateReplicator::get_replication_stream or get_copy_stream.
+#[derive(Debug, Clone)]
+pub struct OperationStream {
+    com_impl: IFabricOperationStream,
+}
+
+impl From<IFabricOperationStream> for OperationStream {
+    fn from(com_impl: IFabricOperationStream) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl OperationStream {
+    pub(crate) fn get_operation_internal(
+        &self,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<Option<IFabricOperation>>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetOperation(callback) },
+            move |ctx| match unsafe { com2.EndGetOperation(ctx) } {
+                Ok(op) => Ok(Some(op)),
+                // SF returns null operation at the end of stream.
+                Err(e) if e.code().is_ok() => Ok(None),
+                Err(e) => Err(e),
+            },
+            cancellation_token,
+        )
+    }
+
+    /// Gets the next operation from the stream.
+    /// Returns None when the stream has reached the end, which happens after
+    /// the copy completes or when the replica is no longer a secondary.
+    pub async fn get_operation(
+        &self,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Option<Operation>> {
+        self.get_operation_internal(cancellation_token)
+            .await?
+            .map(|op| op.map(|com_impl| Operation { com_impl }))
+    }
+
+    /// Converts into a Stream of operations.
+    pub fn into_stream(self) -> OperationStreamAdapter {
+        OperationStreamAdapter::new(self)
+    }
+
+    /// Reports a fault of the replica while processing the stream.
+    pub fn report_fault(&self, fault_type: FaultType) -> crate::Result<()> {
+        let com = self.com_impl.cast::<IFabricOperationStream2>()?;
+        unsafe { com.ReportFault(fault_type.into()) }.map_err(crate::Error::from)
+    }
+}
+
+/// Stream of operation data provided by SF.
+/// On the primary this is the copy context stream sent from the idle secondary,
+/// passed into IStateProvider::get_copy_state.
+#[derive(Debug, Clone)]
+pub struct OperationDataStream {
+    com_impl: IFabricOperationDataStream,
+}
+
+impl From<IFabricOperationDataStream> for OperationDataStream {
+    fn from(com_impl: IFabricOperationDataStream) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl From<OperationDataStream> for IFabricOperationDataStream {
+    fn from(value: OperationDataStream) -> Self {
+        value.com_impl
+    }
+}
+
+impl OperationDataStream {
+    fn get_next_internal(
+        &self,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<Option<IFabricOperationData>>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetNext(callback) },
+            move |ctx| match unsafe { com2.EndGetNext(ctx) } {
+                Ok(data) => Ok(Some(data)),
+                // SF returns null data at the end of stream.
+                Err(e) if e.code().is_ok() => Ok(None),
+                Err(e) => Err(e),
+            },
+            cancellation_token,
+        )
+    }
+
+    /// Gets the next operation data buffers.
+    /// Returns None when the stream has reached the end.
+    pub async fn get_next(
+        &self,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Option<Vec<Vec<u8>>>> {
+        let data = self.get_next_internal(cancellation_token).await??;
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        let mut count = 0;
+        let buffers = unsafe { data.GetData(&mut count) }?;
+        Ok(Some(
+            unsafe { operation_data_buffers(count, buffers) }
+                .into_iter()
+                .map(|b| b.to_vec())
+                .collect(),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl IOperationDataStream for OperationDataStream {
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<Option<Vec<Vec<u8>>>> {
+        OperationDataStream::get_next(self, Some(cancellation_token)).await
+    }
+}
+
+/// Proxy for the SF built-in replicator, created by
+/// IStatefulServicePartition::create_replicator.
+/// Primary replicates operations using replicate(), and secondaries
+/// receive operations from the copy and replication streams.
+#[derive(Debug, Clone)]
+pub struct StateReplicator {
+    com_impl: IFabricStateReplicator,
+}
+
+impl From<IFabricStateReplicator> for StateReplicator {
+    fn from(com_impl: IFabricStateReplicator) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl From<StateReplicator> for IFabricStateReplicator {
+    fn from(value: StateReplicator) -> Self {
+        value.com_impl
+    }
+}
+
+impl StateReplicator {
+    fn replicate_internal(
+        &self,
+        data: &IFabricOperationData,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<i64>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        // The same sequence number is returned again in the end api.
+        let mut sequence_number = 0;
+        fabric_begin_end_proxy(
+            |callback| unsafe { com1.BeginReplicate(data, callback, &mut sequence_number) },
+            move |ctx| unsafe { com2.EndReplicate(ctx) },
+            cancellation_token,
+        )
+    }
+
+    /// Replicates the data to the secondaries.
+    /// Can only be called on the primary with write status granted.
+    /// Returns the sequence number (LSN) of the operation after it is
+    /// acknowledged by a write quorum.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn replicate(
+        &self,
+        data: impl Into<OperationData>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        let data: IFabricOperationData = data.into().into();
+        self.replicate_internal(&data, cancellation_token).await?
+    }
+
+    /// Gets the stream of operations replicated by the primary.
+    /// Called on secondaries.
+    pub fn get_replication_stream(&self) -> crate::Result<OperationStream> {
+        unsafe { self.com_impl.GetReplicationStream() }
+            .map(|com_impl| OperationStream { com_impl })
+            .map_err(crate::Error::from)
+    }
+
+    /// Gets the stream of copy operations, which is the state provided by
+    /// the primary state provider get_copy_state.
+    /// Called on idle secondaries during replica build.
+    pub fn get_copy_stream(&self) -> crate::Result<OperationStream> {
+        unsafe { self.com_impl.GetCopyStream() }
+            .map(|com_impl| OperationStream { com_impl })
+            .map_err(crate::Error::from)
+    }
+
+    /// Updates the replicator settings.
+    /// Only the security credentials can be updated at runtime by SF.
+    pub fn update_replicator_settings(&self, settings: &ReplicatorSettings) -> crate::Result<()> {
+        let raw = settings.get_raw();
+        unsafe { self.com_impl.UpdateReplicatorSettings(&raw) }.map_err(crate::Error::from)
+    }
+
+    /// Gets the current replicator settings.
+    pub fn get_replicator_settings(&self) -> crate::Result<ReplicatorSettings> {
+        let com = self.com_impl.cast::<IFabricStateReplicator2>()?;
+        let res = unsafe { com.GetReplicatorSettings() }?;
+        unsafe { res.get_ReplicatorSettings().as_ref() }
+            .map(ReplicatorSettings::from)
+            .ok_or(crate::ErrorCode::E_POINTER.into())
+    }
+}
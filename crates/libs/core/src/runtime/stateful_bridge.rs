@@ -11,16 +11,24 @@
 use std::sync::Arc;
 
 use crate::{
-    Interface, runtime::stateful_proxy::StatefulServicePartition, strings::StringResult, types::Uri,
+    Interface,
+    runtime::{
+        state_replicator::{OperationData, OperationDataStream},
+        stateful_proxy::StatefulServicePartition,
+    },
+    strings::StringResult,
+    types::Uri,
 };
 use windows_core::implement;
 
 use mssf_com::{
     FabricCommon::IFabricStringResult,
     FabricRuntime::{
+        IFabricOperationData, IFabricOperationDataStream, IFabricOperationDataStream_Impl,
         IFabricPrimaryReplicator, IFabricPrimaryReplicator_Impl, IFabricReplicator,
         IFabricReplicator_Impl, IFabricReplicatorCatchupSpecificQuorum,
-        IFabricReplicatorCatchupSpecificQuorum_Impl, IFabricStatefulServiceFactory,
+        IFabricReplicatorCatchupSpecificQuorum_Impl, IFabricStateProvider,
+        IFabricStateProvider_Impl, IFabricStatefulServiceFactory,
         IFabricStatefulServiceFactory_Impl, IFabricStatefulServicePartition,
         IFabricStatefulServicePartition3, IFabricStatefulServiceReplica,
         IFabricStatefulServiceReplica_Impl,
@@ -38,8 +46,8 @@ use crate::{
 };
 
 use super::{
-    IPrimaryReplicator, IReplicator, IStatefulServiceFactory, IStatefulServiceReplica,
-    executor::Executor,
+    IOperationDataStream, IPrimaryReplicator, IReplicator, IStateProvider, IStatefulServiceFactory,
+    IStatefulServiceReplica, executor::Executor,
 };
 // bridges from rs into com
 
@@ -627,7 +635,10 @@ where
             .unwrap()
             .cast::<IFabricStatefulServicePartition3>()
             .expect("cannot query interface");
-        let partition = Arc::new(StatefulServicePartition::from(&com_partition));
+        let partition = Arc::new(StatefulServicePartition::new(
+            &com_partition,
+            self.rt.clone(),
+        ));
         let (ctx, token) = BridgeContext::make(callback);
         ctx.spawn(&self.rt, async move {
             inner
@@ -721,3 +732,197 @@ where
 }
 
 // endregion: IFabricStatefulServiceReplicaBridge
+
+// region: IFabricOperationDataStreamBridge
+
+/// Bridge from rust operation data stream to com.
+#[implement(IFabricOperationDataStream)]
+pub struct IFabricOperationDataStreamBridge<E>
+where
+    E: Executor,
+{
+    inner: Arc<Box<dyn IOperationDataStream>>,
+    rt: E,
+}
+
+impl<E> IFabricOperationDataStreamBridge<E>
+where
+    E: Executor,
+{
+    pub fn create(stream: Box<dyn IOperationDataStream>, rt: E) -> Self {
+        IFabricOperationDataStreamBridge {
+            inner: Arc::new(stream),
+            rt,
+        }
+    }
+}
+
+impl<E> IFabricOperationDataStream_Impl for IFabricOperationDataStreamBridge_Impl<E>
+where
+    E: Executor,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginGetNext(
+        &self,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            match inner.get_next(token).await {
+                Ok(Some(data)) => Ok(IFabricOperationData::from(OperationData::new(data))),
+                // End of stream is indicated by null data with S_OK.
+                Ok(None) => Err(crate::WinError::empty()),
+                Err(e) => Err(crate::WinError::from(e)),
+            }
+        })
+    }
+
+    // No err tracing since end of stream is returned as an empty error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, level = "debug"))]
+    fn EndGetNext(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<IFabricOperationData> {
+        BridgeContext::result(context)?
+    }
+}
+
+// endregion: IFabricOperationDataStreamBridge
+
+// region: IFabricStateProviderBridge
+
+/// Bridge from rust state provider to com.
+/// StatefulServicePartition::create_replicator wraps the state provider with it.
+#[implement(IFabricStateProvider)]
+pub struct IFabricStateProviderBridge<E>
+where
+    E: Executor,
+{
+    inner: Arc<dyn IStateProvider>,
+    rt: E,
+}
+
+impl<E> IFabricStateProviderBridge<E>
+where
+    E: Executor,
+{
+    pub fn create(provider: Arc<dyn IStateProvider>, rt: E) -> IFabricStateProviderBridge<E> {
+        IFabricStateProviderBridge {
+            inner: provider,
+            rt,
+        }
+    }
+}
+
+impl<E> IFabricStateProvider_Impl for IFabricStateProviderBridge_Impl<E>
+where
+    E: Executor,
+{
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginUpdateEpoch(
+        &self,
+        epoch: *const FABRIC_EPOCH,
+        previousepochlastsequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let epoch2: Epoch = unsafe { epoch.as_ref().unwrap().into() };
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .update_epoch(epoch2, previousepochlastsequencenumber, token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndUpdateEpoch(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        BridgeContext::result(context)?
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn GetLastCommittedSequenceNumber(&self) -> crate::WinResult<i64> {
+        self.inner
+            .get_last_committed_sequence_number()
+            .map_err(crate::WinError::from)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginOnDataLoss(
+        &self,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .on_data_loss(token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndOnDataLoss(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        BridgeContext::result(context)?
+    }
+
+    // No err tracing since no copy context is returned as an empty error.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, level = "debug"))]
+    fn GetCopyContext(&self) -> crate::WinResult<IFabricOperationDataStream> {
+        match self.inner.get_copy_context()? {
+            Some(stream) => {
+                Ok(IFabricOperationDataStreamBridge::create(stream, self.rt.clone()).into())
+            }
+            // null copy context is returned with S_OK.
+            None => Err(crate::WinError::empty()),
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn GetCopyState(
+        &self,
+        uptosequencenumber: i64,
+        copycontextstream: windows_core::Ref<IFabricOperationDataStream>,
+    ) -> crate::WinResult<IFabricOperationDataStream> {
+        let copy_context = copycontextstream
+            .as_ref()
+            .map(|s| OperationDataStream::from(s.clone()));
+        let stream = self
+            .inner
+            .get_copy_state(uptosequencenumber, copy_context)?;
+        Ok(IFabricOperationDataStreamBridge::create(stream, self.rt.clone()).into())
+    }
+}
+
+// endregion: IFabricStateProviderBridge
@@ -9,9 +9,13 @@
 use std::{ffi::c_void, sync::Arc};
 
 use crate::{Interface, WString, runtime::executor::BoxedCancelToken, strings::StringResult};
-use mssf_com::FabricRuntime::{
-    IFabricKeyValueStoreReplica8, IFabricPrimaryReplicator, IFabricReplicator,
-    IFabricReplicatorCatchupSpecificQuorum, IFabricStatefulServicePartition3,
+use mssf_com::{
+    FabricRuntime::{
+        IFabricKeyValueStoreReplica8, IFabricPrimaryReplicator, IFabricReplicator,
+        IFabricReplicatorCatchupSpecificQuorum, IFabricStateProvider,
+        IFabricStatefulServicePartition3,
+    },
+    FabricTypes::FABRIC_REPLICATOR_SETTINGS,
 };
 
 use crate::{
@@ -19,11 +23,14 @@ use crate::{
     sync::fabric_begin_end_proxy,
     types::{
         FaultType, HealthInformation, LoadMetric, LoadMetricListRef, MoveCost, ReplicaRole,
-        ServicePartitionAccessStatus, ServicePartitionInformation,
+        ReplicatorSettings, ServicePartitionAccessStatus, ServicePartitionInformation,
     },
 };
 
-use super::{IPrimaryReplicator, IReplicator, IStatefulServiceReplica};
+use super::{
+    IPrimaryReplicator, IReplicator, IStateProvider, IStatefulServiceReplica, executor::Executor,
+    state_replicator::StateReplicator, stateful_bridge::IFabricStateProviderBridge,
+};
 use crate::types::{Epoch, OpenMode, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode};
 
 pub struct StatefulServiceReplicaProxy {
@@ -378,10 +385,48 @@ impl IPrimaryReplicator for PrimaryReplicatorProxy {
     }
 }
 
+/// Wraps a rust state provider into the com state provider.
+type StateProviderBridgeFn = dyn Fn(Arc<dyn IStateProvider>) -> IFabricStateProvider + Send + Sync;
+
 /// Proxy COM object IFabricStatefulServicePartition3
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct StatefulServicePartition {
     com_impl: IFabricStatefulServicePartition3,
+    // Captures the executor used to run the state provider callbacks.
+    // None if created without an executor.
+    state_provider_bridge: Option<Arc<StateProviderBridgeFn>>,
+}
+
+impl std::fmt::Debug for StatefulServicePartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatefulServicePartition")
+            .field("com_impl", &self.com_impl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatefulServicePartition {
+    /// The executor runs the callbacks of the state provider passed
+    /// to create_replicator.
+    pub fn new<E: Executor>(com_impl: &IFabricStatefulServicePartition3, rt: E) -> Self {
+        StatefulServicePartition {
+            com_impl: com_impl.clone(),
+            state_provider_bridge: Some(Arc::new(move |provider| {
+                IFabricStateProviderBridge::create(provider, rt.clone()).into()
+            })),
+        }
+    }
+}
+
+/// Deprecated: use `StatefulServicePartition::new` with an executor instead.
+/// The partition has no executor, so create_replicator fails.
+impl From<&IFabricStatefulServicePartition3> for StatefulServicePartition {
+    fn from(e: &IFabricStatefulServicePartition3) -> Self {
+        StatefulServicePartition {
+            com_impl: e.clone(),
+            state_provider_bridge: None,
+        }
+    }
 }
 
 impl super::IStatefulServicePartition for StatefulServicePartition {
@@ -403,9 +448,31 @@ impl super::IStatefulServicePartition for StatefulServicePartition {
             .map_err(crate::Error::from)
     }
 
-    /// TODO: not implemented
-    fn create_replicator(&self) -> crate::Result<Box<dyn IPrimaryReplicator>> {
-        Err(ErrorCode::E_NOTIMPL.into())
+    fn create_replicator(
+        &self,
+        state_provider: Arc<dyn IStateProvider>,
+        replicator_settings: Option<&ReplicatorSettings>,
+    ) -> crate::Result<(Box<dyn IPrimaryReplicator>, StateReplicator)> {
+        let raw_settings = replicator_settings.map(|s| s.get_raw());
+        let raw_settings_ptr = match raw_settings.as_ref() {
+            Some(x) => x as *const FABRIC_REPLICATOR_SETTINGS,
+            None => std::ptr::null(),
+        };
+        let bridge = self
+            .state_provider_bridge
+            .as_ref()
+            .ok_or(ErrorCode::FABRIC_E_INVALID_OPERATION)?;
+        let state_provider = bridge(state_provider);
+        let mut replicator: Option<IFabricReplicator> = None;
+        let state_replicator = unsafe {
+            self.com_impl
+                .CreateReplicator(&state_provider, raw_settings_ptr, &mut replicator)
+        }?;
+        let p_rplctr: IFabricPrimaryReplicator = replicator.ok_or(ErrorCode::E_POINTER)?.cast()?;
+        Ok((
+            Box::new(PrimaryReplicatorProxy::new(p_rplctr)),
+            StateReplicator::from(state_replicator),
+        ))
     }
 
     fn report_load(&self, metrics: &[LoadMetric]) -> crate::Result<()> {
@@ -438,11 +505,3 @@ impl super::IStatefulServicePartition for StatefulServicePartition {
         Ok(&self.com_impl)
     }
 }
-
-impl From<&IFabricStatefulServicePartition3> for StatefulServicePartition {
-    fn from(e: &IFabricStatefulServicePartition3) -> Self {
-        StatefulServicePartition {
-            com_impl: e.clone(),
-        }
-    }
-}
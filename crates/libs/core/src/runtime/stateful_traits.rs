@@ -6,6 +6,7 @@
 // stateful contains rs definition of stateful traits that user needs to implement
 
 use crate::runtime::executor::BoxedCancelToken;
+use crate::runtime::state_replicator::{OperationDataStream, StateReplicator};
 use crate::types::ReplicaRole;
 
 use crate::types::{Epoch, OpenMode, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode};
@@ -34,9 +35,8 @@ pub trait IStatefulServiceReplica: Send + Sync + 'static {
     /// Opens an initialized service replica so that additional actions can be taken.
     /// Returns PrimaryReplicator that is used by the stateful service.
     /// Note:
-    /// Most user calls IFabricStatefulServicePartition.CreateReplicator instead of
-    /// writing their own replicator (TODO: not supported in mssf yet),
-    /// or use FabricCreateKeyValueStoreReplica.
+    /// Most user calls IStatefulServicePartition::create_replicator instead of
+    /// writing their own replicator, or use FabricCreateKeyValueStoreReplica.
     async fn open(
         &self,
         openmode: OpenMode,
@@ -250,10 +250,76 @@ impl std::fmt::Debug for dyn IPrimaryReplicator {
     }
 }
 
+/// Stream of operation data, used by the state provider to supply copy context
+/// and copy state to the SF built-in replicator.
+#[async_trait::async_trait]
+pub trait IOperationDataStream: Send + Sync + 'static {
+    /// Gets the next operation data, where each inner Vec is a separate buffer.
+    /// Returns None when the stream has reached the end.
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<Option<Vec<Vec<u8>>>>;
+}
+
+/// Defines the state provider used by the SF built-in replicator created by
+/// IStatefulServicePartition::create_replicator.
+/// The replicator calls into the state provider to get and apply state
+/// during copy, and to notify epoch changes and data loss.
+#[async_trait::async_trait]
+pub trait IStateProvider: Send + Sync + 'static {
+    /// Indicates to a replica that the configuration of a replica set has changed due to
+    /// a change or attempted change to the primary replica.
+    /// previous_epoch_last_sequence_number is the last LSN of the previous epoch.
+    /// Operations from the old primary with a higher LSN should be discarded.
+    /// Called only on active/idle secondary replicas.
+    async fn update_epoch(
+        &self,
+        epoch: Epoch,
+        previous_epoch_last_sequence_number: i64,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<()>;
+
+    /// Gets the last committed LSN in the state provider.
+    /// SF uses this to do primary selection, and the replicator uses it as the
+    /// starting point for replication.
+    fn get_last_committed_sequence_number(&self) -> crate::Result<i64>;
+
+    /// Indicates that a write quorum of replicas in the replica set has been lost,
+    /// and data loss might have occurred.
+    /// Returns is_state_changed. Non zero value indicates that the state provider
+    /// has changed its state (i.e. restored from backup), and SF will rebuild
+    /// the other replicas from this replica.
+    async fn on_data_loss(&self, cancellation_token: BoxedCancelToken) -> crate::Result<u8>;
+
+    /// Called on the idle secondary to get the context (i.e. current progress of the
+    /// secondary state) that is sent to the primary for building the copy state.
+    /// Return None if no copy context is needed.
+    fn get_copy_context(&self) -> crate::Result<Option<Box<dyn IOperationDataStream>>>;
+
+    /// Called on the primary to get the copy state up to up_to_sequence_number
+    /// for building an idle secondary.
+    /// copy_context_stream is the context returned from the secondary get_copy_context,
+    /// it is None if the secondary has no copy context.
+    fn get_copy_state(
+        &self,
+        up_to_sequence_number: i64,
+        copy_context_stream: Option<OperationDataStream>,
+    ) -> crate::Result<Box<dyn IOperationDataStream>>;
+}
+
 pub trait IStatefulServicePartition: Send + Sync + 'static {
-    /// Creates a FabricReplicator with the specified settings and returns it to the replica.
-    /// Not supported currently.
-    fn create_replicator(&self) -> crate::Result<Box<dyn IPrimaryReplicator>>;
+    /// Creates the SF built-in replicator with the specified settings.
+    /// The replicator uses the state provider to get and apply state during copy and
+    /// epoch changes. If settings are not provided, the defaults from the service
+    /// manifest/cluster settings are used.
+    /// Returns the primary replicator which should be returned from the replica open,
+    /// and the state replicator which the replica uses to replicate and receive operations.
+    fn create_replicator(
+        &self,
+        state_provider: std::sync::Arc<dyn IStateProvider>,
+        replicator_settings: Option<&crate::types::ReplicatorSettings>,
+    ) -> crate::Result<(Box<dyn IPrimaryReplicator>, StateReplicator)>;
 
     /// Provides access to the ServicePartitionInformation of the service, which contains the partition type and ID.
     fn get_partition_information(&self)
@@ -60,4 +60,8 @@ impl crate::runtime::IStatelessServicePartition for StatelessServicePartition {
         let healthinfo_ref = &healthinfo.into();
         unsafe { self.com_impl.ReportInstanceHealth(healthinfo_ref) }.map_err(crate::Error::from)
     }
+
+    fn try_get_com(&self) -> crate::Result<&IFabricStatelessServicePartition> {
+        Ok(&self.com_impl)
+    }
 }
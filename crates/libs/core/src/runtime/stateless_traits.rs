@@ -82,4 +82,11 @@ pub trait IStatelessServicePartition: Send + Sync + 'static {
         &self,
         health_info: &crate::types::HealthInformation,
     ) -> crate::Result<()>;
+
+    /// Returns the com object for proxy interop. This is only used when using Proxy.
+    fn try_get_com(
+        &self,
+    ) -> crate::Result<&mssf_com::FabricRuntime::IFabricStatelessServicePartition> {
+        Err(crate::ErrorCode::E_NOTIMPL.into())
+    }
 }
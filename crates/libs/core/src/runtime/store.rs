@@ -3,18 +3,45 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use std::ffi::c_void;
+// windows::core::implement macro generates snake case types.
+#![allow(non_camel_case_types)]
 
-use crate::{PCWSTR, WString};
+use std::{
+    ffi::c_void,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    PCWSTR, WString,
+    runtime::{
+        executor::{BoxedCancelToken, Executor},
+        store_proxy::{KVStoreEnumeratorProxy, KVStoreNotificationEnumerator},
+    },
+    sync::BridgeContext,
+};
+use futures_channel::mpsc;
+use futures_core::Stream;
 use mssf_com::{
+    FabricCommon::{IFabricAsyncOperationCallback, IFabricAsyncOperationContext},
     FabricRuntime::{
-        IFabricKeyValueStoreReplica8, IFabricStoreEventHandler, IFabricStoreEventHandler_Impl,
+        IFabricKeyValueStoreEnumerator, IFabricKeyValueStoreNotificationEnumerator,
+        IFabricKeyValueStoreReplica8, IFabricSecondaryEventHandler,
+        IFabricSecondaryEventHandler_Impl, IFabricStoreEventHandler, IFabricStoreEventHandler_Impl,
+        IFabricStoreEventHandler2, IFabricStoreEventHandler2_Impl, IFabricStorePostBackupHandler,
+        IFabricStorePostBackupHandler_Impl,
+    },
+    FabricTypes::{
+        FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND, FABRIC_STORE_BACKUP_INFO,
     },
-    FabricTypes::{FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND},
 };
 use windows_core::implement;
 
-use crate::types::{EseLocalStoreSettings, LocalStoreKind, ReplicatorSettings};
+use crate::types::{
+    EseLocalStoreSettings, KeyValueStoreNotificationMode, LocalStoreKind, ReplicatorSettings,
+    StoreBackupInfo,
+};
 
 #[implement(IFabricStoreEventHandler)]
 pub struct DummyStoreEventHandler {}
@@ -27,6 +54,298 @@ impl IFabricStoreEventHandler_Impl for DummyStoreEventHandler_Impl {
     fn OnDataLoss(&self) {}
 }
 
+/// Handles store events of the key value store replica.
+#[async_trait::async_trait]
+pub trait IStoreEventHandler: Send + Sync + 'static {
+    /// Called when write quorum is lost and data loss might have occurred.
+    /// Returns is_state_changed. If true, SF will rebuild other replicas from this replica,
+    /// i.e. after the store is restored from a backup.
+    async fn on_data_loss(&self, cancellation_token: BoxedCancelToken) -> crate::Result<bool>;
+}
+
+/// Receives store changes on secondaries.
+/// Notifications are enabled by KeyValueStoreNotificationMode when creating the store.
+/// The callbacks are invoked sequentially by SF, and should return quickly.
+pub trait ISecondaryEventHandler: Send + Sync + 'static {
+    /// Called when the copy of the store from the primary completes.
+    /// The enumerator gives access to the full store state.
+    fn on_copy_complete(&self, enumerator: KVStoreEnumeratorProxy) -> crate::Result<()>;
+
+    /// Called with the changes of each replication operation applied on the secondary.
+    fn on_replication_operation(
+        &self,
+        enumerator: KVStoreNotificationEnumerator,
+    ) -> crate::Result<()>;
+}
+
+/// Bridge from rust store event handler to com.
+#[implement(IFabricStoreEventHandler2)]
+pub struct StoreEventHandlerBridge<E>
+where
+    E: Executor,
+{
+    inner: Arc<Box<dyn IStoreEventHandler>>,
+    rt: E,
+}
+
+impl<E> StoreEventHandlerBridge<E>
+where
+    E: Executor,
+{
+    pub fn create(handler: Box<dyn IStoreEventHandler>, rt: E) -> StoreEventHandlerBridge<E> {
+        StoreEventHandlerBridge {
+            inner: Arc::new(handler),
+            rt,
+        }
+    }
+}
+
+impl<E> IFabricStoreEventHandler_Impl for StoreEventHandlerBridge_Impl<E>
+where
+    E: Executor,
+{
+    // SF uses the async version in IFabricStoreEventHandler2 when it is implemented.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret)
+    )]
+    fn OnDataLoss(&self) {}
+}
+
+impl<E> IFabricStoreEventHandler2_Impl for StoreEventHandlerBridge_Impl<E>
+where
+    E: Executor,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginOnDataLoss(
+        &self,
+        callback: windows_core::Ref<IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .on_data_loss(token)
+                .await
+                .map(u8::from)
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndOnDataLoss(
+        &self,
+        context: windows_core::Ref<IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        BridgeContext::result(context)?
+    }
+}
+
+/// Called after a store backup completes, i.e. to upload the backup folder
+/// to external storage. Passed into KVStoreProxy::backup.
+#[async_trait::async_trait]
+pub trait IStorePostBackupHandler: Send + Sync + 'static {
+    /// Returns true if the backup is handled successfully.
+    /// If false is returned, the next incremental backup will fail, and
+    /// a full backup is required.
+    async fn post_backup(
+        &self,
+        info: StoreBackupInfo,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<bool>;
+}
+
+/// Bridge from rust post backup handler to com.
+#[implement(IFabricStorePostBackupHandler)]
+pub struct StorePostBackupHandlerBridge<E>
+where
+    E: Executor,
+{
+    inner: Arc<Box<dyn IStorePostBackupHandler>>,
+    rt: E,
+}
+
+impl<E> StorePostBackupHandlerBridge<E>
+where
+    E: Executor,
+{
+    pub fn create(
+        handler: Box<dyn IStorePostBackupHandler>,
+        rt: E,
+    ) -> StorePostBackupHandlerBridge<E> {
+        StorePostBackupHandlerBridge {
+            inner: Arc::new(handler),
+            rt,
+        }
+    }
+}
+
+impl<E> IFabricStorePostBackupHandler_Impl for StorePostBackupHandlerBridge_Impl<E>
+where
+    E: Executor,
+{
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginPostBackup(
+        &self,
+        info: *const FABRIC_STORE_BACKUP_INFO,
+        callback: windows_core::Ref<IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let info2: StoreBackupInfo = unsafe { info.as_ref().unwrap().into() };
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .post_backup(info2, token)
+                .await
+                .map(u8::from)
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndPostBackup(
+        &self,
+        context: windows_core::Ref<IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        BridgeContext::result(context)?
+    }
+}
+
+/// Bridge from rust secondary event handler to com.
+#[implement(IFabricSecondaryEventHandler)]
+pub struct SecondaryEventHandlerBridge {
+    inner: Box<dyn ISecondaryEventHandler>,
+}
+
+impl SecondaryEventHandlerBridge {
+    pub fn create(handler: Box<dyn ISecondaryEventHandler>) -> SecondaryEventHandlerBridge {
+        SecondaryEventHandlerBridge { inner: handler }
+    }
+}
+
+impl IFabricSecondaryEventHandler_Impl for SecondaryEventHandlerBridge_Impl {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn OnCopyComplete(
+        &self,
+        enumerator: windows_core::Ref<IFabricKeyValueStoreEnumerator>,
+    ) -> crate::WinResult<()> {
+        let enumerator = enumerator.ok()?.clone();
+        self.inner
+            .on_copy_complete(KVStoreEnumeratorProxy::try_from(enumerator)?)
+            .map_err(crate::WinError::from)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn OnReplicationOperation(
+        &self,
+        enumerator: windows_core::Ref<IFabricKeyValueStoreNotificationEnumerator>,
+    ) -> crate::WinResult<()> {
+        let enumerator = enumerator.ok()?.clone();
+        self.inner
+            .on_replication_operation(KVStoreNotificationEnumerator::try_from(enumerator)?)
+            .map_err(crate::WinError::from)
+    }
+}
+
+/// A change of a single key in the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreChange {
+    pub key: WString,
+    /// Empty if the key is deleted.
+    pub value: Vec<u8>,
+    pub sequence_number: i64,
+    pub is_delete: bool,
+}
+
+/// Notification received by StoreNotificationStream.
+pub enum StoreNotification {
+    /// Copy from primary completed. The enumerator can be used to
+    /// rebuild the state from the store.
+    CopyComplete(KVStoreEnumeratorProxy),
+    /// Changes from one replication operation.
+    Replication(Vec<StoreChange>),
+}
+
+/// Secondary event handler that forwards notifications to the StoreNotificationStream.
+/// Replication changes are copied out of SF memory before the callback returns.
+pub struct ChannelSecondaryEventHandler {
+    tx: mpsc::UnboundedSender<StoreNotification>,
+}
+
+/// Stream of store notifications on the secondary.
+/// The stream ends when the store replica releases the event handler.
+pub struct StoreNotificationStream {
+    rx: mpsc::UnboundedReceiver<StoreNotification>,
+}
+
+/// Creates the channel based secondary event handler and its notification stream.
+/// The handler needs to be passed into create_com_key_value_store_replica2 via
+/// SecondaryEventHandlerBridge.
+pub fn store_notification_channel() -> (ChannelSecondaryEventHandler, StoreNotificationStream) {
+    let (tx, rx) = mpsc::unbounded();
+    (
+        ChannelSecondaryEventHandler { tx },
+        StoreNotificationStream { rx },
+    )
+}
+
+impl ISecondaryEventHandler for ChannelSecondaryEventHandler {
+    fn on_copy_complete(&self, enumerator: KVStoreEnumeratorProxy) -> crate::Result<()> {
+        // Receiver dropped means no one is listening.
+        let _ = self
+            .tx
+            .unbounded_send(StoreNotification::CopyComplete(enumerator));
+        Ok(())
+    }
+
+    fn on_replication_operation(
+        &self,
+        enumerator: KVStoreNotificationEnumerator,
+    ) -> crate::Result<()> {
+        let changes = enumerator
+            .map(|n| {
+                n.map(|n| StoreChange {
+                    key: WString::from_wide(n.key()),
+                    value: n.val().to_vec(),
+                    sequence_number: n.sequence_number(),
+                    is_delete: n.is_delete(),
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let _ = self
+            .tx
+            .unbounded_send(StoreNotification::Replication(changes));
+        Ok(())
+    }
+}
+
+impl Stream for StoreNotificationStream {
+    type Item = StoreNotification;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().rx).poll_next(cx)
+    }
+}
+
 pub fn create_com_key_value_store_replica(
     storename: &WString,
     partitionid: crate::GUID,
@@ -56,3 +375,40 @@ pub fn create_com_key_value_store_replica(
         )
         .map_err(crate::Error::from)
 }
+
+/// Creates the key value store replica with secondary notifications.
+/// secondaryeventhandler receives store changes on secondaries according to
+/// notificationmode, and can be created from SecondaryEventHandlerBridge.
+/// storeeventhandler can be created from StoreEventHandlerBridge.
+#[allow(clippy::too_many_arguments)]
+pub fn create_com_key_value_store_replica2(
+    storename: &WString,
+    partitionid: crate::GUID,
+    replicaid: i64,
+    replicatorsettings: &ReplicatorSettings,
+    localstorekind: LocalStoreKind,
+    localstoresettings: Option<&EseLocalStoreSettings>,
+    storeeventhandler: &IFabricStoreEventHandler,
+    secondaryeventhandler: Option<&IFabricSecondaryEventHandler>,
+    notificationmode: KeyValueStoreNotificationMode,
+) -> crate::Result<IFabricKeyValueStoreReplica8> {
+    let kind: FABRIC_LOCAL_STORE_KIND = localstorekind.into();
+    let local_settings: Option<FABRIC_ESE_LOCAL_STORE_SETTINGS> =
+        localstoresettings.map(|x| x.get_raw());
+    let local_settings_ptr = local_settings
+        .as_ref()
+        .map_or(std::ptr::null(), |x| x as *const _);
+    crate::API_TABLE
+        .fabric_create_key_value_store_replica2::<IFabricKeyValueStoreReplica8>(
+            PCWSTR::from_raw(storename.as_ptr()),
+            partitionid,
+            replicaid,
+            &replicatorsettings.get_raw(),
+            kind,
+            local_settings_ptr as *const c_void,
+            Some(storeeventhandler),
+            secondaryeventhandler,
+            notificationmode.into(),
+        )
+        .map_err(crate::Error::from)
+}
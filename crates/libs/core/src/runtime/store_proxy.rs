@@ -3,17 +3,32 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::{PCWSTR, runtime::executor::BoxedCancelToken};
+use crate::Interface;
+use crate::{
+    PCWSTR, WString,
+    mem::{BoxPool, GetRawWithBoxPool},
+    runtime::{
+        executor::{BoxedCancelToken, Executor},
+        store::{IStorePostBackupHandler, StorePostBackupHandlerBridge},
+    },
+    sync::FabricReceiver,
+};
 use mssf_com::{
     FabricRuntime::{
-        IFabricKeyValueStoreItemResult, IFabricKeyValueStoreReplica8, IFabricTransaction,
+        IFabricKeyValueStoreEnumerator, IFabricKeyValueStoreEnumerator2,
+        IFabricKeyValueStoreItemEnumerator2, IFabricKeyValueStoreItemMetadataEnumerator2,
+        IFabricKeyValueStoreItemMetadataResult, IFabricKeyValueStoreItemResult,
+        IFabricKeyValueStoreNotification, IFabricKeyValueStoreNotificationEnumerator,
+        IFabricKeyValueStoreNotificationEnumerator2, IFabricKeyValueStoreReplica8,
+        IFabricStorePostBackupHandler, IFabricTransaction,
     },
     FabricTypes::{FABRIC_KEY_VALUE_STORE_ITEM, FABRIC_KEY_VALUE_STORE_ITEM_METADATA},
 };
+use windows_core::Win32::Foundation::FILETIME;
 
 use crate::sync::fabric_begin_end_proxy;
 
-use crate::types::TransactionIsolationLevel;
+use crate::types::{KeyValueStoreRestoreSettings, StoreBackupOption, TransactionIsolationLevel};
 
 // wrapp for kv store
 #[derive(Clone)]
@@ -29,6 +44,43 @@ pub struct KVStoreItemProxy {
     com_impl: IFabricKeyValueStoreItemResult,
 }
 
+pub struct KVStoreItemMetadataProxy {
+    com_impl: IFabricKeyValueStoreItemMetadataResult,
+}
+
+/// Iterator over the items of the store, returned by KVStoreProxy::enumerate_by_key.
+/// The items are ordered by key.
+pub struct KVStoreItemEnumerator {
+    com_impl: IFabricKeyValueStoreItemEnumerator2,
+    done: bool,
+}
+
+/// Iterator over the item metadata of the store, returned by
+/// KVStoreProxy::enumerate_metadata_by_key.
+/// The metadata are ordered by key.
+pub struct KVStoreItemMetadataEnumerator {
+    com_impl: IFabricKeyValueStoreItemMetadataEnumerator2,
+    done: bool,
+}
+
+/// Store change received on secondaries, from the
+/// replication notifications of ISecondaryEventHandler.
+pub struct KVStoreNotificationProxy {
+    com_impl: IFabricKeyValueStoreNotification,
+}
+
+/// Iterator over the store changes of a replication operation.
+pub struct KVStoreNotificationEnumerator {
+    com_impl: IFabricKeyValueStoreNotificationEnumerator2,
+    done: bool,
+}
+
+/// Enumerates the store contents on secondaries after copy completes.
+/// Passed into ISecondaryEventHandler::on_copy_complete.
+pub struct KVStoreEnumeratorProxy {
+    com_impl: IFabricKeyValueStoreEnumerator2,
+}
+
 impl KVStoreItemProxy {
     pub fn key(&self) -> &[u16] {
         let item = self.get_item_inner();
@@ -36,6 +88,15 @@ impl KVStoreItemProxy {
         unsafe { meta.Key.as_wide() }
     }
 
+    /// The lsn that last modified the item.
+    pub fn sequence_number(&self) -> i64 {
+        Self::get_meta_inner(self.get_item_inner()).SequenceNumber
+    }
+
+    pub fn last_modified_utc(&self) -> FILETIME {
+        Self::get_meta_inner(self.get_item_inner()).LastModifiedUtc
+    }
+
     pub fn val(&self) -> &[u8] {
         let item = self.get_item_inner();
         let meta = Self::get_meta_inner(item);
@@ -50,6 +111,184 @@ impl KVStoreItemProxy {
     }
 }
 
+impl KVStoreItemMetadataProxy {
+    pub fn key(&self) -> &[u16] {
+        unsafe { self.get_meta_inner().Key.as_wide() }
+    }
+
+    pub fn value_size_in_bytes(&self) -> i32 {
+        self.get_meta_inner().ValueSizeInBytes
+    }
+
+    /// The lsn that last modified the item.
+    pub fn sequence_number(&self) -> i64 {
+        self.get_meta_inner().SequenceNumber
+    }
+
+    pub fn last_modified_utc(&self) -> FILETIME {
+        self.get_meta_inner().LastModifiedUtc
+    }
+
+    fn get_meta_inner(&self) -> &FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+        unsafe { self.com_impl.get_Metadata().as_ref().unwrap() }
+    }
+}
+
+impl KVStoreNotificationProxy {
+    pub fn key(&self) -> &[u16] {
+        unsafe { self.get_meta_inner().Key.as_wide() }
+    }
+
+    /// The value of the item. Empty if the item is deleted.
+    pub fn val(&self) -> &[u8] {
+        let item = self.get_item_inner();
+        let meta = self.get_meta_inner();
+        if item.Value.is_null() || meta.ValueSizeInBytes <= 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(item.Value, meta.ValueSizeInBytes as usize) }
+    }
+
+    /// The lsn of the change.
+    pub fn sequence_number(&self) -> i64 {
+        self.get_meta_inner().SequenceNumber
+    }
+
+    pub fn is_delete(&self) -> bool {
+        unsafe { self.com_impl.IsDelete() }
+    }
+
+    fn get_item_inner(&self) -> &FABRIC_KEY_VALUE_STORE_ITEM {
+        unsafe { self.com_impl.get_Item().as_ref().unwrap() }
+    }
+
+    fn get_meta_inner(&self) -> &FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+        unsafe { self.get_item_inner().Metadata.as_ref().unwrap() }
+    }
+}
+
+impl TryFrom<IFabricKeyValueStoreNotificationEnumerator> for KVStoreNotificationEnumerator {
+    type Error = crate::Error;
+
+    fn try_from(value: IFabricKeyValueStoreNotificationEnumerator) -> crate::Result<Self> {
+        Ok(Self {
+            com_impl: value.cast()?,
+            done: false,
+        })
+    }
+}
+
+impl Iterator for KVStoreNotificationEnumerator {
+    type Item = crate::Result<KVStoreNotificationProxy>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match unsafe { self.com_impl.TryMoveNext() } {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => unsafe { self.com_impl.get_Current() }
+                .map(|com_impl| Ok(KVStoreNotificationProxy { com_impl })),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+impl TryFrom<IFabricKeyValueStoreEnumerator> for KVStoreEnumeratorProxy {
+    type Error = crate::Error;
+
+    fn try_from(value: IFabricKeyValueStoreEnumerator) -> crate::Result<Self> {
+        Ok(Self {
+            com_impl: value.cast()?,
+        })
+    }
+}
+
+impl KVStoreEnumeratorProxy {
+    /// Enumerates items with keys starting with key_prefix.
+    /// See KVStoreProxy::enumerate_by_key for strict_prefix.
+    pub fn enumerate_by_key(
+        &self,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemEnumerator> {
+        let com = unsafe {
+            self.com_impl
+                .EnumerateByKey2(PCWSTR::from_raw(key_prefix.as_ptr()), strict_prefix)
+        }?;
+        Ok(KVStoreItemEnumerator {
+            com_impl: com.cast()?,
+            done: false,
+        })
+    }
+
+    /// Enumerates item metadata with keys starting with key_prefix.
+    pub fn enumerate_metadata_by_key(
+        &self,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemMetadataEnumerator> {
+        let com = unsafe {
+            self.com_impl
+                .EnumerateMetadataByKey2(PCWSTR::from_raw(key_prefix.as_ptr()), strict_prefix)
+        }?;
+        Ok(KVStoreItemMetadataEnumerator {
+            com_impl: com.cast()?,
+            done: false,
+        })
+    }
+}
+
+impl Iterator for KVStoreItemEnumerator {
+    type Item = crate::Result<KVStoreItemProxy>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match unsafe { self.com_impl.TryMoveNext() } {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => unsafe { self.com_impl.get_Current() }
+                .map(|com_impl| Ok(KVStoreItemProxy { com_impl })),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+impl Iterator for KVStoreItemMetadataEnumerator {
+    type Item = crate::Result<KVStoreItemMetadataProxy>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match unsafe { self.com_impl.TryMoveNext() } {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => unsafe { self.com_impl.get_Current() }
+                .map(|com_impl| Ok(KVStoreItemMetadataProxy { com_impl })),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
 impl KVStoreProxy {
     pub fn new(com_impl: IFabricKeyValueStoreReplica8) -> KVStoreProxy {
         KVStoreProxy { com_impl }
@@ -76,6 +315,90 @@ impl KVStoreProxy {
         Ok(KVStoreItemProxy { com_impl: com })
     }
 
+    // check sequence number is the lsn that last time the key got modified.
+    // if lsn does not match the update will error out with FABRIC_E_SEQUENCE_NUMBER_CHECK_FAILED.
+    // specify 0 to ignore check.
+    pub fn update(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+        value: &[u8],
+        checksequencenumber: i64,
+    ) -> crate::Result<()> {
+        unsafe {
+            self.com_impl.Update(
+                &tx.com_impl,
+                PCWSTR::from_raw(key.as_ptr()),
+                value,
+                checksequencenumber,
+            )
+        }
+        .map_err(crate::Error::from)
+    }
+
+    pub fn contains(&self, tx: &TransactionProxy, key: &[u16]) -> crate::Result<bool> {
+        let res = unsafe {
+            self.com_impl
+                .Contains(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        }?;
+        Ok(res != 0)
+    }
+
+    pub fn get_metadata(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+    ) -> crate::Result<KVStoreItemMetadataProxy> {
+        let com = unsafe {
+            self.com_impl
+                .GetMetadata(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        }?;
+        Ok(KVStoreItemMetadataProxy { com_impl: com })
+    }
+
+    /// Enumerates items with keys starting with key_prefix.
+    /// If strict_prefix is false, the enumeration starts at key_prefix and continues
+    /// to the end of the store instead of stopping at the first key without the prefix.
+    pub fn enumerate_by_key(
+        &self,
+        tx: &TransactionProxy,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemEnumerator> {
+        let com = unsafe {
+            self.com_impl.EnumerateByKey2(
+                &tx.com_impl,
+                PCWSTR::from_raw(key_prefix.as_ptr()),
+                strict_prefix,
+            )
+        }?;
+        Ok(KVStoreItemEnumerator {
+            com_impl: com.cast()?,
+            done: false,
+        })
+    }
+
+    /// Enumerates item metadata with keys starting with key_prefix.
+    /// See enumerate_by_key for strict_prefix.
+    pub fn enumerate_metadata_by_key(
+        &self,
+        tx: &TransactionProxy,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemMetadataEnumerator> {
+        let com = unsafe {
+            self.com_impl.EnumerateMetadataByKey2(
+                &tx.com_impl,
+                PCWSTR::from_raw(key_prefix.as_ptr()),
+                strict_prefix,
+            )
+        }?;
+        Ok(KVStoreItemMetadataEnumerator {
+            com_impl: com.cast()?,
+            done: false,
+        })
+    }
+
     // check sequence number is the lsn that last time the key got modified.
     // if lsn does not match the remove will error out.
     // specify 0 to ignore check.
@@ -96,6 +419,102 @@ impl KVStoreProxy {
     }
 }
 
+// region: backup and restore
+impl KVStoreProxy {
+    fn backup_internal(
+        &self,
+        backup_directory: &WString,
+        backup_option: StoreBackupOption,
+        post_backup_handler: Option<&IFabricStorePostBackupHandler>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginBackup(
+                    PCWSTR::from_raw(backup_directory.as_ptr()),
+                    backup_option.into(),
+                    post_backup_handler,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndBackup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn restore_internal(
+        &self,
+        backup_directory: &WString,
+        settings: Option<&KeyValueStoreRestoreSettings>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::Result<()>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let mut pool = BoxPool::new();
+        let settings_raw = settings.map(|s| s.get_raw_with_pool(&mut pool));
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                let dir = PCWSTR::from_raw(backup_directory.as_ptr());
+                match settings_raw.as_ref() {
+                    Some(raw) => com1.BeginRestore2(dir, raw, callback),
+                    None => com1.BeginRestore(dir, callback),
+                }
+            },
+            move |ctx| unsafe { com2.EndRestore(ctx) },
+            cancellation_token,
+        )
+    }
+
+    /// Backs up the store into backup_directory, which must not exist or be empty.
+    /// post_backup_handler is called after the backup files are written, and runs on rt.
+    /// Incremental backups require a post backup handler, and the store to be created
+    /// with incremental backup enabled.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn backup<E: Executor>(
+        &self,
+        backup_directory: &WString,
+        backup_option: StoreBackupOption,
+        post_backup_handler: Option<Box<dyn IStorePostBackupHandler>>,
+        rt: E,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        let post_backup_handler: Option<IFabricStorePostBackupHandler> =
+            post_backup_handler.map(|h| StorePostBackupHandlerBridge::create(h, rt).into());
+        self.backup_internal(
+            backup_directory,
+            backup_option,
+            post_backup_handler.as_ref(),
+            cancellation_token,
+        )
+        .await??;
+        Ok(())
+    }
+
+    /// Restores the store from backup_directory. Incremental backups need to be
+    /// merged into the full backup folder in order before restore.
+    /// Unless inline_reopen is set, the replica restarts after restore.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn restore(
+        &self,
+        backup_directory: &WString,
+        settings: Option<&KeyValueStoreRestoreSettings>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.restore_internal(backup_directory, settings, cancellation_token)
+            .await??;
+        Ok(())
+    }
+}
+// endregion: backup and restore
+
 impl TransactionProxy {
     pub fn get_id(&self) -> &crate::GUID {
         unsafe { self.com_impl.get_Id().as_ref().unwrap() }
@@ -128,3 +547,215 @@ impl TransactionProxy {
         unsafe { self.com_impl.Rollback() };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use mssf_com::{
+        FabricRuntime::{
+            IFabricKeyValueStoreItemEnumerator_Impl, IFabricKeyValueStoreItemEnumerator2,
+            IFabricKeyValueStoreItemEnumerator2_Impl,
+            IFabricKeyValueStoreItemMetadataEnumerator_Impl,
+            IFabricKeyValueStoreItemMetadataEnumerator2,
+            IFabricKeyValueStoreItemMetadataEnumerator2_Impl,
+            IFabricKeyValueStoreItemMetadataResult, IFabricKeyValueStoreItemMetadataResult_Impl,
+            IFabricKeyValueStoreItemResult, IFabricKeyValueStoreItemResult_Impl,
+        },
+        FabricTypes::{FABRIC_KEY_VALUE_STORE_ITEM, FABRIC_KEY_VALUE_STORE_ITEM_METADATA},
+    };
+    use windows_core::implement;
+
+    use super::{KVStoreItemEnumerator, KVStoreItemMetadataEnumerator};
+    use crate::{ErrorCode, WString};
+
+    #[implement(IFabricKeyValueStoreItemResult, IFabricKeyValueStoreItemMetadataResult)]
+    struct MockItem {
+        _key: WString,
+        _value: Vec<u8>,
+        metadata: Box<FABRIC_KEY_VALUE_STORE_ITEM_METADATA>,
+        item: Box<FABRIC_KEY_VALUE_STORE_ITEM>,
+    }
+
+    impl MockItem {
+        fn new(key: &str, value: &[u8], sequence_number: i64) -> Self {
+            let key = WString::from(key);
+            let mut value = value.to_vec();
+            let metadata = Box::new(FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+                Key: key.as_pcwstr(),
+                ValueSizeInBytes: value.len() as i32,
+                SequenceNumber: sequence_number,
+                ..Default::default()
+            });
+            let item = Box::new(FABRIC_KEY_VALUE_STORE_ITEM {
+                Metadata: &*metadata,
+                Value: value.as_mut_ptr(),
+                ..Default::default()
+            });
+            Self {
+                _key: key,
+                _value: value,
+                metadata,
+                item,
+            }
+        }
+    }
+
+    impl IFabricKeyValueStoreItemResult_Impl for MockItem_Impl {
+        fn get_Item(&self) -> *mut FABRIC_KEY_VALUE_STORE_ITEM {
+            &*self.item as *const _ as *mut _
+        }
+    }
+
+    impl IFabricKeyValueStoreItemMetadataResult_Impl for MockItem_Impl {
+        fn get_Metadata(&self) -> *mut FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+            &*self.metadata as *const _ as *mut _
+        }
+    }
+
+    /// Moves over the items, then reports the end, or fails at fail_at.
+    #[implement(
+        IFabricKeyValueStoreItemEnumerator2,
+        IFabricKeyValueStoreItemMetadataEnumerator2
+    )]
+    struct MockEnumerator {
+        items: Vec<(&'static str, &'static [u8], i64)>,
+        fail_at: Option<usize>,
+        position: Mutex<usize>,
+        moves: Arc<AtomicUsize>,
+    }
+
+    impl MockEnumerator {
+        /// Returns the enumerator and the count of moves.
+        fn create<I: From<Self>>(
+            items: Vec<(&'static str, &'static [u8], i64)>,
+            fail_at: Option<usize>,
+        ) -> (I, Arc<AtomicUsize>) {
+            let moves = Arc::new(AtomicUsize::new(0));
+            let mock = Self {
+                items,
+                fail_at,
+                position: Mutex::new(0),
+                moves: moves.clone(),
+            };
+            (mock.into(), moves)
+        }
+
+        fn try_move_next(&self) -> crate::WinResult<u8> {
+            self.moves.fetch_add(1, Ordering::SeqCst);
+            let mut position = self.position.lock().unwrap();
+            if self.fail_at == Some(*position) {
+                return Err(ErrorCode::FABRIC_E_OBJECT_CLOSED.into());
+            }
+            if *position == self.items.len() {
+                return Ok(0);
+            }
+            *position += 1;
+            Ok(1)
+        }
+
+        fn current(&self) -> Option<MockItem> {
+            let position = *self.position.lock().unwrap();
+            let (key, value, sequence_number) = self.items.get(position.checked_sub(1)?)?;
+            Some(MockItem::new(key, value, *sequence_number))
+        }
+    }
+
+    impl IFabricKeyValueStoreItemEnumerator_Impl for MockEnumerator_Impl {
+        fn MoveNext(&self) -> crate::WinResult<()> {
+            self.try_move_next().map(|_| ())
+        }
+
+        fn get_Current(&self) -> Option<IFabricKeyValueStoreItemResult> {
+            self.current().map(Into::into)
+        }
+    }
+
+    impl IFabricKeyValueStoreItemEnumerator2_Impl for MockEnumerator_Impl {
+        fn TryMoveNext(&self) -> crate::WinResult<u8> {
+            self.try_move_next()
+        }
+    }
+
+    impl IFabricKeyValueStoreItemMetadataEnumerator_Impl for MockEnumerator_Impl {
+        fn MoveNext(&self) -> crate::WinResult<()> {
+            self.try_move_next().map(|_| ())
+        }
+
+        fn get_Current(&self) -> Option<IFabricKeyValueStoreItemMetadataResult> {
+            self.current().map(Into::into)
+        }
+    }
+
+    impl IFabricKeyValueStoreItemMetadataEnumerator2_Impl for MockEnumerator_Impl {
+        fn TryMoveNext(&self) -> crate::WinResult<u8> {
+            self.try_move_next()
+        }
+    }
+
+    fn items() -> Vec<(&'static str, &'static [u8], i64)> {
+        vec![("a", b"1", 3), ("b", b"22", 5)]
+    }
+
+    #[test]
+    fn test_item_enumerator_end() {
+        let (com_impl, moves) = MockEnumerator::create(items(), None);
+        let mut enumerator = KVStoreItemEnumerator {
+            com_impl,
+            done: false,
+        };
+        let item = enumerator.next().unwrap().unwrap();
+        assert_eq!(item.key(), WString::from("a").as_wide());
+        assert_eq!(item.val(), b"1");
+        assert_eq!(item.sequence_number(), 3);
+        let item = enumerator.next().unwrap().unwrap();
+        assert_eq!(item.val(), b"22");
+        assert!(enumerator.next().is_none());
+        // The enumerator is not moved again after the end.
+        assert!(enumerator.next().is_none());
+        assert_eq!(moves.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_item_enumerator_error() {
+        let (com_impl, moves) = MockEnumerator::create(items(), Some(1));
+        let mut enumerator = KVStoreItemEnumerator {
+            com_impl,
+            done: false,
+        };
+        assert!(enumerator.next().unwrap().is_ok());
+        assert!(matches!(
+            enumerator.next(),
+            Some(Err(e)) if e == ErrorCode::FABRIC_E_OBJECT_CLOSED.into()
+        ));
+        assert!(enumerator.next().is_none());
+        assert_eq!(moves.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_item_metadata_enumerator_end() {
+        let (com_impl, moves) = MockEnumerator::create(items(), None);
+        let enumerator = KVStoreItemMetadataEnumerator {
+            com_impl,
+            done: false,
+        };
+        let metadata = enumerator
+            .map(|m| m.map(|m| (m.value_size_in_bytes(), m.sequence_number())))
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(metadata, vec![(1, 3), (2, 5)]);
+        assert_eq!(moves.load(Ordering::SeqCst), 3);
+
+        let (com_impl, moves) = MockEnumerator::create(vec![], Some(0));
+        let mut enumerator = KVStoreItemMetadataEnumerator {
+            com_impl,
+            done: false,
+        };
+        assert!(enumerator.next().unwrap().is_err());
+        assert!(enumerator.next().is_none());
+        assert_eq!(moves.load(Ordering::SeqCst), 1);
+    }
+}
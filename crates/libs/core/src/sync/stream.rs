@@ -0,0 +1,296 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Adapters between SF operation streams and rust Stream.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, ready},
+};
+
+use futures_core::Stream;
+use mssf_com::FabricRuntime::{IFabricOperation, IFabricOperationDataStream};
+
+use crate::{
+    ErrorCode,
+    runtime::{
+        IOperationDataStream,
+        executor::{BoxedCancelToken, Executor},
+        state_replicator::{Operation, OperationStream},
+        stateful_bridge::IFabricOperationDataStreamBridge,
+    },
+    sync::{FabricReceiver, SimpleCancelToken},
+};
+
+/// Stream of operations pumped from the SF operation stream.
+/// Each item needs to be acknowledged by the user if required.
+/// The stream ends when SF returns the end of stream, or after the first error.
+/// Dropping the adapter cancels the pending get operation.
+pub struct OperationStreamAdapter {
+    stream: OperationStream,
+    pending: Option<FabricReceiver<crate::Result<Option<IFabricOperation>>>>,
+    done: bool,
+}
+
+impl OperationStreamAdapter {
+    pub fn new(stream: OperationStream) -> Self {
+        Self {
+            stream,
+            pending: None,
+            done: false,
+        }
+    }
+}
+
+impl From<OperationStream> for OperationStreamAdapter {
+    fn from(stream: OperationStream) -> Self {
+        Self::new(stream)
+    }
+}
+
+impl Stream for OperationStreamAdapter {
+    type Item = crate::Result<Operation>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let rx = this.pending.get_or_insert_with(|| {
+            // The token allows the receiver to cancel the SF operation on drop.
+            this.stream
+                .get_operation_internal(Some(SimpleCancelToken::new_boxed()))
+        });
+        let res = ready!(Pin::new(rx).poll(cx));
+        this.pending = None;
+        match res.map_err(crate::Error::from).and_then(|r| r) {
+            Ok(Some(com)) => Poll::Ready(Some(Ok(Operation::from(com)))),
+            Ok(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Err(e) => {
+                this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+}
+
+/// Operation data stream from a rust Stream, where each item is
+/// the list of buffers of one operation data.
+/// Used to supply copy context and copy state in IStateProvider.
+pub struct OperationDataStreamAdapter<S> {
+    stream: Mutex<Pin<Box<S>>>,
+}
+
+impl<S, B> OperationDataStreamAdapter<S>
+where
+    S: Stream<Item = Vec<B>> + Send + 'static,
+    B: Into<Vec<u8>>,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: Mutex::new(Box::pin(stream)),
+        }
+    }
+
+    /// Converts into the com stream that can be passed to SF.
+    pub fn into_com(self, rt: impl Executor) -> IFabricOperationDataStream {
+        IFabricOperationDataStreamBridge::create(Box::new(self), rt).into()
+    }
+}
+
+#[async_trait::async_trait]
+impl<S, B> IOperationDataStream for OperationDataStreamAdapter<S>
+where
+    S: Stream<Item = Vec<B>> + Send + 'static,
+    B: Into<Vec<u8>>,
+{
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<Option<Vec<Vec<u8>>>> {
+        let mut cancel = cancellation_token.wait();
+        std::future::poll_fn(|cx| {
+            // Lock is only held within the poll.
+            if let Poll::Ready(item) = self.stream.lock().unwrap().as_mut().poll_next(cx) {
+                return Poll::Ready(Ok(
+                    item.map(|buffers| buffers.into_iter().map(Into::into).collect())
+                ));
+            }
+            if cancel.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(ErrorCode::E_ABORT.into()));
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::VecDeque,
+        pin::Pin,
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        task::{Context, Poll},
+    };
+
+    use futures_core::Stream;
+    use mssf_com::{
+        FabricCommon::{IFabricAsyncOperationCallback, IFabricAsyncOperationContext},
+        FabricRuntime::{
+            IFabricOperation, IFabricOperation_Impl, IFabricOperationStream,
+            IFabricOperationStream_Impl,
+        },
+        FabricTypes::{
+            FABRIC_OPERATION_DATA_BUFFER, FABRIC_OPERATION_METADATA, FABRIC_OPERATION_TYPE_NORMAL,
+        },
+    };
+    use windows_core::implement;
+
+    use super::{OperationDataStreamAdapter, OperationStreamAdapter};
+    use crate::{
+        runtime::{IOperationDataStream, state_replicator::OperationStream},
+        sync::{SimpleCancelToken, wait::AsyncContext},
+    };
+
+    #[implement(IFabricOperation)]
+    struct MockOperation {
+        metadata: FABRIC_OPERATION_METADATA,
+        acks: Arc<AtomicUsize>,
+    }
+
+    impl IFabricOperation_Impl for MockOperation_Impl {
+        fn get_Metadata(&self) -> *mut FABRIC_OPERATION_METADATA {
+            &self.metadata as *const _ as *mut _
+        }
+
+        fn GetData(&self, count: *mut u32) -> crate::WinResult<*mut FABRIC_OPERATION_DATA_BUFFER> {
+            unsafe { *count = 0 };
+            Ok(std::ptr::null_mut())
+        }
+
+        fn Acknowledge(&self) -> crate::WinResult<()> {
+            self.acks.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    // Completes each get synchronously with the next operation,
+    // and with a null operation once the operations run out.
+    #[implement(IFabricOperationStream)]
+    struct MockOperationStream {
+        operations: Mutex<VecDeque<IFabricOperation>>,
+    }
+
+    impl IFabricOperationStream_Impl for MockOperationStream_Impl {
+        fn BeginGetOperation(
+            &self,
+            callback: windows_core::Ref<IFabricAsyncOperationCallback>,
+        ) -> crate::WinResult<IFabricAsyncOperationContext> {
+            let ctx: IFabricAsyncOperationContext = AsyncContext::new(callback.as_ref()).into();
+            unsafe { ctx.Callback()?.Invoke(&ctx) };
+            Ok(ctx)
+        }
+
+        fn EndGetOperation(
+            &self,
+            _context: windows_core::Ref<IFabricAsyncOperationContext>,
+        ) -> crate::WinResult<IFabricOperation> {
+            self.operations
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(crate::WinError::empty)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_stream_adapter() {
+        let acks = Arc::new(AtomicUsize::new(0));
+        let operations = (1..=2)
+            .map(|sequence_number| {
+                MockOperation {
+                    metadata: FABRIC_OPERATION_METADATA {
+                        Type: FABRIC_OPERATION_TYPE_NORMAL,
+                        SequenceNumber: sequence_number,
+                        ..Default::default()
+                    },
+                    acks: acks.clone(),
+                }
+                .into()
+            })
+            .collect();
+        let com: IFabricOperationStream = MockOperationStream {
+            operations: Mutex::new(operations),
+        }
+        .into();
+        let mut adapter = OperationStreamAdapter::new(OperationStream::from(com));
+        let mut next =
+            async || std::future::poll_fn(|cx| Pin::new(&mut adapter).poll_next(cx)).await;
+
+        for sequence_number in 1..=2 {
+            let op = next().await.unwrap().unwrap();
+            assert_eq!(op.sequence_number().unwrap(), sequence_number);
+            assert!(op.data().unwrap().is_empty());
+            op.acknowledge().unwrap();
+        }
+        assert_eq!(acks.load(Ordering::SeqCst), 2);
+        // The null operation ends the stream.
+        assert!(next().await.is_none());
+        assert!(next().await.is_none());
+    }
+
+    struct VecStream(Vec<Vec<&'static str>>);
+
+    impl Stream for VecStream {
+        type Item = Vec<&'static str>;
+        fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            if this.0.is_empty() {
+                Poll::Ready(None)
+            } else {
+                Poll::Ready(Some(this.0.remove(0)))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_data_stream_adapter() {
+        let adapter = OperationDataStreamAdapter::new(VecStream(vec![vec!["a", "bc"], vec![]]));
+        let token = SimpleCancelToken::new_boxed();
+        assert_eq!(
+            adapter.get_next(token.clone()).await.unwrap(),
+            Some(vec![b"a".to_vec(), b"bc".to_vec()])
+        );
+        assert_eq!(adapter.get_next(token.clone()).await.unwrap(), Some(vec![]));
+        assert_eq!(adapter.get_next(token).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_operation_data_stream_adapter_cancel() {
+        struct PendingStream;
+        impl Stream for PendingStream {
+            type Item = Vec<Vec<u8>>;
+            fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                Poll::Pending
+            }
+        }
+        let adapter = OperationDataStreamAdapter::new(PendingStream);
+        let token = SimpleCancelToken::new_boxed();
+        token.cancel();
+        assert_eq!(
+            adapter.get_next(token).await.unwrap_err(),
+            crate::ErrorCode::E_ABORT.into()
+        );
+    }
+}
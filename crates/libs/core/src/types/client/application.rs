@@ -4,14 +4,46 @@
 // ------------------------------------------------------------
 
 use mssf_com::{
-    FabricClient::{IFabricApplicationHealthResult, IFabricGetApplicationListResult2},
+    FabricClient::{
+        IFabricApplicationHealthResult, IFabricApplicationUpgradeProgressResult2,
+        IFabricGetApplicationListResult2, IFabricGetApplicationTypePagedListResult,
+    },
     FabricTypes::{
-        FABRIC_APPLICATION_DEFINITION_KIND, FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION,
-        FABRIC_APPLICATION_QUERY_DESCRIPTION, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX1,
-        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX2, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX3,
-        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX4, FABRIC_APPLICATION_QUERY_RESULT_ITEM,
-        FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX1, FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX2,
-        FABRIC_APPLICATION_STATUS, FABRIC_DEPLOYED_APPLICATION_HEALTH_STATES_FILTER,
+        FABRIC_APPLICATION_CAPACITY_DESCRIPTION, FABRIC_APPLICATION_DEFINITION_KIND,
+        FABRIC_APPLICATION_DESCRIPTION, FABRIC_APPLICATION_DESCRIPTION_EX1,
+        FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION, FABRIC_APPLICATION_METRIC_DESCRIPTION,
+        FABRIC_APPLICATION_METRIC_LIST, FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_AUTOMATIC,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_DEFAULT,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_INVALID,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_MANUAL, FABRIC_APPLICATION_PARAMETER,
+        FABRIC_APPLICATION_PARAMETER_LIST, FABRIC_APPLICATION_QUERY_DESCRIPTION,
+        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX1, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX2,
+        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX3, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX4,
+        FABRIC_APPLICATION_QUERY_RESULT_ITEM, FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX1,
+        FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX2, FABRIC_APPLICATION_STATUS,
+        FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM, FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_APPLICATION_TYPE_STATUS, FABRIC_APPLICATION_TYPE_STATUS_AVAILABLE,
+        FABRIC_APPLICATION_TYPE_STATUS_FAILED, FABRIC_APPLICATION_TYPE_STATUS_PROVISIONING,
+        FABRIC_APPLICATION_TYPE_STATUS_UNPROVISIONING, FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_NONE, FABRIC_APPLICATION_UPGRADE_DESCRIPTION,
+        FABRIC_APPLICATION_UPGRADE_KIND_ROLLING, FABRIC_APPLICATION_UPGRADE_STATE,
+        FABRIC_APPLICATION_UPGRADE_STATE_FAILED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_COMPLETED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_PENDING,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_PENDING,
+        FABRIC_DELETE_APPLICATION_DESCRIPTION, FABRIC_DEPLOYED_APPLICATION_HEALTH_STATES_FILTER,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_EX1,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION, FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1,
+        FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION,
+        PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION,
     },
 };
 use windows_core::{PCWSTR, WString};
@@ -20,7 +52,7 @@ use crate::{
     mem::{BoxPool, GetRaw, GetRawWithBoxPool},
     types::{
         ApplicationHealthPolicy, HealthEventsFilter, HealthState, HealthStateFilterFlags,
-        PagingStatus, Uri,
+        PagingStatus, RollingUpgradeMode, RollingUpgradeMonitoringPolicy, UpgradeDomainStatus, Uri,
     },
 };
 use std::ffi::c_void;
@@ -337,3 +369,564 @@ impl From<&mssf_com::FabricTypes::FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE>
         }
     }
 }
+
+// FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApplicationPackageCleanupPolicy {
+    Invalid,
+    /// Uses the cluster wide setting.
+    #[default]
+    Default,
+    /// Removes the package from the image store after provision succeeds.
+    Automatic,
+    Manual,
+}
+
+impl From<ApplicationPackageCleanupPolicy> for FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY {
+    fn from(value: ApplicationPackageCleanupPolicy) -> Self {
+        match value {
+            ApplicationPackageCleanupPolicy::Invalid => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_INVALID
+            }
+            ApplicationPackageCleanupPolicy::Default => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_DEFAULT
+            }
+            ApplicationPackageCleanupPolicy::Automatic => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_AUTOMATIC
+            }
+            ApplicationPackageCleanupPolicy::Manual => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_MANUAL
+            }
+        }
+    }
+}
+
+// FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionApplicationTypeDescription {
+    /// Relative path of the application package in the image store.
+    pub build_path: WString,
+    /// If true, the call returns once the request is accepted,
+    /// and the progress can be checked by application type query.
+    pub is_async: bool,
+    pub application_package_cleanup_policy: ApplicationPackageCleanupPolicy,
+}
+
+impl GetRawWithBoxPool<FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION>
+    for ProvisionApplicationTypeDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION {
+        let ex1 = pool.push(Box::new(
+            FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_EX1 {
+                ApplicationPackageCleanupPolicy: self.application_package_cleanup_policy.into(),
+                Reserved: std::ptr::null_mut(),
+            },
+        ));
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION {
+            BuildPath: self.build_path.as_pcwstr(),
+            Async: self.is_async,
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct UnprovisionApplicationTypeDescription {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub is_async: bool,
+}
+
+impl GetRaw<FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION>
+    for UnprovisionApplicationTypeDescription
+{
+    fn get_raw(&self) -> FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION {
+        FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION {
+            ApplicationTypeName: self.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: self.application_type_version.as_pcwstr(),
+            Async: self.is_async,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Builds FABRIC_APPLICATION_PARAMETER_LIST from name value pairs.
+fn application_parameters_raw(
+    parameters: &[(WString, WString)],
+    pool: &mut BoxPool,
+) -> *mut FABRIC_APPLICATION_PARAMETER_LIST {
+    let items = parameters
+        .iter()
+        .map(|(name, value)| FABRIC_APPLICATION_PARAMETER {
+            Name: name.as_pcwstr(),
+            Value: value.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        })
+        .collect::<Vec<_>>();
+    let (count, items) = pool.push_vec(items);
+    pool.push(Box::new(FABRIC_APPLICATION_PARAMETER_LIST {
+        Count: count as u32,
+        Items: items as *mut _,
+    })) as *mut _
+}
+
+// FABRIC_APPLICATION_METRIC_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationMetricDescription {
+    pub name: WString,
+    pub node_reservation_capacity: u32,
+    pub maximum_node_capacity: u32,
+    pub total_application_capacity: u32,
+}
+
+/// Builds FABRIC_APPLICATION_METRIC_LIST.
+fn application_metrics_raw(
+    metrics: &[ApplicationMetricDescription],
+    pool: &mut BoxPool,
+) -> *const FABRIC_APPLICATION_METRIC_LIST {
+    let items = metrics
+        .iter()
+        .map(|m| FABRIC_APPLICATION_METRIC_DESCRIPTION {
+            Name: m.name.as_pcwstr(),
+            NodeReservationCapacity: m.node_reservation_capacity,
+            MaximumNodeCapacity: m.maximum_node_capacity,
+            TotalApplicationCapacity: m.total_application_capacity,
+            Reserved: std::ptr::null_mut(),
+        })
+        .collect::<Vec<_>>();
+    let (count, items) = pool.push_vec(items);
+    pool.push(Box::new(FABRIC_APPLICATION_METRIC_LIST {
+        Count: count as u32,
+        Capacities: items as *mut _,
+    }))
+}
+
+// FABRIC_APPLICATION_CAPACITY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationCapacityDescription {
+    pub maximum_nodes: u32,
+    pub minimum_nodes: u32,
+    pub metrics: Vec<ApplicationMetricDescription>,
+}
+
+impl GetRawWithBoxPool<FABRIC_APPLICATION_CAPACITY_DESCRIPTION> for ApplicationCapacityDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_APPLICATION_CAPACITY_DESCRIPTION {
+        FABRIC_APPLICATION_CAPACITY_DESCRIPTION {
+            MaximumNodes: self.maximum_nodes,
+            MinimumNodes: self.minimum_nodes,
+            Metrics: application_metrics_raw(&self.metrics, pool),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationDescription {
+    pub application_name: Uri,
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    /// Overrides of the parameters defined in the application manifest.
+    pub application_parameters: Vec<(WString, WString)>,
+    // EX1
+    pub application_capacity: Option<ApplicationCapacityDescription>,
+}
+
+impl GetRawWithBoxPool<FABRIC_APPLICATION_DESCRIPTION> for ApplicationDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_APPLICATION_DESCRIPTION {
+        let capacity = self
+            .application_capacity
+            .as_ref()
+            .map_or(std::ptr::null(), |c| {
+                let raw = Box::new(c.get_raw_with_pool(pool));
+                pool.push(raw)
+            });
+        let ex1 = pool.push(Box::new(FABRIC_APPLICATION_DESCRIPTION_EX1 {
+            ApplicationCapacity: capacity,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_APPLICATION_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            ApplicationTypeName: self.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: self.application_type_version.as_pcwstr(),
+            ApplicationParameters: application_parameters_raw(&self.application_parameters, pool),
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_APPLICATION_UPDATE_DESCRIPTION
+/// Updates the capacity of an application. Only the fields set are updated.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationUpdateDescription {
+    pub application_name: Uri,
+    /// Removes all capacity settings, other fields are ignored.
+    pub remove_application_capacity: bool,
+    pub maximum_nodes: Option<u32>,
+    pub minimum_nodes: Option<u32>,
+    pub metrics: Option<Vec<ApplicationMetricDescription>>,
+}
+
+impl GetRawWithBoxPool<FABRIC_APPLICATION_UPDATE_DESCRIPTION> for ApplicationUpdateDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_APPLICATION_UPDATE_DESCRIPTION {
+        let mut flags = FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_NONE.0;
+        if self.maximum_nodes.is_some() {
+            flags |= FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES.0;
+        }
+        if self.minimum_nodes.is_some() {
+            flags |= FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES.0;
+        }
+        if self.metrics.is_some() {
+            flags |= FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS.0;
+        }
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION {
+            Flags: flags as u32,
+            ApplicationName: self.application_name.as_raw(),
+            RemoveApplicationCapacity: self.remove_application_capacity,
+            MaximumNodes: self.maximum_nodes.unwrap_or(0),
+            MinimumNodes: self.minimum_nodes.unwrap_or(0),
+            Metrics: self
+                .metrics
+                .as_ref()
+                .map_or(std::ptr::null(), |m| application_metrics_raw(m, pool)),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION for applications.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationUpgradePolicyDescription {
+    pub rolling_upgrade_mode: RollingUpgradeMode,
+    pub force_restart: bool,
+    pub upgrade_replica_set_check_timeout_seconds: u32,
+    // EX1
+    /// Used in monitored mode.
+    pub monitoring_policy: Option<RollingUpgradeMonitoringPolicy>,
+    /// Health policy to evaluate the application during upgrade.
+    /// Defaults to the policy in the application manifest.
+    pub health_policy: Option<ApplicationHealthPolicy>,
+}
+
+impl GetRawWithBoxPool<FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION>
+    for ApplicationUpgradePolicyDescription
+{
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+        let monitoring_policy = self
+            .monitoring_policy
+            .as_ref()
+            .map_or(std::ptr::null(), |p| {
+                let raw = Box::new(p.get_raw_with_pool(pool));
+                pool.push(raw)
+            });
+        let health_policy = self
+            .health_policy
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |p| {
+                let raw = Box::new(p.get_raw_with_pool(pool));
+                pool.push(raw) as *mut c_void
+            });
+        let ex1 = pool.push(Box::new(FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1 {
+            MonitoringPolicy: monitoring_policy,
+            HealthPolicy: health_policy,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+            RollingUpgradeMode: self.rolling_upgrade_mode.into(),
+            ForceRestart: self.force_restart,
+            UpgradeReplicaSetCheckTimeoutInSeconds: self.upgrade_replica_set_check_timeout_seconds,
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_APPLICATION_UPGRADE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationUpgradeDescription {
+    pub application_name: Uri,
+    /// The application type version to upgrade to. It needs to be provisioned.
+    pub target_application_type_version: WString,
+    /// Parameters of the upgraded application.
+    /// Parameters not given are reset to the manifest defaults.
+    pub application_parameters: Vec<(WString, WString)>,
+    pub upgrade_policy: ApplicationUpgradePolicyDescription,
+}
+
+impl GetRawWithBoxPool<FABRIC_APPLICATION_UPGRADE_DESCRIPTION> for ApplicationUpgradeDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_APPLICATION_UPGRADE_DESCRIPTION {
+        let policy = Box::new(self.upgrade_policy.get_raw_with_pool(pool));
+        FABRIC_APPLICATION_UPGRADE_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            TargetApplicationTypeVersion: self.target_application_type_version.as_pcwstr(),
+            ApplicationParameters: application_parameters_raw(&self.application_parameters, pool),
+            UpgradeKind: FABRIC_APPLICATION_UPGRADE_KIND_ROLLING,
+            UpgradePolicyDescription: pool.push(policy) as *mut c_void,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DELETE_APPLICATION_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeleteApplicationDescription {
+    pub application_name: Uri,
+    /// Deletes without waiting for services to close gracefully.
+    pub force_delete: bool,
+}
+
+impl GetRaw<FABRIC_DELETE_APPLICATION_DESCRIPTION> for DeleteApplicationDescription {
+    fn get_raw(&self) -> FABRIC_DELETE_APPLICATION_DESCRIPTION {
+        FABRIC_DELETE_APPLICATION_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            ForceDelete: self.force_delete,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_UPGRADE_STATE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationUpgradeState {
+    Invalid,
+    RollingBackInProgress,
+    RollingBackCompleted,
+    RollingForwardPending,
+    RollingForwardInProgress,
+    RollingForwardCompleted,
+    Failed,
+    RollingBackPending,
+}
+
+impl From<FABRIC_APPLICATION_UPGRADE_STATE> for ApplicationUpgradeState {
+    fn from(value: FABRIC_APPLICATION_UPGRADE_STATE) -> Self {
+        match value {
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS => {
+                Self::RollingBackInProgress
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_COMPLETED => Self::RollingBackCompleted,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_PENDING => Self::RollingForwardPending,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS => {
+                Self::RollingForwardInProgress
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED => {
+                Self::RollingForwardCompleted
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_FAILED => Self::Failed,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_PENDING => Self::RollingBackPending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl ApplicationUpgradeState {
+    /// Whether the upgrade has reached a final state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::RollingBackCompleted | Self::RollingForwardCompleted | Self::Failed
+        )
+    }
+}
+
+// IFabricApplicationUpgradeProgressResult2
+#[derive(Debug, Clone)]
+pub struct ApplicationUpgradeProgress {
+    pub application_name: Uri,
+    pub application_type_name: WString,
+    pub target_application_type_version: WString,
+    pub upgrade_state: ApplicationUpgradeState,
+    pub upgrade_domains: Vec<UpgradeDomainStatus>,
+    pub rolling_upgrade_mode: RollingUpgradeMode,
+    /// The upgrade domain to pass to move next in manual mode.
+    /// Empty if there is none.
+    pub next_upgrade_domain: WString,
+}
+
+impl TryFrom<&IFabricApplicationUpgradeProgressResult2> for ApplicationUpgradeProgress {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricApplicationUpgradeProgressResult2) -> crate::Result<Self> {
+        let mut count = 0;
+        let domains = unsafe { value.GetUpgradeDomains(&mut count) }?;
+        Ok(Self {
+            application_name: Uri::from(unsafe { value.get_ApplicationName() }),
+            application_type_name: WString::from(unsafe { value.get_ApplicationTypeName() }),
+            target_application_type_version: WString::from(unsafe {
+                value.get_TargetApplicationTypeVersion()
+            }),
+            upgrade_state: unsafe { value.get_UpgradeState() }.into(),
+            upgrade_domains: crate::iter::vec_from_raw_com(count as usize, domains),
+            rolling_upgrade_mode: unsafe { value.get_RollingUpgradeMode() }.into(),
+            next_upgrade_domain: WString::from(unsafe { value.get_NextUpgradeDomain() }),
+        })
+    }
+}
+
+// PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationTypeQueryDescription {
+    pub application_type_name_filter: Option<WString>,
+    pub max_results: Option<i32>,
+    pub continuation_token: Option<WString>,
+    pub exclude_application_parameters: bool,
+}
+
+impl GetRaw<PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION> for ApplicationTypeQueryDescription {
+    fn get_raw(&self) -> PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION {
+        PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION {
+            ApplicationTypeNameFilter: self.application_type_name_filter.as_ref().into(),
+            MaxResults: self.max_results.unwrap_or(0), // 0 means no limit
+            ContinuationToken: self.continuation_token.as_ref().into(),
+            ExcludeApplicationParameters: self.exclude_application_parameters,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_TYPE_STATUS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationTypeStatus {
+    Invalid,
+    Provisioning,
+    Available,
+    Unprovisioning,
+    Failed,
+}
+
+impl From<FABRIC_APPLICATION_TYPE_STATUS> for ApplicationTypeStatus {
+    fn from(value: FABRIC_APPLICATION_TYPE_STATUS) -> Self {
+        match value {
+            FABRIC_APPLICATION_TYPE_STATUS_PROVISIONING => Self::Provisioning,
+            FABRIC_APPLICATION_TYPE_STATUS_AVAILABLE => Self::Available,
+            FABRIC_APPLICATION_TYPE_STATUS_UNPROVISIONING => Self::Unprovisioning,
+            FABRIC_APPLICATION_TYPE_STATUS_FAILED => Self::Failed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// IFabricGetApplicationTypePagedListResult
+#[derive(Debug, Clone)]
+pub struct ApplicationTypeListResult {
+    pub items: Vec<ApplicationTypeQueryResultItem>,
+    pub paging_status: Option<PagingStatus>,
+}
+
+impl From<&IFabricGetApplicationTypePagedListResult> for ApplicationTypeListResult {
+    fn from(value: &IFabricGetApplicationTypePagedListResult) -> Self {
+        let items = unsafe { value.get_ApplicationTypePagedList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        let paging_status = unsafe { value.get_PagingStatus().as_ref() }.map(|ps| ps.into());
+        Self {
+            items,
+            paging_status,
+        }
+    }
+}
+
+// FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct ApplicationTypeQueryResultItem {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    /// Empty if the query excluded application parameters.
+    pub default_parameters: Vec<(WString, WString)>,
+    pub status: ApplicationTypeStatus,
+    pub status_details: WString,
+}
+
+impl From<&FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM> for ApplicationTypeQueryResultItem {
+    fn from(value: &FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM) -> Self {
+        let default_parameters = unsafe { value.DefaultParameters.as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p: ApplicationParameter| (p.name, p.value))
+            .collect();
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        Self {
+            application_type_name: WString::from(value.ApplicationTypeName),
+            application_type_version: WString::from(value.ApplicationTypeVersion),
+            default_parameters,
+            status: ex1.map_or(ApplicationTypeStatus::Invalid, |ex1| ex1.Status.into()),
+            status_details: ex1.map_or(WString::new(), |ex1| WString::from(ex1.StatusDetails)),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_PARAMETER
+struct ApplicationParameter {
+    name: WString,
+    value: WString,
+}
+
+impl From<&FABRIC_APPLICATION_PARAMETER> for ApplicationParameter {
+    fn from(value: &FABRIC_APPLICATION_PARAMETER) -> Self {
+        Self {
+            name: WString::from(value.Name),
+            value: WString::from(value.Value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_APPLICATION_DESCRIPTION_EX1, FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES,
+    };
+    use windows_core::WString;
+
+    use super::{ApplicationDescription, ApplicationUpdateDescription};
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::Uri,
+    };
+
+    #[test]
+    fn test_application_description_raw() {
+        let desc = ApplicationDescription {
+            application_name: Uri::from("fabric:/myapp"),
+            application_type_name: WString::from("MyAppType"),
+            application_type_version: WString::from("1.0.0"),
+            application_parameters: vec![(WString::from("Count"), WString::from("3"))],
+            application_capacity: None,
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        let params = unsafe { raw.ApplicationParameters.as_ref() }.unwrap();
+        assert_eq!(params.Count, 1);
+        let param = unsafe { params.Items.as_ref() }.unwrap();
+        assert_eq!(WString::from(param.Name), WString::from("Count"));
+        assert_eq!(WString::from(param.Value), WString::from("3"));
+        let ex1 = unsafe { (raw.Reserved as *const FABRIC_APPLICATION_DESCRIPTION_EX1).as_ref() }
+            .unwrap();
+        assert!(ex1.ApplicationCapacity.is_null());
+    }
+
+    #[test]
+    fn test_application_update_flags() {
+        let desc = ApplicationUpdateDescription {
+            application_name: Uri::from("fabric:/myapp"),
+            minimum_nodes: Some(2),
+            metrics: Some(vec![]),
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert_eq!(
+            raw.Flags,
+            (FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES.0
+                | FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS.0) as u32
+        );
+        assert_eq!(raw.MinimumNodes, 2);
+        assert!(!raw.Metrics.is_null());
+    }
+}
@@ -0,0 +1,725 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Chaos types.
+
+use std::ffi::c_void;
+
+use mssf_com::FabricTypes::{
+    FABRIC_CHAOS_DESCRIPTION, FABRIC_CHAOS_EVENT, FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS,
+    FABRIC_CHAOS_EVENT_KIND_STARTED, FABRIC_CHAOS_EVENT_KIND_STOPPED,
+    FABRIC_CHAOS_EVENT_KIND_TEST_ERROR, FABRIC_CHAOS_EVENT_KIND_VALIDATION_FAILED,
+    FABRIC_CHAOS_EVENT_KIND_WAITING, FABRIC_CHAOS_PARAMETERS, FABRIC_CHAOS_PARAMETERS_EX1,
+    FABRIC_CHAOS_PARAMETERS_EX2, FABRIC_CHAOS_REPORT, FABRIC_CHAOS_REPORT_FILTER,
+    FABRIC_CHAOS_SCHEDULE, FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP,
+    FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP_ITEM, FABRIC_CHAOS_SCHEDULE_DESCRIPTION,
+    FABRIC_CHAOS_SCHEDULE_JOB, FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS,
+    FABRIC_CHAOS_SCHEDULE_JOB_LIST, FABRIC_CHAOS_SCHEDULE_STATUS,
+    FABRIC_CHAOS_SCHEDULE_STATUS_ACTIVE, FABRIC_CHAOS_SCHEDULE_STATUS_EXPIRED,
+    FABRIC_CHAOS_SCHEDULE_STATUS_PENDING, FABRIC_CHAOS_SCHEDULE_STATUS_STOPPED,
+    FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC, FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC_LIST,
+    FABRIC_CHAOS_SCHEDULE_TIME_UTC, FABRIC_CHAOS_STATUS, FABRIC_CHAOS_STATUS_RUNNING,
+    FABRIC_CHAOS_STATUS_STOPPED, FABRIC_CHAOS_TARGET_FILTER, FABRIC_EVENT_CONTEXT_MAP,
+    FABRIC_EVENT_CONTEXT_MAP_ITEM, FABRIC_EXECUTING_FAULTS_EVENT,
+    FABRIC_GET_CHAOS_REPORT_DESCRIPTION, FABRIC_START_CHAOS_DESCRIPTION, FABRIC_STARTED_EVENT,
+    FABRIC_STOPPED_EVENT, FABRIC_TEST_ERROR_EVENT, FABRIC_VALIDATION_FAILED_EVENT,
+    FABRIC_WAITING_EVENT,
+};
+use windows_core::{PCWSTR, WString, Win32::Foundation::FILETIME};
+
+use super::{string_list_from_raw, string_list_raw};
+use crate::{
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    types::ClusterHealthPolicy,
+};
+
+const FILETIME_ZERO: FILETIME = FILETIME {
+    dwLowDateTime: 0,
+    dwHighDateTime: 0,
+};
+
+// FABRIC_CHAOS_TARGET_FILTER
+/// Limits the faults to the listed node types and applications.
+/// Empty lists do not filter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosTargetFilter {
+    pub node_type_inclusion_list: Vec<WString>,
+    pub application_inclusion_list: Vec<WString>,
+}
+
+impl GetRawWithBoxPool<FABRIC_CHAOS_TARGET_FILTER> for ChaosTargetFilter {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_CHAOS_TARGET_FILTER {
+        FABRIC_CHAOS_TARGET_FILTER {
+            NodeTypeInclusionList: string_list_raw(&self.node_type_inclusion_list, pool),
+            ApplicationInclusionList: string_list_raw(&self.application_inclusion_list, pool),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+impl From<&FABRIC_CHAOS_TARGET_FILTER> for ChaosTargetFilter {
+    fn from(value: &FABRIC_CHAOS_TARGET_FILTER) -> Self {
+        Self {
+            node_type_inclusion_list: string_list_from_raw(value.NodeTypeInclusionList),
+            application_inclusion_list: string_list_from_raw(value.ApplicationInclusionList),
+        }
+    }
+}
+
+// FABRIC_CHAOS_PARAMETERS
+/// Controls how Chaos runs. Durations are in seconds.
+#[derive(Debug, Clone, Default)]
+pub struct ChaosParameters {
+    pub max_cluster_stabilization_timeout_seconds: u32,
+    pub max_concurrent_faults: u32,
+    pub enable_move_replica_faults: bool,
+    pub time_to_run_seconds: u64,
+    pub wait_time_between_iterations_seconds: u32,
+    pub wait_time_between_faults_seconds: u32,
+    /// Key value pairs recorded with the Chaos run.
+    pub context: Vec<(WString, WString)>,
+    // EX1
+    /// Health policy to validate the cluster between iterations.
+    pub cluster_health_policy: Option<ClusterHealthPolicy>,
+    // EX2
+    pub target_filter: Option<ChaosTargetFilter>,
+}
+
+impl GetRawWithBoxPool<FABRIC_CHAOS_PARAMETERS> for ChaosParameters {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_CHAOS_PARAMETERS {
+        let target_filter = self.target_filter.as_ref().map_or(std::ptr::null(), |f| {
+            let raw = Box::new(f.get_raw_with_pool(pool));
+            pool.push(raw)
+        });
+        let ex2 = pool.push(Box::new(FABRIC_CHAOS_PARAMETERS_EX2 {
+            ChaosTargetFilter: target_filter,
+            Reserved: std::ptr::null_mut(),
+        }));
+        let health_policy = self
+            .cluster_health_policy
+            .as_ref()
+            .map_or(std::ptr::null(), |p| pool.push(Box::new(p.get_raw())));
+        let ex1 = pool.push(Box::new(FABRIC_CHAOS_PARAMETERS_EX1 {
+            ClusterHealthPolicy: health_policy,
+            Reserved: ex2 as *mut c_void,
+        }));
+        let items = self
+            .context
+            .iter()
+            .map(|(k, v)| FABRIC_EVENT_CONTEXT_MAP_ITEM {
+                Key: k.as_pcwstr(),
+                Value: v.as_pcwstr(),
+            })
+            .collect::<Vec<_>>();
+        let (count, items) = pool.push_vec(items);
+        let context = pool.push(Box::new(FABRIC_EVENT_CONTEXT_MAP {
+            Count: count as u32,
+            Items: items as *mut _,
+        }));
+        FABRIC_CHAOS_PARAMETERS {
+            MaxClusterStabilizationTimeoutInSeconds: self.max_cluster_stabilization_timeout_seconds,
+            MaxConcurrentFaults: self.max_concurrent_faults,
+            EnableMoveReplicaFaults: self.enable_move_replica_faults,
+            TimeToRunInSeconds: self.time_to_run_seconds,
+            WaitTimeBetweenIterationsInSeconds: self.wait_time_between_iterations_seconds,
+            WaitTimeBetweenFaultsInSeconds: self.wait_time_between_faults_seconds,
+            Context: context,
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+impl From<&FABRIC_CHAOS_PARAMETERS> for ChaosParameters {
+    fn from(value: &FABRIC_CHAOS_PARAMETERS) -> Self {
+        let context = unsafe { value.Context.as_ref() }
+            .map(|m| {
+                crate::iter::vec_from_raw_com::<_, ContextItem>(m.Count as usize, m.Items)
+                    .into_iter()
+                    .map(|i| (i.0, i.1))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX2).as_ref()
+        });
+        Self {
+            max_cluster_stabilization_timeout_seconds: value
+                .MaxClusterStabilizationTimeoutInSeconds,
+            max_concurrent_faults: value.MaxConcurrentFaults,
+            enable_move_replica_faults: value.EnableMoveReplicaFaults,
+            time_to_run_seconds: value.TimeToRunInSeconds,
+            wait_time_between_iterations_seconds: value.WaitTimeBetweenIterationsInSeconds,
+            wait_time_between_faults_seconds: value.WaitTimeBetweenFaultsInSeconds,
+            context,
+            cluster_health_policy: ex1
+                .and_then(|ex1| unsafe { ex1.ClusterHealthPolicy.as_ref() })
+                .map(ClusterHealthPolicy::from),
+            target_filter: ex2
+                .and_then(|ex2| unsafe { ex2.ChaosTargetFilter.as_ref() })
+                .map(ChaosTargetFilter::from),
+        }
+    }
+}
+
+// Helper to convert FABRIC_EVENT_CONTEXT_MAP_ITEM.
+struct ContextItem(WString, WString);
+
+impl From<&FABRIC_EVENT_CONTEXT_MAP_ITEM> for ContextItem {
+    fn from(value: &FABRIC_EVENT_CONTEXT_MAP_ITEM) -> Self {
+        Self(WString::from(value.Key), WString::from(value.Value))
+    }
+}
+
+// FABRIC_START_CHAOS_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct StartChaosDescription {
+    pub chaos_parameters: ChaosParameters,
+}
+
+impl GetRawWithBoxPool<FABRIC_START_CHAOS_DESCRIPTION> for StartChaosDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_START_CHAOS_DESCRIPTION {
+        let parameters = Box::new(self.chaos_parameters.get_raw_with_pool(pool));
+        FABRIC_START_CHAOS_DESCRIPTION {
+            ChaosParameters: pool.push(parameters) as *mut _,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_STATUS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosStatus {
+    Invalid,
+    Running,
+    Stopped,
+}
+
+impl From<FABRIC_CHAOS_STATUS> for ChaosStatus {
+    fn from(value: FABRIC_CHAOS_STATUS) -> Self {
+        match value {
+            FABRIC_CHAOS_STATUS_RUNNING => Self::Running,
+            FABRIC_CHAOS_STATUS_STOPPED => Self::Stopped,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_STATUS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosScheduleStatus {
+    Invalid,
+    Stopped,
+    Active,
+    Expired,
+    Pending,
+}
+
+impl From<FABRIC_CHAOS_SCHEDULE_STATUS> for ChaosScheduleStatus {
+    fn from(value: FABRIC_CHAOS_SCHEDULE_STATUS) -> Self {
+        match value {
+            FABRIC_CHAOS_SCHEDULE_STATUS_STOPPED => Self::Stopped,
+            FABRIC_CHAOS_SCHEDULE_STATUS_ACTIVE => Self::Active,
+            FABRIC_CHAOS_SCHEDULE_STATUS_EXPIRED => Self::Expired,
+            FABRIC_CHAOS_SCHEDULE_STATUS_PENDING => Self::Pending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_CHAOS_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ChaosDescription {
+    pub chaos_parameters: Option<ChaosParameters>,
+    pub status: ChaosStatus,
+    pub schedule_status: ChaosScheduleStatus,
+}
+
+impl From<&FABRIC_CHAOS_DESCRIPTION> for ChaosDescription {
+    fn from(value: &FABRIC_CHAOS_DESCRIPTION) -> Self {
+        Self {
+            chaos_parameters: unsafe { value.ChaosParameters.as_ref() }.map(ChaosParameters::from),
+            status: value.Status.into(),
+            schedule_status: value.ScheduleStatus.into(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_EVENT
+#[derive(Debug, Clone)]
+pub enum ChaosEvent {
+    Invalid,
+    Started {
+        time_stamp_utc: FILETIME,
+        chaos_parameters: Option<ChaosParameters>,
+    },
+    Stopped {
+        time_stamp_utc: FILETIME,
+        reason: WString,
+    },
+    ExecutingFaults {
+        time_stamp_utc: FILETIME,
+        faults: Vec<WString>,
+    },
+    Waiting {
+        time_stamp_utc: FILETIME,
+        reason: WString,
+    },
+    ValidationFailed {
+        time_stamp_utc: FILETIME,
+        reason: WString,
+    },
+    TestError {
+        time_stamp_utc: FILETIME,
+        reason: WString,
+    },
+}
+
+impl From<&FABRIC_CHAOS_EVENT> for ChaosEvent {
+    fn from(value: &FABRIC_CHAOS_EVENT) -> Self {
+        match value.Kind {
+            FABRIC_CHAOS_EVENT_KIND_STARTED => {
+                match unsafe { (value.Value as *const FABRIC_STARTED_EVENT).as_ref() } {
+                    Some(e) => Self::Started {
+                        time_stamp_utc: e.TimeStampUtc,
+                        chaos_parameters: unsafe { e.ChaosParameters.as_ref() }
+                            .map(ChaosParameters::from),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_STOPPED => {
+                match unsafe { (value.Value as *const FABRIC_STOPPED_EVENT).as_ref() } {
+                    Some(e) => Self::Stopped {
+                        time_stamp_utc: e.TimeStampUtc,
+                        reason: WString::from(e.Reason),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS => {
+                match unsafe { (value.Value as *const FABRIC_EXECUTING_FAULTS_EVENT).as_ref() } {
+                    Some(e) => Self::ExecutingFaults {
+                        time_stamp_utc: e.TimeStampUtc,
+                        faults: string_list_from_raw(e.Faults),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_WAITING => {
+                match unsafe { (value.Value as *const FABRIC_WAITING_EVENT).as_ref() } {
+                    Some(e) => Self::Waiting {
+                        time_stamp_utc: e.TimeStampUtc,
+                        reason: WString::from(e.Reason),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_VALIDATION_FAILED => {
+                match unsafe { (value.Value as *const FABRIC_VALIDATION_FAILED_EVENT).as_ref() } {
+                    Some(e) => Self::ValidationFailed {
+                        time_stamp_utc: e.TimeStampUtc,
+                        reason: WString::from(e.Reason),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_TEST_ERROR => {
+                match unsafe { (value.Value as *const FABRIC_TEST_ERROR_EVENT).as_ref() } {
+                    Some(e) => Self::TestError {
+                        time_stamp_utc: e.TimeStampUtc,
+                        reason: WString::from(e.Reason),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_GET_CHAOS_REPORT_DESCRIPTION
+/// Selects the Chaos events to report.
+/// Either give a time filter to start, or the continuation token of the
+/// previous report to continue.
+#[derive(Debug, Clone, Default)]
+pub struct GetChaosReportDescription {
+    pub start_time_utc: Option<FILETIME>,
+    pub end_time_utc: Option<FILETIME>,
+    pub continuation_token: Option<WString>,
+}
+
+impl GetRawWithBoxPool<FABRIC_GET_CHAOS_REPORT_DESCRIPTION> for GetChaosReportDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_GET_CHAOS_REPORT_DESCRIPTION {
+        let filter = if self.start_time_utc.is_some() || self.end_time_utc.is_some() {
+            pool.push(Box::new(FABRIC_CHAOS_REPORT_FILTER {
+                StartTimeUtc: self.start_time_utc.unwrap_or(FILETIME_ZERO),
+                EndTimeUtc: self.end_time_utc.unwrap_or(FILETIME_ZERO),
+                Reserved: std::ptr::null_mut(),
+            })) as *mut _
+        } else {
+            std::ptr::null_mut()
+        };
+        FABRIC_GET_CHAOS_REPORT_DESCRIPTION {
+            Filter: filter,
+            ContinuationToken: self
+                .continuation_token
+                .as_ref()
+                .map_or(PCWSTR::null(), |t| t.as_pcwstr()),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_REPORT
+#[derive(Debug, Clone)]
+pub struct ChaosReport {
+    pub chaos_parameters: Option<ChaosParameters>,
+    pub status: ChaosStatus,
+    /// Empty when there are no more events.
+    pub continuation_token: WString,
+    pub history: Vec<ChaosEvent>,
+}
+
+impl From<&FABRIC_CHAOS_REPORT> for ChaosReport {
+    fn from(value: &FABRIC_CHAOS_REPORT) -> Self {
+        let history = unsafe { value.History.as_ref() }
+            .map(|h| crate::iter::vec_from_raw_com(h.Count as usize, h.Items))
+            .unwrap_or_default();
+        Self {
+            chaos_parameters: unsafe { value.ChaosParameters.as_ref() }.map(ChaosParameters::from),
+            status: value.Status.into(),
+            continuation_token: WString::from(value.ContinuationToken),
+            history,
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_TIME_UTC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosScheduleTimeUtc {
+    pub hour: u32,
+    pub minute: u32,
+}
+
+// FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosScheduleTimeRangeUtc {
+    pub start_time: ChaosScheduleTimeUtc,
+    pub end_time: ChaosScheduleTimeUtc,
+}
+
+// FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosScheduleJobActiveDays {
+    pub sunday: bool,
+    pub monday: bool,
+    pub tuesday: bool,
+    pub wednesday: bool,
+    pub thursday: bool,
+    pub friday: bool,
+    pub saturday: bool,
+}
+
+// FABRIC_CHAOS_SCHEDULE_JOB
+/// Runs Chaos with the named parameters on the active days and times.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosScheduleJob {
+    /// Name of the parameters in the schedule parameters map.
+    pub chaos_parameters: WString,
+    pub days: ChaosScheduleJobActiveDays,
+    pub times: Vec<ChaosScheduleTimeRangeUtc>,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_JOB> for ChaosScheduleJob {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_JOB) -> Self {
+        let days = unsafe { value.Days.as_ref() }
+            .map(|d| ChaosScheduleJobActiveDays {
+                sunday: d.Sunday,
+                monday: d.Monday,
+                tuesday: d.Tuesday,
+                wednesday: d.Wednesday,
+                thursday: d.Thursday,
+                friday: d.Friday,
+                saturday: d.Saturday,
+            })
+            .unwrap_or_default();
+        let time_from_raw = |t: *const FABRIC_CHAOS_SCHEDULE_TIME_UTC| {
+            unsafe { t.as_ref() }
+                .map(|t| ChaosScheduleTimeUtc {
+                    hour: t.Hour,
+                    minute: t.Minute,
+                })
+                .unwrap_or_default()
+        };
+        let times = unsafe { value.Times.as_ref() }
+            .filter(|l| l.Count != 0 && !l.Items.is_null())
+            .map(|l| unsafe { std::slice::from_raw_parts(l.Items, l.Count as usize) })
+            .unwrap_or_default()
+            .iter()
+            .map(|r| ChaosScheduleTimeRangeUtc {
+                start_time: time_from_raw(r.StartTime),
+                end_time: time_from_raw(r.EndTime),
+            })
+            .collect();
+        Self {
+            chaos_parameters: WString::from(value.ChaosParameters),
+            days,
+            times,
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_CHAOS_SCHEDULE_JOB> for ChaosScheduleJob {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_CHAOS_SCHEDULE_JOB {
+        let days = pool.push(Box::new(FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS {
+            Sunday: self.days.sunday,
+            Monday: self.days.monday,
+            Tuesday: self.days.tuesday,
+            Wednesday: self.days.wednesday,
+            Thursday: self.days.thursday,
+            Friday: self.days.friday,
+            Saturday: self.days.saturday,
+            Reserved: std::ptr::null_mut(),
+        }));
+        let mut time_raw = |t: &ChaosScheduleTimeUtc| {
+            pool.push(Box::new(FABRIC_CHAOS_SCHEDULE_TIME_UTC {
+                Hour: t.hour,
+                Minute: t.minute,
+                Reserved: std::ptr::null_mut(),
+            }))
+        };
+        let ranges = self
+            .times
+            .iter()
+            .map(|r| FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC {
+                StartTime: time_raw(&r.start_time),
+                EndTime: time_raw(&r.end_time),
+                Reserved: std::ptr::null_mut(),
+            })
+            .collect::<Vec<_>>();
+        let (count, ranges) = pool.push_vec(ranges);
+        let times = pool.push(Box::new(FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC_LIST {
+            Count: count as u32,
+            Items: ranges as *mut _,
+        }));
+        FABRIC_CHAOS_SCHEDULE_JOB {
+            ChaosParameters: self.chaos_parameters.as_pcwstr(),
+            Days: days,
+            Times: times,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE
+#[derive(Debug, Clone)]
+pub struct ChaosSchedule {
+    pub start_date: FILETIME,
+    pub expiry_date: FILETIME,
+    /// Named parameters referenced by the jobs.
+    pub chaos_parameters_map: Vec<(WString, ChaosParameters)>,
+    pub jobs: Vec<ChaosScheduleJob>,
+}
+
+impl Default for ChaosSchedule {
+    fn default() -> Self {
+        Self {
+            start_date: FILETIME_ZERO,
+            expiry_date: FILETIME_ZERO,
+            chaos_parameters_map: vec![],
+            jobs: vec![],
+        }
+    }
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE> for ChaosSchedule {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE) -> Self {
+        let chaos_parameters_map = unsafe { value.ChaosParametersMap.as_ref() }
+            .filter(|m| m.Count != 0 && !m.Items.is_null())
+            .map(|m| unsafe { std::slice::from_raw_parts(m.Items, m.Count as usize) })
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| {
+                unsafe { item.Parameters.as_ref() }
+                    .map(|p| (WString::from(item.Name), ChaosParameters::from(p)))
+            })
+            .collect();
+        let jobs = unsafe { value.Jobs.as_ref() }
+            .map(|j| crate::iter::vec_from_raw_com(j.Count as usize, j.Items))
+            .unwrap_or_default();
+        Self {
+            start_date: value.StartDate,
+            expiry_date: value.ExpiryDate,
+            chaos_parameters_map,
+            jobs,
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_CHAOS_SCHEDULE> for ChaosSchedule {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_CHAOS_SCHEDULE {
+        let items = self
+            .chaos_parameters_map
+            .iter()
+            .map(|(name, parameters)| {
+                let parameters = Box::new(parameters.get_raw_with_pool(pool));
+                FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP_ITEM {
+                    Name: name.as_pcwstr(),
+                    Parameters: pool.push(parameters),
+                }
+            })
+            .collect::<Vec<_>>();
+        let (count, items) = pool.push_vec(items);
+        let parameters_map = pool.push(Box::new(FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP {
+            Count: count as u32,
+            Items: items as *mut _,
+        }));
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|j| j.get_raw_with_pool(pool))
+            .collect::<Vec<_>>();
+        let (count, jobs) = pool.push_vec(jobs);
+        let jobs = pool.push(Box::new(FABRIC_CHAOS_SCHEDULE_JOB_LIST {
+            Count: count as u32,
+            Items: jobs as *mut _,
+        }));
+        FABRIC_CHAOS_SCHEDULE {
+            StartDate: self.start_date,
+            ExpiryDate: self.expiry_date,
+            ChaosParametersMap: parameters_map,
+            Jobs: jobs,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ChaosScheduleDescription {
+    /// Needs to match the current version when setting the schedule.
+    /// The version is incremented on each update.
+    pub version: u32,
+    pub schedule: ChaosSchedule,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_DESCRIPTION> for ChaosScheduleDescription {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_DESCRIPTION) -> Self {
+        Self {
+            version: value.Version,
+            schedule: unsafe { value.Schedule.as_ref() }
+                .map(ChaosSchedule::from)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_CHAOS_SCHEDULE_DESCRIPTION> for ChaosScheduleDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_CHAOS_SCHEDULE_DESCRIPTION {
+        let schedule = Box::new(self.schedule.get_raw_with_pool(pool));
+        FABRIC_CHAOS_SCHEDULE_DESCRIPTION {
+            Version: self.version,
+            Schedule: pool.push(schedule),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use windows_core::{WString, Win32::Foundation::FILETIME};
+
+    use super::{
+        ChaosParameters, ChaosSchedule, ChaosScheduleDescription, ChaosScheduleJob,
+        ChaosScheduleJobActiveDays, ChaosScheduleTimeRangeUtc, ChaosScheduleTimeUtc,
+        ChaosTargetFilter,
+    };
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::ClusterHealthPolicy,
+    };
+
+    fn parameters() -> ChaosParameters {
+        ChaosParameters {
+            max_concurrent_faults: 2,
+            time_to_run_seconds: 3600,
+            context: vec![(WString::from("owner"), WString::from("tests"))],
+            cluster_health_policy: Some(ClusterHealthPolicy {
+                consider_warning_as_error: false,
+                max_percent_unhealthy_nodes: 20,
+                max_percent_unhealthy_applications: 0,
+            }),
+            target_filter: Some(ChaosTargetFilter {
+                node_type_inclusion_list: vec![WString::from("FrontEnd")],
+                application_inclusion_list: vec![],
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_chaos_parameters_round_trip() {
+        let params = parameters();
+        let mut pool = BoxPool::new();
+        let raw = params.get_raw_with_pool(&mut pool);
+        let back = ChaosParameters::from(&raw);
+        assert_eq!(back.max_concurrent_faults, 2);
+        assert_eq!(back.time_to_run_seconds, 3600);
+        assert_eq!(
+            back.context,
+            vec![(WString::from("owner"), WString::from("tests"))]
+        );
+        assert_eq!(
+            back.cluster_health_policy
+                .unwrap()
+                .max_percent_unhealthy_nodes,
+            20
+        );
+        assert_eq!(back.target_filter, params.target_filter);
+    }
+
+    #[test]
+    fn test_chaos_schedule_round_trip() {
+        let job = ChaosScheduleJob {
+            chaos_parameters: WString::from("nightly"),
+            days: ChaosScheduleJobActiveDays {
+                monday: true,
+                friday: true,
+                ..Default::default()
+            },
+            times: vec![ChaosScheduleTimeRangeUtc {
+                start_time: ChaosScheduleTimeUtc { hour: 1, minute: 0 },
+                end_time: ChaosScheduleTimeUtc {
+                    hour: 5,
+                    minute: 30,
+                },
+            }],
+        };
+        let desc = ChaosScheduleDescription {
+            version: 3,
+            schedule: ChaosSchedule {
+                start_date: FILETIME {
+                    dwLowDateTime: 1,
+                    dwHighDateTime: 2,
+                },
+                chaos_parameters_map: vec![(WString::from("nightly"), parameters())],
+                jobs: vec![job.clone()],
+                ..Default::default()
+            },
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        let back = ChaosScheduleDescription::from(&raw);
+        assert_eq!(back.version, 3);
+        assert_eq!(back.schedule.start_date, desc.schedule.start_date);
+        assert_eq!(back.schedule.jobs, vec![job]);
+        assert_eq!(back.schedule.chaos_parameters_map.len(), 1);
+        assert_eq!(
+            back.schedule.chaos_parameters_map[0].0,
+            WString::from("nightly")
+        );
+    }
+}
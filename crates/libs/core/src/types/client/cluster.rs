@@ -0,0 +1,364 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Cluster (fabric) upgrade types.
+
+use std::ffi::c_void;
+
+use mssf_com::{
+    FabricClient::{
+        IFabricGetProvisionedCodeVersionListResult, IFabricGetProvisionedConfigVersionListResult,
+        IFabricUpgradeProgressResult2,
+    },
+    FabricTypes::{
+        FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY, FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION, FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX2, FABRIC_UPGRADE_DESCRIPTION,
+        FABRIC_UPGRADE_KIND_ROLLING, FABRIC_UPGRADE_STATE, FABRIC_UPGRADE_STATE_FAILED,
+        FABRIC_UPGRADE_STATE_ROLLING_BACK_COMPLETED, FABRIC_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS,
+        FABRIC_UPGRADE_STATE_ROLLING_BACK_PENDING, FABRIC_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED,
+        FABRIC_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS,
+        FABRIC_UPGRADE_STATE_ROLLING_FORWARD_PENDING,
+    },
+};
+use windows_core::WString;
+
+use crate::{
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    types::{
+        ClusterHealthPolicy, RollingUpgradeMode, RollingUpgradeMonitoringPolicy,
+        UpgradeDomainStatus,
+    },
+};
+
+// FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY
+/// Limits on the health degradation caused by the upgrade, in percent.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterUpgradeHealthPolicy {
+    pub max_percent_delta_unhealthy_nodes: u8,
+    pub max_percent_upgrade_domain_delta_unhealthy_nodes: u8,
+}
+
+impl GetRaw<FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY> for ClusterUpgradeHealthPolicy {
+    fn get_raw(&self) -> FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY {
+        FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY {
+            MaxPercentDeltaUnhealthyNodes: self.max_percent_delta_unhealthy_nodes,
+            MaxPercentUpgradeDomainDeltaUnhealthyNodes: self
+                .max_percent_upgrade_domain_delta_unhealthy_nodes,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION for the cluster.
+#[derive(Debug, Clone, Default)]
+pub struct FabricUpgradePolicyDescription {
+    pub rolling_upgrade_mode: RollingUpgradeMode,
+    pub force_restart: bool,
+    pub upgrade_replica_set_check_timeout_seconds: u32,
+    // EX1
+    /// Used in monitored mode.
+    pub monitoring_policy: Option<RollingUpgradeMonitoringPolicy>,
+    /// Health policy to evaluate the cluster during upgrade.
+    /// Defaults to the policy in the cluster manifest.
+    pub health_policy: Option<ClusterHealthPolicy>,
+    // EX2
+    /// Evaluates health against the state before the upgrade started,
+    /// using upgrade_health_policy.
+    pub enable_delta_health_evaluation: bool,
+    pub upgrade_health_policy: Option<ClusterUpgradeHealthPolicy>,
+}
+
+impl GetRawWithBoxPool<FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION>
+    for FabricUpgradePolicyDescription
+{
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+        let upgrade_health_policy = self
+            .upgrade_health_policy
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |p| {
+                pool.push(Box::new(p.get_raw())) as *mut c_void
+            });
+        let ex2 = pool.push(Box::new(FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX2 {
+            EnableDeltaHealthEvaluation: self.enable_delta_health_evaluation,
+            UpgradeHealthPolicy: upgrade_health_policy,
+            Reserved: std::ptr::null_mut(),
+        }));
+        let monitoring_policy = self
+            .monitoring_policy
+            .as_ref()
+            .map_or(std::ptr::null(), |p| {
+                let raw = Box::new(p.get_raw_with_pool(pool));
+                pool.push(raw)
+            });
+        let health_policy = self
+            .health_policy
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |p| {
+                pool.push(Box::new(p.get_raw())) as *mut c_void
+            });
+        let ex1 = pool.push(Box::new(FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1 {
+            MonitoringPolicy: monitoring_policy,
+            HealthPolicy: health_policy,
+            Reserved: ex2 as *mut c_void,
+        }));
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+            RollingUpgradeMode: self.rolling_upgrade_mode.into(),
+            ForceRestart: self.force_restart,
+            UpgradeReplicaSetCheckTimeoutInSeconds: self.upgrade_replica_set_check_timeout_seconds,
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_UPGRADE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct FabricUpgradeDescription {
+    /// The code version to upgrade to. Empty keeps the current version.
+    pub code_version: WString,
+    /// The config version to upgrade to. Empty keeps the current version.
+    pub config_version: WString,
+    pub upgrade_policy: FabricUpgradePolicyDescription,
+}
+
+impl GetRawWithBoxPool<FABRIC_UPGRADE_DESCRIPTION> for FabricUpgradeDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_UPGRADE_DESCRIPTION {
+        let policy = Box::new(self.upgrade_policy.get_raw_with_pool(pool));
+        FABRIC_UPGRADE_DESCRIPTION {
+            CodeVersion: self.code_version.as_pcwstr(),
+            ConfigVersion: self.config_version.as_pcwstr(),
+            UpgradeKind: FABRIC_UPGRADE_KIND_ROLLING,
+            UpgradePolicyDescription: pool.push(policy) as *mut c_void,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_UPGRADE_STATE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FabricUpgradeState {
+    Invalid,
+    RollingBackInProgress,
+    RollingBackCompleted,
+    RollingForwardPending,
+    RollingForwardInProgress,
+    RollingForwardCompleted,
+    Failed,
+    RollingBackPending,
+}
+
+impl From<FABRIC_UPGRADE_STATE> for FabricUpgradeState {
+    fn from(value: FABRIC_UPGRADE_STATE) -> Self {
+        match value {
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS => Self::RollingBackInProgress,
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_COMPLETED => Self::RollingBackCompleted,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_PENDING => Self::RollingForwardPending,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS => Self::RollingForwardInProgress,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED => Self::RollingForwardCompleted,
+            FABRIC_UPGRADE_STATE_FAILED => Self::Failed,
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_PENDING => Self::RollingBackPending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl FabricUpgradeState {
+    /// Whether the upgrade has reached a final state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::RollingBackCompleted | Self::RollingForwardCompleted | Self::Failed
+        )
+    }
+}
+
+// IFabricUpgradeProgressResult2
+#[derive(Debug, Clone)]
+pub struct FabricUpgradeProgress {
+    pub target_code_version: WString,
+    pub target_config_version: WString,
+    pub upgrade_state: FabricUpgradeState,
+    pub upgrade_domains: Vec<UpgradeDomainStatus>,
+    pub rolling_upgrade_mode: RollingUpgradeMode,
+    /// The upgrade domain to pass to move next in manual mode.
+    /// Empty if there is none.
+    pub next_upgrade_domain: WString,
+}
+
+impl TryFrom<&IFabricUpgradeProgressResult2> for FabricUpgradeProgress {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricUpgradeProgressResult2) -> crate::Result<Self> {
+        let mut count = 0;
+        let domains = unsafe { value.GetUpgradeDomains(&mut count) }?;
+        Ok(Self {
+            target_code_version: WString::from(unsafe { value.get_TargetCodeVersion() }),
+            target_config_version: WString::from(unsafe { value.get_TargetConfigVersion() }),
+            upgrade_state: unsafe { value.get_UpgradeState() }.into(),
+            upgrade_domains: crate::iter::vec_from_raw_com(count as usize, domains),
+            rolling_upgrade_mode: unsafe { value.get_RollingUpgradeMode() }.into(),
+            next_upgrade_domain: WString::from(unsafe { value.get_NextUpgradeDomain() }),
+        })
+    }
+}
+
+// FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionedFabricCodeVersionQueryDescription {
+    /// None lists all provisioned code versions.
+    pub code_version_filter: Option<WString>,
+}
+
+impl From<&ProvisionedFabricCodeVersionQueryDescription>
+    for FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION
+{
+    fn from(value: &ProvisionedFabricCodeVersionQueryDescription) -> Self {
+        Self {
+            CodeVersionFilter: value.code_version_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetProvisionedCodeVersionListResult
+#[derive(Debug, Clone)]
+pub struct ProvisionedFabricCodeVersionList {
+    pub code_versions: Vec<WString>,
+}
+
+impl From<&IFabricGetProvisionedCodeVersionListResult> for ProvisionedFabricCodeVersionList {
+    fn from(value: &IFabricGetProvisionedCodeVersionListResult) -> Self {
+        let code_versions = unsafe { value.get_ProvisionedCodeVersionList().as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v: ProvisionedVersion| v.0)
+            .collect();
+        Self { code_versions }
+    }
+}
+
+// FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionedFabricConfigVersionQueryDescription {
+    /// None lists all provisioned config versions.
+    pub config_version_filter: Option<WString>,
+}
+
+impl From<&ProvisionedFabricConfigVersionQueryDescription>
+    for FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION
+{
+    fn from(value: &ProvisionedFabricConfigVersionQueryDescription) -> Self {
+        Self {
+            ConfigVersionFilter: value.config_version_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetProvisionedConfigVersionListResult
+#[derive(Debug, Clone)]
+pub struct ProvisionedFabricConfigVersionList {
+    pub config_versions: Vec<WString>,
+}
+
+impl From<&IFabricGetProvisionedConfigVersionListResult> for ProvisionedFabricConfigVersionList {
+    fn from(value: &IFabricGetProvisionedConfigVersionListResult) -> Self {
+        let config_versions = unsafe { value.get_ProvisionedConfigVersionList().as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v: ProvisionedVersion| v.0)
+            .collect();
+        Self { config_versions }
+    }
+}
+
+/// Version string of a provisioned code or config query result item.
+struct ProvisionedVersion(WString);
+
+impl From<&FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM> for ProvisionedVersion {
+    fn from(value: &FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM) -> Self {
+        Self(WString::from(value.CodeVersion))
+    }
+}
+
+impl From<&FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM> for ProvisionedVersion {
+    fn from(value: &FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM) -> Self {
+        Self(WString::from(value.ConfigVersion))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_CLUSTER_HEALTH_POLICY, FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION, FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX2, FABRIC_UPGRADE_KIND_ROLLING,
+    };
+    use windows_core::WString;
+
+    use super::{ClusterUpgradeHealthPolicy, FabricUpgradeDescription};
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::ClusterHealthPolicy,
+    };
+
+    #[test]
+    fn test_fabric_upgrade_description_raw() {
+        let mut desc = FabricUpgradeDescription {
+            code_version: WString::from("10.0.0.1"),
+            ..Default::default()
+        };
+        desc.upgrade_policy.health_policy = Some(ClusterHealthPolicy {
+            consider_warning_as_error: true,
+            max_percent_unhealthy_nodes: 10,
+            max_percent_unhealthy_applications: 0,
+        });
+        desc.upgrade_policy.enable_delta_health_evaluation = true;
+        desc.upgrade_policy.upgrade_health_policy = Some(ClusterUpgradeHealthPolicy {
+            max_percent_delta_unhealthy_nodes: 5,
+            max_percent_upgrade_domain_delta_unhealthy_nodes: 1,
+        });
+
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.UpgradeKind, FABRIC_UPGRADE_KIND_ROLLING);
+        assert_eq!(WString::from(raw.CodeVersion), WString::from("10.0.0.1"));
+
+        let policy = unsafe {
+            (raw.UpgradePolicyDescription as *const FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION)
+                .as_ref()
+        }
+        .unwrap();
+        let ex1 = unsafe {
+            (policy.Reserved as *const FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1).as_ref()
+        }
+        .unwrap();
+        assert!(ex1.MonitoringPolicy.is_null());
+        let health_policy =
+            unsafe { (ex1.HealthPolicy as *const FABRIC_CLUSTER_HEALTH_POLICY).as_ref() }.unwrap();
+        assert!(health_policy.ConsiderWarningAsError);
+        assert_eq!(health_policy.MaxPercentUnhealthyNodes, 10);
+
+        let ex2 = unsafe {
+            (ex1.Reserved as *const FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX2).as_ref()
+        }
+        .unwrap();
+        assert!(ex2.EnableDeltaHealthEvaluation);
+        let upgrade_health_policy = unsafe {
+            (ex2.UpgradeHealthPolicy as *const FABRIC_CLUSTER_UPGRADE_HEALTH_POLICY).as_ref()
+        }
+        .unwrap();
+        assert_eq!(upgrade_health_policy.MaxPercentDeltaUnhealthyNodes, 5);
+        assert_eq!(
+            upgrade_health_policy.MaxPercentUpgradeDomainDeltaUnhealthyNodes,
+            1
+        );
+    }
+}
@@ -0,0 +1,847 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Types for querying entities deployed on a node.
+
+use mssf_com::{
+    FabricClient::{
+        IFabricDeployedApplicationHealthResult, IFabricDeployedServicePackageHealthResult,
+        IFabricGetDeployedApplicationPagedListResult, IFabricGetDeployedCodePackageListResult,
+        IFabricGetDeployedReplicaListResult, IFabricGetDeployedServicePackagePagedListResult,
+    },
+    FabricTypes::{
+        FABRIC_CODE_PACKAGE_ENTRY_POINT, FABRIC_CODE_PACKAGE_ENTRY_POINT_EX1,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH, FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX,
+        FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX2,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM_EX1, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION_EX1,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_EX1,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX1,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX2,
+        FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM, FABRIC_DEPLOYMENT_STATUS,
+        FABRIC_DEPLOYMENT_STATUS_ACTIVATING, FABRIC_DEPLOYMENT_STATUS_ACTIVE,
+        FABRIC_DEPLOYMENT_STATUS_DEACTIVATING, FABRIC_DEPLOYMENT_STATUS_DOWNLOADING,
+        FABRIC_DEPLOYMENT_STATUS_FAILED, FABRIC_DEPLOYMENT_STATUS_RAN_TO_COMPLETION,
+        FABRIC_DEPLOYMENT_STATUS_UPGRADING, FABRIC_ENTRY_POINT_STATUS,
+        FABRIC_ENTRY_POINT_STATUS_PENDING, FABRIC_ENTRY_POINT_STATUS_STARTED,
+        FABRIC_ENTRY_POINT_STATUS_STARTING, FABRIC_ENTRY_POINT_STATUS_STOPPED,
+        FABRIC_ENTRY_POINT_STATUS_STOPPING, FABRIC_HOST_ISOLATION_MODE,
+        FABRIC_HOST_ISOLATION_MODE_HYPER_V, FABRIC_HOST_ISOLATION_MODE_PROCESS, FABRIC_HOST_TYPE,
+        FABRIC_HOST_TYPE_CONTAINER_HOST, FABRIC_HOST_TYPE_EXE_HOST,
+        FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION,
+        FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION, FABRIC_QUERY_PAGING_DESCRIPTION,
+        FABRIC_SERVICE_KIND_STATEFUL, FABRIC_SERVICE_KIND_STATELESS, FABRIC_URI,
+    },
+};
+use windows_core::{GUID, WString, Win32::Foundation::FILETIME};
+
+use crate::{
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    types::{
+        ApplicationHealthPolicy, DeployedStatelessServiceInstanceQueryResult, HealthEvent,
+        HealthEventsFilter, HealthState, HealthStateFilterFlags, PagedQueryDescription,
+        PagingStatus, QueryServiceReplicaStatus, ReplicaRole, Uri,
+    },
+};
+
+// FABRIC_DEPLOYMENT_STATUS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentStatus {
+    Invalid,
+    Downloading,
+    Activating,
+    Active,
+    Upgrading,
+    Deactivating,
+    RanToCompletion,
+    Failed,
+}
+
+impl From<FABRIC_DEPLOYMENT_STATUS> for DeploymentStatus {
+    fn from(value: FABRIC_DEPLOYMENT_STATUS) -> Self {
+        match value {
+            FABRIC_DEPLOYMENT_STATUS_DOWNLOADING => Self::Downloading,
+            FABRIC_DEPLOYMENT_STATUS_ACTIVATING => Self::Activating,
+            FABRIC_DEPLOYMENT_STATUS_ACTIVE => Self::Active,
+            FABRIC_DEPLOYMENT_STATUS_UPGRADING => Self::Upgrading,
+            FABRIC_DEPLOYMENT_STATUS_DEACTIVATING => Self::Deactivating,
+            FABRIC_DEPLOYMENT_STATUS_RAN_TO_COMPLETION => Self::RanToCompletion,
+            FABRIC_DEPLOYMENT_STATUS_FAILED => Self::Failed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+fn paging_description_raw(
+    paged_query: &PagedQueryDescription,
+    pool: &mut BoxPool,
+) -> *const FABRIC_QUERY_PAGING_DESCRIPTION {
+    pool.push(Box::new(FABRIC_QUERY_PAGING_DESCRIPTION {
+        ContinuationToken: paged_query.continuation_token.as_ref().into(),
+        MaxResults: paged_query.max_results.unwrap_or(0), // 0 means no limit
+        Reserved: std::ptr::null_mut(),
+    }))
+}
+
+// FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedApplicationQueryDescription {
+    pub node_name: WString,
+    pub application_name_filter: Option<Uri>,
+    pub include_health_state: bool,
+    pub paged_query: PagedQueryDescription,
+}
+
+impl GetRawWithBoxPool<FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION>
+    for DeployedApplicationQueryDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION {
+        FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION {
+            NodeName: self.node_name.as_pcwstr(),
+            ApplicationNameFilter: self
+                .application_name_filter
+                .as_ref()
+                .map_or(FABRIC_URI::default(), |u| u.as_raw()),
+            IncludeHealthState: self.include_health_state,
+            PagingDescription: paging_description_raw(&self.paged_query, pool),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetDeployedApplicationPagedListResult
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationListResult {
+    pub items: Vec<DeployedApplicationQueryResultItem>,
+    pub paging_status: Option<PagingStatus>,
+}
+
+impl From<&IFabricGetDeployedApplicationPagedListResult> for DeployedApplicationListResult {
+    fn from(value: &IFabricGetDeployedApplicationPagedListResult) -> Self {
+        let items = unsafe { value.get_DeployedApplicationPagedList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        let paging_status = unsafe { value.get_PagingStatus().as_ref() }.map(|ps| ps.into());
+        Self {
+            items,
+            paging_status,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationQueryResultItem {
+    pub application_name: Uri,
+    pub application_type_name: WString,
+    pub deployed_application_status: DeploymentStatus,
+    pub work_directory: WString,
+    pub log_directory: WString,
+    pub temp_directory: WString,
+    /// Unknown unless the query asked to include health state.
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM> for DeployedApplicationQueryResultItem {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM) -> Self {
+        let ex = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX).as_ref()
+        };
+        let ex2 = ex.and_then(|ex| unsafe {
+            (ex.Reserved as *const FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX2).as_ref()
+        });
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            application_type_name: WString::from(value.ApplicationTypeName),
+            deployed_application_status: value.DeployedApplicationStatus.into(),
+            work_directory: ex.map_or(WString::new(), |ex| WString::from(ex.WorkDirectory)),
+            log_directory: ex.map_or(WString::new(), |ex| WString::from(ex.LogDirectory)),
+            temp_directory: ex.map_or(WString::new(), |ex| WString::from(ex.TempDirectory)),
+            health_state: ex2.map_or(HealthState::Unknown, |ex2| (&ex2.HealthState).into()),
+        }
+    }
+}
+
+// FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedServicePackageQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+    pub include_health_state: bool,
+    pub paged_query: PagedQueryDescription,
+}
+
+impl GetRawWithBoxPool<FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION>
+    for DeployedServicePackageQueryDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION {
+        FABRIC_PAGED_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION {
+            NodeName: self.node_name.as_pcwstr(),
+            ApplicationName: self.application_name.as_raw(),
+            ServiceManifestNameFilter: self.service_manifest_name_filter.as_ref().into(),
+            IncludeHealthState: self.include_health_state,
+            PagingDescription: paging_description_raw(&self.paged_query, pool),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetDeployedServicePackagePagedListResult
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageListResult {
+    pub items: Vec<DeployedServicePackageQueryResultItem>,
+    pub paging_status: Option<PagingStatus>,
+}
+
+impl From<&IFabricGetDeployedServicePackagePagedListResult> for DeployedServicePackageListResult {
+    fn from(value: &IFabricGetDeployedServicePackagePagedListResult) -> Self {
+        let items = unsafe { value.get_DeployedServicePackageList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        let paging_status = unsafe { value.get_PagingStatus().as_ref() }.map(|ps| ps.into());
+        Self {
+            items,
+            paging_status,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageQueryResultItem {
+    pub service_manifest_name: WString,
+    pub service_manifest_version: WString,
+    pub deployed_service_package_status: DeploymentStatus,
+    /// Empty for the shared activation mode.
+    pub service_package_activation_id: WString,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM>
+    for DeployedServicePackageQueryResultItem
+{
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM_EX1)
+                .as_ref()
+        };
+        Self {
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            service_manifest_version: WString::from(value.ServiceManifestVersion),
+            deployed_service_package_status: value.DeployedServicePackageStatus.into(),
+            service_package_activation_id: ex1.map_or(WString::new(), |ex1| {
+                WString::from(ex1.ServicePackageActivationId)
+            }),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedCodePackageQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+    pub code_package_name_filter: Option<WString>,
+}
+
+impl From<&DeployedCodePackageQueryDescription> for FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION {
+    fn from(value: &DeployedCodePackageQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestNameFilter: value.service_manifest_name_filter.as_ref().into(),
+            CodePackageNameFilter: value.code_package_name_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetDeployedCodePackageListResult
+#[derive(Debug, Clone)]
+pub struct DeployedCodePackageListResult {
+    pub items: Vec<DeployedCodePackageQueryResultItem>,
+}
+
+impl From<&IFabricGetDeployedCodePackageListResult> for DeployedCodePackageListResult {
+    fn from(value: &IFabricGetDeployedCodePackageListResult) -> Self {
+        let items = unsafe { value.get_DeployedCodePackageList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        Self { items }
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedCodePackageQueryResultItem {
+    pub code_package_name: WString,
+    pub code_package_version: WString,
+    pub service_manifest_name: WString,
+    pub run_frequency_interval: u32,
+    pub deployed_code_package_status: DeploymentStatus,
+    pub setup_entry_point: Option<CodePackageEntryPoint>,
+    pub entry_point: Option<CodePackageEntryPoint>,
+    pub service_package_activation_id: WString,
+    pub host_type: HostType,
+    pub host_isolation_mode: HostIsolationMode,
+}
+
+impl From<&FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM> for DeployedCodePackageQueryResultItem {
+    fn from(value: &FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        Self {
+            code_package_name: WString::from(value.CodePackageName),
+            code_package_version: WString::from(value.CodePackageVersion),
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            run_frequency_interval: value.RunFrequencyInterval,
+            deployed_code_package_status: value.DeployedCodePackageStatus.into(),
+            setup_entry_point: unsafe { value.SetupEntryPoint.as_ref() }.map(|e| e.into()),
+            entry_point: unsafe { value.EntryPoint.as_ref() }.map(|e| e.into()),
+            service_package_activation_id: ex1.map_or(WString::new(), |ex1| {
+                WString::from(ex1.ServicePackageActivationId)
+            }),
+            host_type: ex1.map_or(HostType::Invalid, |ex1| ex1.HostType.into()),
+            host_isolation_mode: ex1
+                .map_or(HostIsolationMode::None, |ex1| ex1.HostIsolationMode.into()),
+        }
+    }
+}
+
+// FABRIC_CODE_PACKAGE_ENTRY_POINT
+#[derive(Debug, Clone)]
+pub struct CodePackageEntryPoint {
+    pub entry_point_location: WString,
+    pub process_id: i64,
+    pub run_as_user_name: WString,
+    pub entry_point_status: EntryPointStatus,
+    pub next_activation_utc: FILETIME,
+    // TODO: statistics
+    pub code_package_instance_id: i64,
+}
+
+impl From<&FABRIC_CODE_PACKAGE_ENTRY_POINT> for CodePackageEntryPoint {
+    fn from(value: &FABRIC_CODE_PACKAGE_ENTRY_POINT) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_CODE_PACKAGE_ENTRY_POINT_EX1).as_ref() };
+        Self {
+            entry_point_location: WString::from(value.EntryPointLocation),
+            process_id: value.ProcessId,
+            run_as_user_name: WString::from(value.RunAsUserName),
+            entry_point_status: value.EntryPointStatus.into(),
+            next_activation_utc: value.NextActivationUtc,
+            code_package_instance_id: ex1.map_or(0, |ex1| ex1.CodePackageInstanceId),
+        }
+    }
+}
+
+// FABRIC_ENTRY_POINT_STATUS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPointStatus {
+    Invalid,
+    Pending,
+    Starting,
+    Started,
+    Stopping,
+    Stopped,
+}
+
+impl From<FABRIC_ENTRY_POINT_STATUS> for EntryPointStatus {
+    fn from(value: FABRIC_ENTRY_POINT_STATUS) -> Self {
+        match value {
+            FABRIC_ENTRY_POINT_STATUS_PENDING => Self::Pending,
+            FABRIC_ENTRY_POINT_STATUS_STARTING => Self::Starting,
+            FABRIC_ENTRY_POINT_STATUS_STARTED => Self::Started,
+            FABRIC_ENTRY_POINT_STATUS_STOPPING => Self::Stopping,
+            FABRIC_ENTRY_POINT_STATUS_STOPPED => Self::Stopped,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_HOST_TYPE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostType {
+    Invalid,
+    ExeHost,
+    ContainerHost,
+}
+
+impl From<FABRIC_HOST_TYPE> for HostType {
+    fn from(value: FABRIC_HOST_TYPE) -> Self {
+        match value {
+            FABRIC_HOST_TYPE_EXE_HOST => Self::ExeHost,
+            FABRIC_HOST_TYPE_CONTAINER_HOST => Self::ContainerHost,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_HOST_ISOLATION_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostIsolationMode {
+    None,
+    Process,
+    HyperV,
+}
+
+impl From<FABRIC_HOST_ISOLATION_MODE> for HostIsolationMode {
+    fn from(value: FABRIC_HOST_ISOLATION_MODE) -> Self {
+        match value {
+            FABRIC_HOST_ISOLATION_MODE_PROCESS => Self::Process,
+            FABRIC_HOST_ISOLATION_MODE_HYPER_V => Self::HyperV,
+            _ => Self::None,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedServiceReplicaQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+    pub partition_id_filter: Option<GUID>,
+}
+
+impl From<&DeployedServiceReplicaQueryDescription>
+    for FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION
+{
+    fn from(value: &DeployedServiceReplicaQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestNameFilter: value.service_manifest_name_filter.as_ref().into(),
+            // zero guid means no filter
+            PartitionIdFilter: value.partition_id_filter.unwrap_or(GUID::zeroed()),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetDeployedReplicaListResult
+#[derive(Debug, Clone)]
+pub struct DeployedServiceReplicaListResult {
+    pub items: Vec<DeployedServiceReplicaQueryResultItem>,
+}
+
+impl From<&IFabricGetDeployedReplicaListResult> for DeployedServiceReplicaListResult {
+    fn from(value: &IFabricGetDeployedReplicaListResult) -> Self {
+        let items = unsafe { value.get_DeployedReplicaList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        Self { items }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub enum DeployedServiceReplicaQueryResultItem {
+    Invalid,
+    Stateful(DeployedStatefulServiceReplicaQueryResult),
+    Stateless(DeployedStatelessServiceInstanceQueryResult),
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM>
+    for DeployedServiceReplicaQueryResultItem
+{
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM) -> Self {
+        match value.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => unsafe {
+                (value.Value as *const FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM)
+                    .as_ref()
+            }
+            .map_or(Self::Invalid, |raw| Self::Stateful(raw.into())),
+            FABRIC_SERVICE_KIND_STATELESS => unsafe {
+                (value.Value as *const FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM)
+                    .as_ref()
+            }
+            .map_or(Self::Invalid, |raw| {
+                Self::Stateless(DeployedStatelessServiceInstanceQueryResult::new(raw))
+            }),
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl DeployedServiceReplicaQueryResultItem {
+    pub fn get_partition_id(&self) -> GUID {
+        match self {
+            Self::Stateful(r) => r.partition_id,
+            Self::Stateless(r) => r.partition_id,
+            Self::Invalid => GUID::zeroed(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedStatefulServiceReplicaQueryResult {
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub service_manifest_version: WString,
+    pub code_package_name: WString,
+    pub partition_id: GUID,
+    pub replica_id: i64,
+    pub replica_role: ReplicaRole,
+    pub replica_status: QueryServiceReplicaStatus,
+    pub address: WString,
+    pub service_manifest_name: WString,
+    pub service_package_activation_id: WString,
+}
+
+impl From<&FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM>
+    for DeployedStatefulServiceReplicaQueryResult
+{
+    fn from(value: &FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved
+                as *const FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX1)
+                .as_ref()
+        };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX2)
+                .as_ref()
+        });
+        Self {
+            service_name: Uri::from(value.ServiceName),
+            service_type_name: WString::from(value.ServiceTypeName),
+            service_manifest_version: WString::from(value.ServiceManifestVersion),
+            code_package_name: WString::from(value.CodePackageName),
+            partition_id: value.PartitionId,
+            replica_id: value.ReplicaId,
+            replica_role: (&value.ReplicaRole).into(),
+            replica_status: (&value.ReplicaStatus).into(),
+            address: WString::from(value.Address),
+            service_manifest_name: ex1
+                .map_or(WString::new(), |ex1| WString::from(ex1.ServiceManifestName)),
+            service_package_activation_id: ex2.map_or(WString::new(), |ex2| {
+                WString::from(ex2.ServicePackageActivationId)
+            }),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthStatesFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+}
+
+impl GetRaw<FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER>
+    for DeployedServicePackageHealthStatesFilter
+{
+    fn get_raw(&self) -> FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER {
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedApplicationHealthQueryDescription {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthEventsFilter>,
+    pub deployed_service_packages_filter: Option<DeployedServicePackageHealthStatesFilter>,
+    // TODO: support other fields
+    // pub health_statistics_filter: Option<HealthStatisticsFilter>,
+}
+
+impl GetRawWithBoxPool<FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION>
+    for DeployedApplicationHealthQueryDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION {
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            NodeName: self.node_name.as_pcwstr(),
+            HealthPolicy: self.health_policy.as_ref().map_or(std::ptr::null(), |p| {
+                let b = Box::new(p.get_raw_with_pool(pool));
+                pool.push(b)
+            }),
+            EventsFilter: self
+                .events_filter
+                .as_ref()
+                .map_or(std::ptr::null(), |f| pool.push(Box::new(f.get_raw()))),
+            DeployedServicePackagesFilter: self
+                .deployed_service_packages_filter
+                .as_ref()
+                .map_or(std::ptr::null(), |f| pool.push(Box::new(f.get_raw()))),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealth {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub deployed_service_package_health_states: Vec<DeployedServicePackageHealthState>,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_HEALTH> for DeployedApplicationHealth {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_HEALTH) -> Self {
+        let health_events = unsafe { value.HealthEvents.as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        let deployed_service_package_health_states =
+            unsafe { value.DeployedServicePackageHealthStates.as_ref() }
+                .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+                .unwrap_or_default();
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            node_name: WString::from(value.NodeName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events,
+            deployed_service_package_health_states,
+        }
+    }
+}
+
+impl From<&IFabricDeployedApplicationHealthResult> for DeployedApplicationHealth {
+    fn from(value: &IFabricDeployedApplicationHealthResult) -> Self {
+        Self::from(unsafe { value.get_DeployedApplicationHealth().as_ref() }.unwrap())
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthState {
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    /// Empty for the shared activation mode.
+    pub service_package_activation_id: WString,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE> for DeployedServicePackageHealthState {
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_EX1).as_ref()
+        };
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            service_package_activation_id: ex1.map_or(WString::new(), |ex1| {
+                WString::from(ex1.ServicePackageActivationId)
+            }),
+            node_name: WString::from(value.NodeName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedServicePackageHealthQueryDescription {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub service_manifest_name: WString,
+    /// Required for the exclusive activation mode.
+    pub service_package_activation_id: Option<WString>,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthEventsFilter>,
+}
+
+impl GetRawWithBoxPool<FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION>
+    for DeployedServicePackageHealthQueryDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION {
+        let ex1 = pool.push(Box::new(
+            FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION_EX1 {
+                ServicePackageActivationId: self.service_package_activation_id.as_ref().into(),
+                Reserved: std::ptr::null_mut(),
+            },
+        ));
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            NodeName: self.node_name.as_pcwstr(),
+            ServiceManifestName: self.service_manifest_name.as_pcwstr(),
+            HealthPolicy: self.health_policy.as_ref().map_or(std::ptr::null(), |p| {
+                let b = Box::new(p.get_raw_with_pool(pool));
+                pool.push(b)
+            }),
+            EventsFilter: self
+                .events_filter
+                .as_ref()
+                .map_or(std::ptr::null(), |f| pool.push(Box::new(f.get_raw()))),
+            Reserved: ex1 as *mut _,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealth {
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH> for DeployedServicePackageHealth {
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH) -> Self {
+        let health_events = unsafe { value.HealthEvents.as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            node_name: WString::from(value.NodeName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events,
+        }
+    }
+}
+
+impl From<&IFabricDeployedServicePackageHealthResult> for DeployedServicePackageHealth {
+    fn from(value: &IFabricDeployedServicePackageHealthResult) -> Self {
+        Self::from(unsafe { value.get_DeployedServicePackageHealth().as_ref() }.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION_EX1,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION, FABRIC_REPLICA_ROLE_PRIMARY,
+        FABRIC_SERVICE_KIND_STATEFUL,
+    };
+    use windows_core::{GUID, WString};
+
+    use super::{
+        DeployedApplicationQueryDescription, DeployedServicePackageHealthQueryDescription,
+        DeployedServiceReplicaQueryResultItem,
+    };
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::{PagedQueryDescription, ReplicaRole, Uri},
+    };
+
+    #[test]
+    fn test_deployed_application_query_paging_raw() {
+        let desc = DeployedApplicationQueryDescription {
+            node_name: WString::from("_Node_0"),
+            application_name_filter: Some(Uri::from("fabric:/myapp")),
+            include_health_state: true,
+            paged_query: PagedQueryDescription {
+                continuation_token: Some(WString::from("token")),
+                max_results: Some(10),
+            },
+        };
+        let mut pool = BoxPool::new();
+        let raw: FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION =
+            desc.get_raw_with_pool(&mut pool);
+        assert_eq!(WString::from(raw.NodeName), WString::from("_Node_0"));
+        assert!(raw.IncludeHealthState);
+        let paging = unsafe { raw.PagingDescription.as_ref() }.unwrap();
+        assert_eq!(paging.MaxResults, 10);
+        assert_eq!(
+            WString::from(paging.ContinuationToken),
+            WString::from("token")
+        );
+    }
+
+    #[test]
+    fn test_deployed_service_package_health_query_raw() {
+        let mut desc = DeployedServicePackageHealthQueryDescription {
+            application_name: Uri::from("fabric:/myapp"),
+            node_name: WString::from("_Node_0"),
+            service_manifest_name: WString::from("MyPkg"),
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert_eq!(
+            WString::from(raw.ServiceManifestName),
+            WString::from("MyPkg")
+        );
+        assert!(raw.HealthPolicy.is_null());
+        assert!(raw.EventsFilter.is_null());
+        // Shared activation passes a null activation id.
+        let ex1 = unsafe {
+            (raw.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION_EX1)
+                .as_ref()
+        }
+        .unwrap();
+        assert!(ex1.ServicePackageActivationId.is_null());
+
+        desc.service_package_activation_id = Some(WString::from("id1"));
+        let raw = desc.get_raw_with_pool(&mut pool);
+        let ex1 = unsafe {
+            (raw.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION_EX1)
+                .as_ref()
+        }
+        .unwrap();
+        assert_eq!(
+            WString::from(ex1.ServicePackageActivationId),
+            WString::from("id1")
+        );
+    }
+
+    #[test]
+    fn test_deployed_stateful_replica_from_raw() {
+        let service_name = WString::from("fabric:/myapp/svc");
+        let partition_id = GUID::from_u128(0x1234);
+        let stateful = FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM {
+            ServiceName: mssf_com::FabricTypes::FABRIC_URI(service_name.as_ptr() as *mut u16),
+            PartitionId: partition_id,
+            ReplicaId: 7,
+            ReplicaRole: FABRIC_REPLICA_ROLE_PRIMARY,
+            ..Default::default()
+        };
+        let raw = FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM {
+            Kind: FABRIC_SERVICE_KIND_STATEFUL,
+            Value: &stateful as *const _ as *mut _,
+        };
+        let item = DeployedServiceReplicaQueryResultItem::from(&raw);
+        assert_eq!(item.get_partition_id(), partition_id);
+        match item {
+            DeployedServiceReplicaQueryResultItem::Stateful(r) => {
+                assert_eq!(r.service_name, Uri::from("fabric:/myapp/svc"));
+                assert_eq!(r.replica_id, 7);
+                assert_eq!(r.replica_role, ReplicaRole::Primary);
+                assert!(r.service_manifest_name.is_empty());
+            }
+            _ => panic!("expected stateful replica"),
+        }
+    }
+}
@@ -0,0 +1,768 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Fault injection and test command types.
+
+use std::ffi::c_void;
+
+use mssf_com::FabricTypes::{
+    FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION, FABRIC_DATA_LOSS_MODE, FABRIC_DATA_LOSS_MODE_FULL,
+    FABRIC_DATA_LOSS_MODE_PARTIAL, FABRIC_DEPLOYED_CODE_PACKAGE_RESULT,
+    FABRIC_MOVE_PRIMARY_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME, FABRIC_MOVE_PRIMARY_DESCRIPTION2,
+    FABRIC_MOVE_PRIMARY_RESULT, FABRIC_MOVE_SECONDARY_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME, FABRIC_MOVE_SECONDARY_DESCRIPTION2,
+    FABRIC_MOVE_SECONDARY_RESULT, FABRIC_NODE_RESULT, FABRIC_NODE_START_DESCRIPTION,
+    FABRIC_NODE_STOP_DESCRIPTION, FABRIC_NODE_TRANSITION_DESCRIPTION,
+    FABRIC_NODE_TRANSITION_PROGRESS, FABRIC_NODE_TRANSITION_TYPE_START,
+    FABRIC_NODE_TRANSITION_TYPE_STOP, FABRIC_PARTITION_DATA_LOSS_PROGRESS,
+    FABRIC_PARTITION_QUORUM_LOSS_PROGRESS, FABRIC_PARTITION_RESTART_PROGRESS,
+    FABRIC_PARTITION_SELECTOR, FABRIC_PARTITION_SELECTOR_TYPE_NAMED,
+    FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID, FABRIC_PARTITION_SELECTOR_TYPE_RANDOM,
+    FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON, FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64,
+    FABRIC_QUORUM_LOSS_MODE, FABRIC_QUORUM_LOSS_MODE_ALL_REPLICAS,
+    FABRIC_QUORUM_LOSS_MODE_QUORUM_REPLICAS,
+    FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME,
+    FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2,
+    FABRIC_RESTART_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_RESTART_NODE_DESCRIPTION2,
+    FABRIC_RESTART_PARTITION_MODE, FABRIC_RESTART_PARTITION_MODE_ALL_REPLICAS_OR_INSTANCES,
+    FABRIC_RESTART_PARTITION_MODE_ONLY_ACTIVE_SECONDARIES, FABRIC_SELECTED_PARTITION,
+    FABRIC_START_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_START_NODE_DESCRIPTION2,
+    FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION, FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION,
+    FABRIC_START_PARTITION_RESTART_DESCRIPTION, FABRIC_STOP_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+    FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_STOP_NODE_DESCRIPTION2,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE, FABRIC_TEST_COMMAND_PROGRESS_STATE_CANCELLED,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED, FABRIC_TEST_COMMAND_PROGRESS_STATE_FAULTED,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_FORCE_CANCELLED,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_ROLLING_BACK, FABRIC_TEST_COMMAND_PROGRESS_STATE_RUNNING,
+};
+use windows_core::{GUID, HRESULT, WString};
+
+use crate::{
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    types::Uri,
+};
+
+// FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone, Default)]
+pub struct RestartNodeDescription {
+    pub node_name: WString,
+    /// Restarts only if the node instance matches. 0 matches any instance.
+    pub node_instance_id: u64,
+    pub create_fabric_dump: bool,
+}
+
+impl GetRawWithBoxPool<FABRIC_RESTART_NODE_DESCRIPTION2> for RestartNodeDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_RESTART_NODE_DESCRIPTION2 {
+        let value = pool.push(Box::new(FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME {
+            NodeName: self.node_name.as_pcwstr(),
+            NodeInstanceId: self.node_instance_id,
+            ShouldCreateFabricDump: self.create_fabric_dump,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_RESTART_NODE_DESCRIPTION2 {
+            Kind: FABRIC_RESTART_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone, Default)]
+pub struct StartNodeDescription {
+    pub node_name: WString,
+    /// The instance of the stopped node. 0 matches any instance.
+    pub node_instance_id: u64,
+    pub ip_address_or_fqdn: WString,
+    pub cluster_connection_port: u32,
+}
+
+impl GetRawWithBoxPool<FABRIC_START_NODE_DESCRIPTION2> for StartNodeDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_START_NODE_DESCRIPTION2 {
+        let value = pool.push(Box::new(FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME {
+            NodeName: self.node_name.as_pcwstr(),
+            NodeInstanceId: self.node_instance_id,
+            IPAddressOrFQDN: self.ip_address_or_fqdn.as_pcwstr(),
+            ClusterConnectionPort: self.cluster_connection_port,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_START_NODE_DESCRIPTION2 {
+            Kind: FABRIC_START_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone, Default)]
+pub struct StopNodeDescription {
+    pub node_name: WString,
+    /// Stops only if the node instance matches. 0 matches any instance.
+    pub node_instance_id: u64,
+}
+
+impl GetRawWithBoxPool<FABRIC_STOP_NODE_DESCRIPTION2> for StopNodeDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_STOP_NODE_DESCRIPTION2 {
+        let value = pool.push(Box::new(FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME {
+            NodeName: self.node_name.as_pcwstr(),
+            NodeInstanceId: self.node_instance_id,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_STOP_NODE_DESCRIPTION2 {
+            Kind: FABRIC_STOP_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone, Default)]
+pub struct RestartDeployedCodePackageDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub code_package_name: WString,
+    /// Restarts only if the code package instance matches. 0 matches any instance.
+    pub code_package_instance_id: i64,
+}
+
+impl GetRawWithBoxPool<FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2>
+    for RestartDeployedCodePackageDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2 {
+        let value = pool.push(Box::new(
+            FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME {
+                NodeName: self.node_name.as_pcwstr(),
+                ApplicationName: self.application_name.as_raw(),
+                ServiceManifestName: self.service_manifest_name.as_pcwstr(),
+                CodePackageName: self.code_package_name.as_pcwstr(),
+                CodePackageInstanceId: self.code_package_instance_id,
+                Reserved: std::ptr::null_mut(),
+            },
+        ));
+        FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2 {
+            Kind: FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct MovePrimaryDescription {
+    /// The node to move the primary to. Empty picks a random node.
+    pub node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+    pub ignore_constraints: bool,
+}
+
+impl GetRawWithBoxPool<FABRIC_MOVE_PRIMARY_DESCRIPTION2> for MovePrimaryDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_MOVE_PRIMARY_DESCRIPTION2 {
+        let value = pool.push(Box::new(FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME {
+            NodeName: self.node_name.as_pcwstr(),
+            ServiceName: self.service_name.as_raw(),
+            PartitionId: self.partition_id,
+            IgnoreConstraints: self.ignore_constraints,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_MOVE_PRIMARY_DESCRIPTION2 {
+            Kind: FABRIC_MOVE_PRIMARY_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct MoveSecondaryDescription {
+    pub current_node_name: WString,
+    /// The node to move the secondary to. Empty picks a random node.
+    pub new_node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+    pub ignore_constraints: bool,
+}
+
+impl GetRawWithBoxPool<FABRIC_MOVE_SECONDARY_DESCRIPTION2> for MoveSecondaryDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_MOVE_SECONDARY_DESCRIPTION2 {
+        let value = pool.push(Box::new(
+            FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME {
+                CurrentNodeName: self.current_node_name.as_pcwstr(),
+                NewNodeName: self.new_node_name.as_pcwstr(),
+                ServiceName: self.service_name.as_raw(),
+                PartitionId: self.partition_id,
+                IgnoreConstraints: self.ignore_constraints,
+                Reserved: std::ptr::null_mut(),
+            },
+        ));
+        FABRIC_MOVE_SECONDARY_DESCRIPTION2 {
+            Kind: FABRIC_MOVE_SECONDARY_DESCRIPTION_KIND_USING_NODE_NAME,
+            Value: value as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_NODE_RESULT
+#[derive(Debug, Clone)]
+pub struct NodeResult {
+    pub node_name: WString,
+    pub node_instance_id: u64,
+}
+
+impl From<&FABRIC_NODE_RESULT> for NodeResult {
+    fn from(value: &FABRIC_NODE_RESULT) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            node_instance_id: value.NodeInstance,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_RESULT
+#[derive(Debug, Clone)]
+pub struct DeployedCodePackageResult {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub code_package_name: WString,
+    pub code_package_instance_id: i64,
+}
+
+impl From<&FABRIC_DEPLOYED_CODE_PACKAGE_RESULT> for DeployedCodePackageResult {
+    fn from(value: &FABRIC_DEPLOYED_CODE_PACKAGE_RESULT) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            application_name: Uri::from(value.ApplicationName),
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            code_package_name: WString::from(value.CodePackageName),
+            code_package_instance_id: value.CodePackageInstanceId,
+        }
+    }
+}
+
+// FABRIC_MOVE_PRIMARY_RESULT
+#[derive(Debug, Clone)]
+pub struct MovePrimaryResult {
+    pub node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_MOVE_PRIMARY_RESULT> for MovePrimaryResult {
+    fn from(value: &FABRIC_MOVE_PRIMARY_RESULT) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            service_name: Uri::from(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+// FABRIC_MOVE_SECONDARY_RESULT
+#[derive(Debug, Clone)]
+pub struct MoveSecondaryResult {
+    pub current_node_name: WString,
+    pub new_node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_MOVE_SECONDARY_RESULT> for MoveSecondaryResult {
+    fn from(value: &FABRIC_MOVE_SECONDARY_RESULT) -> Self {
+        Self {
+            current_node_name: WString::from(value.CurrentNodeName),
+            new_node_name: WString::from(value.NewNodeName),
+            service_name: Uri::from(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+// FABRIC_PARTITION_SELECTOR
+/// Selects the partition of a service to run a test command on.
+#[derive(Debug, Clone)]
+pub enum PartitionSelector {
+    Singleton {
+        service_name: Uri,
+    },
+    Named {
+        service_name: Uri,
+        name: WString,
+    },
+    UniformInt64 {
+        service_name: Uri,
+        key: i64,
+    },
+    PartitionId {
+        service_name: Uri,
+        partition_id: GUID,
+    },
+    /// A random partition of the service.
+    Random {
+        service_name: Uri,
+    },
+}
+
+impl GetRawWithBoxPool<FABRIC_PARTITION_SELECTOR> for PartitionSelector {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_PARTITION_SELECTOR {
+        let (service_name, selector_type, key) = match self {
+            PartitionSelector::Singleton { service_name } => {
+                (service_name, FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON, None)
+            }
+            PartitionSelector::Named { service_name, name } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_NAMED,
+                Some(name.clone()),
+            ),
+            PartitionSelector::UniformInt64 { service_name, key } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64,
+                Some(WString::from(key.to_string())),
+            ),
+            PartitionSelector::PartitionId {
+                service_name,
+                partition_id,
+            } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID,
+                Some(WString::from(format!("{partition_id:?}"))),
+            ),
+            PartitionSelector::Random { service_name } => {
+                (service_name, FABRIC_PARTITION_SELECTOR_TYPE_RANDOM, None)
+            }
+        };
+        // The key string is kept in the pool so that the raw pointer stays valid.
+        let key = key.map_or(windows_core::PCWSTR::null(), |k| {
+            let k = pool.push(Box::new(k));
+            unsafe { (*k).as_pcwstr() }
+        });
+        FABRIC_PARTITION_SELECTOR {
+            ServiceName: service_name.as_raw(),
+            PartitionSelectorType: selector_type,
+            PartitionKey: key,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DATA_LOSS_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataLossMode {
+    /// Drops some replicas, the partition rebuilds from the remaining ones.
+    Partial,
+    /// Drops all replicas and their state.
+    Full,
+}
+
+impl From<DataLossMode> for FABRIC_DATA_LOSS_MODE {
+    fn from(value: DataLossMode) -> Self {
+        match value {
+            DataLossMode::Partial => FABRIC_DATA_LOSS_MODE_PARTIAL,
+            DataLossMode::Full => FABRIC_DATA_LOSS_MODE_FULL,
+        }
+    }
+}
+
+// FABRIC_QUORUM_LOSS_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumLossMode {
+    /// Brings down a quorum of replicas.
+    QuorumReplicas,
+    AllReplicas,
+}
+
+impl From<QuorumLossMode> for FABRIC_QUORUM_LOSS_MODE {
+    fn from(value: QuorumLossMode) -> Self {
+        match value {
+            QuorumLossMode::QuorumReplicas => FABRIC_QUORUM_LOSS_MODE_QUORUM_REPLICAS,
+            QuorumLossMode::AllReplicas => FABRIC_QUORUM_LOSS_MODE_ALL_REPLICAS,
+        }
+    }
+}
+
+// FABRIC_RESTART_PARTITION_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPartitionMode {
+    AllReplicasOrInstances,
+    OnlyActiveSecondaries,
+}
+
+impl From<RestartPartitionMode> for FABRIC_RESTART_PARTITION_MODE {
+    fn from(value: RestartPartitionMode) -> Self {
+        match value {
+            RestartPartitionMode::AllReplicasOrInstances => {
+                FABRIC_RESTART_PARTITION_MODE_ALL_REPLICAS_OR_INSTANCES
+            }
+            RestartPartitionMode::OnlyActiveSecondaries => {
+                FABRIC_RESTART_PARTITION_MODE_ONLY_ACTIVE_SECONDARIES
+            }
+        }
+    }
+}
+
+// FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct StartPartitionDataLossDescription {
+    /// Caller chosen id to track the command progress.
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub data_loss_mode: DataLossMode,
+}
+
+impl GetRawWithBoxPool<FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION>
+    for StartPartitionDataLossDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION {
+        let selector = Box::new(self.partition_selector.get_raw_with_pool(pool));
+        FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: pool.push(selector) as *mut _,
+            DataLossMode: self.data_loss_mode.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct StartPartitionQuorumLossDescription {
+    /// Caller chosen id to track the command progress.
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub quorum_loss_mode: QuorumLossMode,
+    /// How long the partition is kept in quorum loss.
+    pub quorum_loss_duration_milliseconds: i32,
+}
+
+impl GetRawWithBoxPool<FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION>
+    for StartPartitionQuorumLossDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION {
+        let selector = Box::new(self.partition_selector.get_raw_with_pool(pool));
+        FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: pool.push(selector) as *mut _,
+            QuorumLossMode: self.quorum_loss_mode.into(),
+            QuorumLossDurationInMilliSeconds: self.quorum_loss_duration_milliseconds,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_START_PARTITION_RESTART_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct StartPartitionRestartDescription {
+    /// Caller chosen id to track the command progress.
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub restart_partition_mode: RestartPartitionMode,
+}
+
+impl GetRawWithBoxPool<FABRIC_START_PARTITION_RESTART_DESCRIPTION>
+    for StartPartitionRestartDescription
+{
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_START_PARTITION_RESTART_DESCRIPTION {
+        let selector = Box::new(self.partition_selector.get_raw_with_pool(pool));
+        FABRIC_START_PARTITION_RESTART_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: pool.push(selector) as *mut _,
+            RestartPartitionMode: self.restart_partition_mode.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_NODE_TRANSITION_DESCRIPTION
+#[derive(Debug, Clone)]
+pub enum NodeTransitionDescription {
+    /// Starts a node stopped by a previous Stop transition.
+    Start {
+        operation_id: GUID,
+        node_name: WString,
+        node_instance_id: u64,
+    },
+    /// Stops a node, it is started again after the duration.
+    Stop {
+        operation_id: GUID,
+        node_name: WString,
+        node_instance_id: u64,
+        stop_duration_seconds: u32,
+    },
+}
+
+impl GetRawWithBoxPool<FABRIC_NODE_TRANSITION_DESCRIPTION> for NodeTransitionDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_NODE_TRANSITION_DESCRIPTION {
+        match self {
+            NodeTransitionDescription::Start {
+                operation_id,
+                node_name,
+                node_instance_id,
+            } => {
+                let value = pool.push(Box::new(FABRIC_NODE_START_DESCRIPTION {
+                    OperationId: *operation_id,
+                    NodeName: node_name.as_pcwstr(),
+                    NodeInstanceId: *node_instance_id,
+                    Reserved: std::ptr::null_mut(),
+                }));
+                FABRIC_NODE_TRANSITION_DESCRIPTION {
+                    NodeTransitionType: FABRIC_NODE_TRANSITION_TYPE_START,
+                    Value: value as *mut c_void,
+                }
+            }
+            NodeTransitionDescription::Stop {
+                operation_id,
+                node_name,
+                node_instance_id,
+                stop_duration_seconds,
+            } => {
+                let value = pool.push(Box::new(FABRIC_NODE_STOP_DESCRIPTION {
+                    OperationId: *operation_id,
+                    NodeName: node_name.as_pcwstr(),
+                    NodeInstanceId: *node_instance_id,
+                    StopDurationInSeconds: *stop_duration_seconds,
+                    Reserved: std::ptr::null_mut(),
+                }));
+                FABRIC_NODE_TRANSITION_DESCRIPTION {
+                    NodeTransitionType: FABRIC_NODE_TRANSITION_TYPE_STOP,
+                    Value: value as *mut c_void,
+                }
+            }
+        }
+    }
+}
+
+// FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct CancelTestCommandDescription {
+    pub operation_id: GUID,
+    /// Cancels without rolling back the state changed by the command.
+    pub force: bool,
+}
+
+impl GetRaw<FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION> for CancelTestCommandDescription {
+    fn get_raw(&self) -> FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION {
+        FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION {
+            OperationId: self.operation_id,
+            Force: self.force,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_TEST_COMMAND_PROGRESS_STATE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestCommandProgressState {
+    Invalid,
+    Running,
+    RollingBack,
+    Completed,
+    Faulted,
+    Cancelled,
+    ForceCancelled,
+}
+
+impl From<FABRIC_TEST_COMMAND_PROGRESS_STATE> for TestCommandProgressState {
+    fn from(value: FABRIC_TEST_COMMAND_PROGRESS_STATE) -> Self {
+        match value {
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_RUNNING => Self::Running,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_ROLLING_BACK => Self::RollingBack,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED => Self::Completed,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_FAULTED => Self::Faulted,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_CANCELLED => Self::Cancelled,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_FORCE_CANCELLED => Self::ForceCancelled,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl TestCommandProgressState {
+    /// Whether the command has finished and the progress will not change.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Faulted | Self::Cancelled | Self::ForceCancelled
+        )
+    }
+}
+
+// FABRIC_SELECTED_PARTITION
+#[derive(Debug, Clone)]
+pub struct SelectedPartition {
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_SELECTED_PARTITION> for SelectedPartition {
+    fn from(value: &FABRIC_SELECTED_PARTITION) -> Self {
+        Self {
+            service_name: Uri::from(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+/// Result of a partition test command, available once the command is completed.
+#[derive(Debug, Clone)]
+pub struct PartitionTestCommandResult {
+    pub selected_partition: Option<SelectedPartition>,
+    pub error_code: HRESULT,
+}
+
+/// Result of a node transition, available once the command is completed.
+#[derive(Debug, Clone)]
+pub struct NodeTransitionResult {
+    pub node_result: Option<NodeResult>,
+    pub error_code: HRESULT,
+}
+
+/// Progress of a test command.
+/// Poll until the state is terminal, the result is set once it is completed.
+#[derive(Debug, Clone)]
+pub struct TestCommandProgress<R> {
+    pub state: TestCommandProgressState,
+    pub result: Option<R>,
+}
+
+pub type PartitionDataLossProgress = TestCommandProgress<PartitionTestCommandResult>;
+pub type PartitionQuorumLossProgress = TestCommandProgress<PartitionTestCommandResult>;
+pub type PartitionRestartProgress = TestCommandProgress<PartitionTestCommandResult>;
+pub type NodeTransitionProgress = TestCommandProgress<NodeTransitionResult>;
+
+// The partition progress structs have the same layout but distinct types.
+macro_rules! impl_partition_progress_from {
+    ($raw:ty) => {
+        impl From<&$raw> for TestCommandProgress<PartitionTestCommandResult> {
+            fn from(value: &$raw) -> Self {
+                let result = unsafe { value.Result.as_ref() }.map(|r| PartitionTestCommandResult {
+                    selected_partition: unsafe { r.SelectedPartition.as_ref() }
+                        .map(SelectedPartition::from),
+                    error_code: r.ErrorCode,
+                });
+                Self {
+                    state: value.State.into(),
+                    result,
+                }
+            }
+        }
+    };
+}
+
+impl_partition_progress_from!(FABRIC_PARTITION_DATA_LOSS_PROGRESS);
+impl_partition_progress_from!(FABRIC_PARTITION_QUORUM_LOSS_PROGRESS);
+impl_partition_progress_from!(FABRIC_PARTITION_RESTART_PROGRESS);
+
+impl From<&FABRIC_NODE_TRANSITION_PROGRESS> for NodeTransitionProgress {
+    fn from(value: &FABRIC_NODE_TRANSITION_PROGRESS) -> Self {
+        let result = unsafe { value.Result.as_ref() }.map(|r| NodeTransitionResult {
+            node_result: unsafe { r.NodeResult.as_ref() }.map(NodeResult::from),
+            error_code: r.ErrorCode,
+        });
+        Self {
+            state: value.State.into(),
+            result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_NODE_STOP_DESCRIPTION, FABRIC_NODE_TRANSITION_TYPE_STOP,
+        FABRIC_PARTITION_DATA_LOSS_PROGRESS, FABRIC_PARTITION_DATA_LOSS_RESULT,
+        FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON, FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64,
+        FABRIC_SELECTED_PARTITION, FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED,
+    };
+    use windows_core::{GUID, HRESULT, WString};
+
+    use super::{
+        NodeTransitionDescription, PartitionDataLossProgress, PartitionSelector,
+        TestCommandProgressState,
+    };
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::Uri,
+    };
+
+    #[test]
+    fn test_partition_selector_raw() {
+        let mut pool = BoxPool::new();
+        let singleton = PartitionSelector::Singleton {
+            service_name: Uri::from("fabric:/app/svc"),
+        }
+        .get_raw_with_pool(&mut pool);
+        assert_eq!(
+            singleton.PartitionSelectorType,
+            FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON
+        );
+        assert!(singleton.PartitionKey.is_null());
+
+        let ranged = PartitionSelector::UniformInt64 {
+            service_name: Uri::from("fabric:/app/svc"),
+            key: -5,
+        }
+        .get_raw_with_pool(&mut pool);
+        assert_eq!(
+            ranged.PartitionSelectorType,
+            FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64
+        );
+        assert_eq!(WString::from(ranged.PartitionKey), WString::from("-5"));
+    }
+
+    #[test]
+    fn test_node_transition_raw() {
+        let mut pool = BoxPool::new();
+        let raw = NodeTransitionDescription::Stop {
+            operation_id: GUID::from_u128(1),
+            node_name: WString::from("_Node_0"),
+            node_instance_id: 0,
+            stop_duration_seconds: 60,
+        }
+        .get_raw_with_pool(&mut pool);
+        assert_eq!(raw.NodeTransitionType, FABRIC_NODE_TRANSITION_TYPE_STOP);
+        let stop = unsafe { (raw.Value as *const FABRIC_NODE_STOP_DESCRIPTION).as_ref() }.unwrap();
+        assert_eq!(stop.OperationId, GUID::from_u128(1));
+        assert_eq!(stop.StopDurationInSeconds, 60);
+    }
+
+    #[test]
+    fn test_progress_from_raw() {
+        let mut selected = FABRIC_SELECTED_PARTITION {
+            PartitionId: GUID::from_u128(2),
+            ..Default::default()
+        };
+        let mut result = FABRIC_PARTITION_DATA_LOSS_RESULT {
+            SelectedPartition: &mut selected,
+            ErrorCode: HRESULT(0),
+            ..Default::default()
+        };
+        let raw = FABRIC_PARTITION_DATA_LOSS_PROGRESS {
+            State: FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED,
+            Result: &mut result,
+            ..Default::default()
+        };
+        let progress = PartitionDataLossProgress::from(&raw);
+        assert_eq!(progress.state, TestCommandProgressState::Completed);
+        assert!(progress.state.is_terminal());
+        let result = progress.result.unwrap();
+        assert_eq!(
+            result.selected_partition.unwrap().partition_id,
+            GUID::from_u128(2)
+        );
+        assert!(result.error_code.is_ok());
+    }
+}
@@ -138,6 +138,16 @@ impl GetRaw<FABRIC_CLUSTER_HEALTH_POLICY> for ClusterHealthPolicy {
     }
 }
 
+impl From<&FABRIC_CLUSTER_HEALTH_POLICY> for ClusterHealthPolicy {
+    fn from(value: &FABRIC_CLUSTER_HEALTH_POLICY) -> Self {
+        Self {
+            consider_warning_as_error: value.ConsiderWarningAsError,
+            max_percent_unhealthy_nodes: value.MaxPercentUnhealthyNodes,
+            max_percent_unhealthy_applications: value.MaxPercentUnhealthyApplications,
+        }
+    }
+}
+
 /// FABRIC_HEALTH_EVENTS_FILTER
 #[derive(Debug, Clone)]
 pub struct HealthEventsFilter {
@@ -364,6 +374,28 @@ impl GetRawWithBoxPool<FABRIC_APPLICATION_HEALTH_POLICY> for ApplicationHealthPo
     }
 }
 
+// FABRIC_APPLICATION_HEALTH_POLICY_MAP
+pub(super) fn application_health_policy_map_raw(
+    policies: &std::collections::HashMap<Uri, ApplicationHealthPolicy>,
+    pool: &mut BoxPool,
+) -> *const FABRIC_APPLICATION_HEALTH_POLICY_MAP {
+    let mut items = Vec::new();
+    for (name, policy) in policies {
+        let raw_policy = Box::new(policy.get_raw_with_pool(pool));
+        let raw_policy = pool.push(raw_policy);
+        items.push(FABRIC_APPLICATION_HEALTH_POLICY_MAP_ITEM {
+            ApplicationName: name.as_raw(),
+            HealthPolicy: raw_policy,
+        });
+    }
+    let (count, items) = pool.push_vec(items);
+    // save the items on heap_holder to extend the lifetime
+    pool.push(Box::new(FABRIC_APPLICATION_HEALTH_POLICY_MAP {
+        Count: count as u32,
+        Items: items as *mut _,
+    }))
+}
+
 // FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION
 #[derive(Debug, Clone, Default)]
 pub struct ClusterHealthQueryDescription {
@@ -381,24 +413,10 @@ impl GetRawWithBoxPool<FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION> for ClusterHealt
             .health_policy
             .as_ref()
             .map(|policy| pool.push(Box::new(policy.get_raw())));
-        let application_health_policy_map =
-            self.application_health_policy_map.as_ref().map(|policies| {
-                let mut items = Vec::new();
-                for (name, policy) in policies {
-                    let raw_policy = Box::new(policy.get_raw_with_pool(pool));
-                    let raw_policy = pool.push(raw_policy);
-                    items.push(FABRIC_APPLICATION_HEALTH_POLICY_MAP_ITEM {
-                        ApplicationName: name.as_raw(),
-                        HealthPolicy: raw_policy,
-                    });
-                }
-                let (count, items) = pool.push_vec(items);
-                // save the items on heap_holder to extend the lifetime
-                pool.push(Box::new(FABRIC_APPLICATION_HEALTH_POLICY_MAP {
-                    Count: count as u32,
-                    Items: items as *mut _,
-                }))
-            });
+        let application_health_policy_map = self
+            .application_health_policy_map
+            .as_ref()
+            .map(|policies| application_health_policy_map_raw(policies, pool));
         FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION {
             HealthPolicy: health_policy.unwrap_or(std::ptr::null()),
             ApplicationHealthPolicyMap: application_health_policy_map.unwrap_or(std::ptr::null()),
@@ -0,0 +1,822 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Cluster health chunk query types.
+// A chunk query returns the health states of the cluster entities matching
+// the filters in a single call. Children are only returned when a filter
+// for them is given.
+
+use mssf_com::{
+    FabricClient::IFabricGetClusterHealthChunkResult,
+    FabricTypes::{
+        FABRIC_APPLICATION_HEALTH_STATE_CHUNK, FABRIC_APPLICATION_HEALTH_STATE_CHUNK_EX1,
+        FABRIC_APPLICATION_HEALTH_STATE_FILTER, FABRIC_APPLICATION_HEALTH_STATE_FILTER_EX1,
+        FABRIC_APPLICATION_HEALTH_STATE_FILTER_LIST, FABRIC_CLUSTER_HEALTH_CHUNK,
+        FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER_LIST,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK_EX1,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER_EX1,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER_LIST, FABRIC_INVALID_REPLICA_ID,
+        FABRIC_NODE_HEALTH_STATE_CHUNK, FABRIC_NODE_HEALTH_STATE_FILTER,
+        FABRIC_NODE_HEALTH_STATE_FILTER_LIST, FABRIC_PARTITION_HEALTH_STATE_CHUNK,
+        FABRIC_PARTITION_HEALTH_STATE_FILTER, FABRIC_PARTITION_HEALTH_STATE_FILTER_LIST,
+        FABRIC_REPLICA_HEALTH_STATE_CHUNK, FABRIC_REPLICA_HEALTH_STATE_FILTER,
+        FABRIC_REPLICA_HEALTH_STATE_FILTER_LIST, FABRIC_SERVICE_HEALTH_STATE_CHUNK,
+        FABRIC_SERVICE_HEALTH_STATE_FILTER, FABRIC_SERVICE_HEALTH_STATE_FILTER_LIST, FABRIC_URI,
+    },
+};
+use windows_core::{GUID, PCWSTR, WString};
+
+use crate::{
+    mem::{BoxPool, GetRaw, GetRawWithBoxPool},
+    types::{
+        ApplicationHealthPolicy, ClusterHealthPolicy, HealthState, HealthStateFilterFlags, Uri,
+    },
+};
+
+use super::health::application_health_policy_map_raw;
+
+/// Pushes the raw filter list on the pool. An empty list is passed as null.
+fn filter_list_raw<T, R, L>(
+    filters: &[T],
+    pool: &mut BoxPool,
+    make_list: impl FnOnce(u32, *const R) -> L,
+) -> *const L
+where
+    T: GetRawWithBoxPool<R>,
+    R: 'static,
+    L: 'static,
+{
+    if filters.is_empty() {
+        return std::ptr::null();
+    }
+    let items = filters
+        .iter()
+        .map(|f| f.get_raw_with_pool(pool))
+        .collect::<Vec<_>>();
+    let (count, items) = pool.push_vec(items);
+    pool.push(Box::new(make_list(count as u32, items)))
+}
+
+fn optional_string_from_raw(value: PCWSTR) -> Option<WString> {
+    if value.is_null() {
+        None
+    } else {
+        Some(WString::from(value))
+    }
+}
+
+// FABRIC_NODE_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct NodeHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the node with this name.
+    pub node_name_filter: Option<WString>,
+}
+
+impl NodeHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            node_name_filter: None,
+        }
+    }
+
+    pub fn with_node_name(mut self, node_name: WString) -> Self {
+        self.node_name_filter = Some(node_name);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_NODE_HEALTH_STATE_FILTER> for NodeHealthStateFilter {
+    fn get_raw_with_pool(&self, _pool: &mut BoxPool) -> FABRIC_NODE_HEALTH_STATE_FILTER {
+        FABRIC_NODE_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            NodeNameFilter: self.node_name_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct ReplicaHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the replica or instance with this id.
+    pub replica_or_instance_id_filter: Option<i64>,
+}
+
+impl ReplicaHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            replica_or_instance_id_filter: None,
+        }
+    }
+
+    pub fn with_replica_or_instance_id(mut self, id: i64) -> Self {
+        self.replica_or_instance_id_filter = Some(id);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_REPLICA_HEALTH_STATE_FILTER> for ReplicaHealthStateFilter {
+    fn get_raw_with_pool(&self, _pool: &mut BoxPool) -> FABRIC_REPLICA_HEALTH_STATE_FILTER {
+        FABRIC_REPLICA_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            ReplicaOrInstanceIdFilter: self
+                .replica_or_instance_id_filter
+                .unwrap_or(FABRIC_INVALID_REPLICA_ID as i64),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct PartitionHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the partition with this id.
+    pub partition_id_filter: Option<GUID>,
+    pub replica_filters: Vec<ReplicaHealthStateFilter>,
+}
+
+impl PartitionHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            partition_id_filter: None,
+            replica_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_partition_id(mut self, partition_id: GUID) -> Self {
+        self.partition_id_filter = Some(partition_id);
+        self
+    }
+
+    pub fn with_replica_filter(mut self, filter: ReplicaHealthStateFilter) -> Self {
+        self.replica_filters.push(filter);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_PARTITION_HEALTH_STATE_FILTER> for PartitionHealthStateFilter {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_PARTITION_HEALTH_STATE_FILTER {
+        FABRIC_PARTITION_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            PartitionIdFilter: self.partition_id_filter.unwrap_or(GUID::zeroed()),
+            ReplicaFilters: filter_list_raw(&self.replica_filters, pool, |count, items| {
+                FABRIC_REPLICA_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                }
+            }),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct ServiceHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the service with this name.
+    pub service_name_filter: Option<Uri>,
+    pub partition_filters: Vec<PartitionHealthStateFilter>,
+}
+
+impl ServiceHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            service_name_filter: None,
+            partition_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_service_name(mut self, service_name: Uri) -> Self {
+        self.service_name_filter = Some(service_name);
+        self
+    }
+
+    pub fn with_partition_filter(mut self, filter: PartitionHealthStateFilter) -> Self {
+        self.partition_filters.push(filter);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_SERVICE_HEALTH_STATE_FILTER> for ServiceHealthStateFilter {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_SERVICE_HEALTH_STATE_FILTER {
+        FABRIC_SERVICE_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            ServiceNameFilter: self
+                .service_name_filter
+                .as_ref()
+                .map_or(FABRIC_URI::default(), |n| n.as_raw()),
+            PartitionFilters: filter_list_raw(&self.partition_filters, pool, |count, items| {
+                FABRIC_PARTITION_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                }
+            }),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the service package with this manifest name.
+    pub service_manifest_name_filter: Option<WString>,
+    // EX1
+    /// Only returns the service package with this activation id.
+    pub service_package_activation_id_filter: Option<WString>,
+}
+
+impl DeployedServicePackageHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            service_manifest_name_filter: None,
+            service_package_activation_id_filter: None,
+        }
+    }
+
+    pub fn with_service_manifest_name(mut self, service_manifest_name: WString) -> Self {
+        self.service_manifest_name_filter = Some(service_manifest_name);
+        self
+    }
+
+    pub fn with_service_package_activation_id(mut self, activation_id: WString) -> Self {
+        self.service_package_activation_id_filter = Some(activation_id);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER>
+    for DeployedServicePackageHealthStateFilter
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER {
+        let ex1 = pool.push(Box::new(
+            FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER_EX1 {
+                ServicePackageActivationIdFilter: self
+                    .service_package_activation_id_filter
+                    .as_ref()
+                    .into(),
+                Reserved: std::ptr::null_mut(),
+            },
+        ));
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            ServiceManifestNameFilter: self.service_manifest_name_filter.as_ref().into(),
+            Reserved: ex1 as *mut _,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the application deployed on this node.
+    pub node_name_filter: Option<WString>,
+    pub deployed_service_package_filters: Vec<DeployedServicePackageHealthStateFilter>,
+}
+
+impl DeployedApplicationHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            node_name_filter: None,
+            deployed_service_package_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_node_name(mut self, node_name: WString) -> Self {
+        self.node_name_filter = Some(node_name);
+        self
+    }
+
+    pub fn with_deployed_service_package_filter(
+        mut self,
+        filter: DeployedServicePackageHealthStateFilter,
+    ) -> Self {
+        self.deployed_service_package_filters.push(filter);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER>
+    for DeployedApplicationHealthStateFilter
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER {
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            NodeNameFilter: self.node_name_filter.as_ref().into(),
+            DeployedServicePackageFilters: filter_list_raw(
+                &self.deployed_service_package_filters,
+                pool,
+                |count, items| FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                },
+            ),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_HEALTH_STATE_FILTER
+#[derive(Debug, Clone)]
+pub struct ApplicationHealthStateFilter {
+    pub health_state_filter: HealthStateFilterFlags,
+    /// Only returns the application with this name.
+    pub application_name_filter: Option<Uri>,
+    pub service_filters: Vec<ServiceHealthStateFilter>,
+    pub deployed_application_filters: Vec<DeployedApplicationHealthStateFilter>,
+    // EX1
+    /// Only returns applications of this type.
+    pub application_type_name_filter: Option<WString>,
+}
+
+impl ApplicationHealthStateFilter {
+    pub fn new(health_state_filter: HealthStateFilterFlags) -> Self {
+        Self {
+            health_state_filter,
+            application_name_filter: None,
+            service_filters: Vec::new(),
+            deployed_application_filters: Vec::new(),
+            application_type_name_filter: None,
+        }
+    }
+
+    pub fn with_application_name(mut self, application_name: Uri) -> Self {
+        self.application_name_filter = Some(application_name);
+        self
+    }
+
+    pub fn with_application_type_name(mut self, application_type_name: WString) -> Self {
+        self.application_type_name_filter = Some(application_type_name);
+        self
+    }
+
+    pub fn with_service_filter(mut self, filter: ServiceHealthStateFilter) -> Self {
+        self.service_filters.push(filter);
+        self
+    }
+
+    pub fn with_deployed_application_filter(
+        mut self,
+        filter: DeployedApplicationHealthStateFilter,
+    ) -> Self {
+        self.deployed_application_filters.push(filter);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_APPLICATION_HEALTH_STATE_FILTER> for ApplicationHealthStateFilter {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_APPLICATION_HEALTH_STATE_FILTER {
+        let ex1 = pool.push(Box::new(FABRIC_APPLICATION_HEALTH_STATE_FILTER_EX1 {
+            ApplicationTypeNameFilter: self.application_type_name_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_APPLICATION_HEALTH_STATE_FILTER {
+            HealthStateFilter: self.health_state_filter.bits() as u32,
+            ApplicationNameFilter: self
+                .application_name_filter
+                .as_ref()
+                .map_or(FABRIC_URI::default(), |n| n.as_raw()),
+            ServiceFilters: filter_list_raw(&self.service_filters, pool, |count, items| {
+                FABRIC_SERVICE_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                }
+            }),
+            DeployedApplicationFilters: filter_list_raw(
+                &self.deployed_application_filters,
+                pool,
+                |count, items| FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                },
+            ),
+            Reserved: ex1 as *mut _,
+        }
+    }
+}
+
+// FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ClusterHealthChunkQueryDescription {
+    pub health_policy: Option<ClusterHealthPolicy>,
+    pub application_health_policy_map:
+        Option<std::collections::HashMap<Uri, ApplicationHealthPolicy>>,
+    /// No applications are returned when empty.
+    pub application_filters: Vec<ApplicationHealthStateFilter>,
+    /// No nodes are returned when empty.
+    pub node_filters: Vec<NodeHealthStateFilter>,
+}
+
+impl GetRawWithBoxPool<FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION>
+    for ClusterHealthChunkQueryDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION {
+        FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION {
+            ClusterHealthPolicy: self
+                .health_policy
+                .as_ref()
+                .map_or(std::ptr::null(), |p| pool.push(Box::new(p.get_raw()))),
+            ApplicationHealthPolicyMap: self
+                .application_health_policy_map
+                .as_ref()
+                .map_or(std::ptr::null(), |m| {
+                    application_health_policy_map_raw(m, pool)
+                }),
+            ApplicationFilters: filter_list_raw(&self.application_filters, pool, |count, items| {
+                FABRIC_APPLICATION_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                }
+            }),
+            NodeFilters: filter_list_raw(&self.node_filters, pool, |count, items| {
+                FABRIC_NODE_HEALTH_STATE_FILTER_LIST {
+                    Count: count,
+                    Items: items,
+                }
+            }),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_NODE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct NodeHealthStateChunk {
+    pub node_name: WString,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_NODE_HEALTH_STATE_CHUNK> for NodeHealthStateChunk {
+    fn from(value: &FABRIC_NODE_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ReplicaHealthStateChunk {
+    pub replica_or_instance_id: i64,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_REPLICA_HEALTH_STATE_CHUNK> for ReplicaHealthStateChunk {
+    fn from(value: &FABRIC_REPLICA_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            replica_or_instance_id: value.ReplicaOrInstanceId,
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct PartitionHealthStateChunk {
+    pub partition_id: GUID,
+    pub health_state: HealthState,
+    pub replica_health_state_chunks: Vec<ReplicaHealthStateChunk>,
+}
+
+impl From<&FABRIC_PARTITION_HEALTH_STATE_CHUNK> for PartitionHealthStateChunk {
+    fn from(value: &FABRIC_PARTITION_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            health_state: (&value.HealthState).into(),
+            replica_health_state_chunks: unsafe { value.ReplicaHealthStateChunks.as_ref() }
+                .map_or(vec![], |list| {
+                    crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+                }),
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ServiceHealthStateChunk {
+    pub service_name: Uri,
+    pub health_state: HealthState,
+    pub partition_health_state_chunks: Vec<PartitionHealthStateChunk>,
+}
+
+impl From<&FABRIC_SERVICE_HEALTH_STATE_CHUNK> for ServiceHealthStateChunk {
+    fn from(value: &FABRIC_SERVICE_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            service_name: Uri::from(value.ServiceName),
+            health_state: (&value.HealthState).into(),
+            partition_health_state_chunks: unsafe { value.PartitionHealthStateChunks.as_ref() }
+                .map_or(vec![], |list| {
+                    crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+                }),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthStateChunk {
+    pub service_manifest_name: WString,
+    /// None for the shared activation mode.
+    pub service_package_activation_id: Option<WString>,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK>
+    for DeployedServicePackageHealthStateChunk
+{
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK_EX1)
+                .as_ref()
+        };
+        Self {
+            service_manifest_name: WString::from(value.ServiceManifestName),
+            service_package_activation_id: ex1
+                .and_then(|ex1| optional_string_from_raw(ex1.ServicePackageActivationId))
+                .filter(|id| !id.is_empty()),
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealthStateChunk {
+    pub node_name: WString,
+    pub health_state: HealthState,
+    pub deployed_service_package_health_state_chunks: Vec<DeployedServicePackageHealthStateChunk>,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK> for DeployedApplicationHealthStateChunk {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            health_state: (&value.HealthState).into(),
+            deployed_service_package_health_state_chunks: unsafe {
+                value.DeployedServicePackageHealthStateChunks.as_ref()
+            }
+            .map_or(vec![], |list| {
+                crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+            }),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ApplicationHealthStateChunk {
+    pub application_name: Uri,
+    pub application_type_name: Option<WString>,
+    pub health_state: HealthState,
+    pub service_health_state_chunks: Vec<ServiceHealthStateChunk>,
+    pub deployed_application_health_state_chunks: Vec<DeployedApplicationHealthStateChunk>,
+}
+
+impl From<&FABRIC_APPLICATION_HEALTH_STATE_CHUNK> for ApplicationHealthStateChunk {
+    fn from(value: &FABRIC_APPLICATION_HEALTH_STATE_CHUNK) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_APPLICATION_HEALTH_STATE_CHUNK_EX1).as_ref()
+        };
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            application_type_name: ex1
+                .and_then(|ex1| optional_string_from_raw(PCWSTR(ex1.ApplicationTypeName.0))),
+            health_state: (&value.HealthState).into(),
+            service_health_state_chunks: unsafe { value.ServiceHealthStateChunks.as_ref() }
+                .map_or(vec![], |list| {
+                    crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+                }),
+            deployed_application_health_state_chunks: unsafe {
+                value.DeployedApplicationHealthStateChunks.as_ref()
+            }
+            .map_or(vec![], |list| {
+                crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+            }),
+        }
+    }
+}
+
+// FABRIC_CLUSTER_HEALTH_CHUNK
+#[derive(Debug, Clone)]
+pub struct ClusterHealthChunk {
+    pub health_state: HealthState,
+    pub node_health_state_chunks: Vec<NodeHealthStateChunk>,
+    pub application_health_state_chunks: Vec<ApplicationHealthStateChunk>,
+}
+
+impl From<&FABRIC_CLUSTER_HEALTH_CHUNK> for ClusterHealthChunk {
+    fn from(value: &FABRIC_CLUSTER_HEALTH_CHUNK) -> Self {
+        Self {
+            health_state: (&value.HealthState).into(),
+            node_health_state_chunks: unsafe { value.NodeHealthStateChunks.as_ref() }
+                .map_or(vec![], |list| {
+                    crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+                }),
+            application_health_state_chunks: unsafe { value.ApplicationHealthStateChunks.as_ref() }
+                .map_or(vec![], |list| {
+                    crate::iter::vec_from_raw_com(list.Count as usize, list.Items)
+                }),
+        }
+    }
+}
+
+impl From<&IFabricGetClusterHealthChunkResult> for ClusterHealthChunk {
+    fn from(value: &IFabricGetClusterHealthChunkResult) -> Self {
+        let raw = unsafe { value.get_ClusterHealthChunk().as_ref() }
+            .expect("cluster health chunk should not be null");
+        Self::from(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_APPLICATION_HEALTH_STATE_CHUNK, FABRIC_APPLICATION_HEALTH_STATE_CHUNK_LIST,
+        FABRIC_APPLICATION_HEALTH_STATE_FILTER_EX1, FABRIC_CLUSTER_HEALTH_CHUNK,
+        FABRIC_HEALTH_STATE_ERROR, FABRIC_HEALTH_STATE_OK, FABRIC_HEALTH_STATE_WARNING,
+        FABRIC_NODE_HEALTH_STATE_CHUNK, FABRIC_NODE_HEALTH_STATE_CHUNK_LIST,
+        FABRIC_PARTITION_HEALTH_STATE_CHUNK, FABRIC_PARTITION_HEALTH_STATE_CHUNK_LIST,
+        FABRIC_SERVICE_HEALTH_STATE_CHUNK, FABRIC_SERVICE_HEALTH_STATE_CHUNK_LIST, FABRIC_URI,
+    };
+    use windows_core::{GUID, WString};
+
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::{HealthState, HealthStateFilterFlags, Uri},
+    };
+
+    use super::{
+        ApplicationHealthStateFilter, ClusterHealthChunk, ClusterHealthChunkQueryDescription,
+        NodeHealthStateFilter, PartitionHealthStateFilter, ReplicaHealthStateFilter,
+        ServiceHealthStateFilter,
+    };
+
+    #[test]
+    fn test_cluster_health_chunk_query_raw() {
+        let desc = ClusterHealthChunkQueryDescription {
+            application_filters: vec![
+                ApplicationHealthStateFilter::new(HealthStateFilterFlags::ALL)
+                    .with_application_type_name("MyAppType".into())
+                    .with_service_filter(
+                        ServiceHealthStateFilter::new(HealthStateFilterFlags::ERROR)
+                            .with_partition_filter(
+                                PartitionHealthStateFilter::new(HealthStateFilterFlags::ALL)
+                                    .with_replica_filter(ReplicaHealthStateFilter::new(
+                                        HealthStateFilterFlags::ALL,
+                                    )),
+                            ),
+                    ),
+            ],
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert!(raw.ClusterHealthPolicy.is_null());
+        // No node filters means no nodes are queried.
+        assert!(raw.NodeFilters.is_null());
+
+        let apps = unsafe { raw.ApplicationFilters.as_ref() }.unwrap();
+        assert_eq!(apps.Count, 1);
+        let app = unsafe { apps.Items.as_ref() }.unwrap();
+        assert_eq!(
+            app.HealthStateFilter,
+            HealthStateFilterFlags::ALL.bits() as u32
+        );
+        assert!(app.ApplicationNameFilter.0.is_null());
+        assert!(app.DeployedApplicationFilters.is_null());
+        let ex1 =
+            unsafe { (app.Reserved as *const FABRIC_APPLICATION_HEALTH_STATE_FILTER_EX1).as_ref() }
+                .unwrap();
+        assert_eq!(
+            WString::from(ex1.ApplicationTypeNameFilter),
+            WString::from("MyAppType")
+        );
+
+        let svc = unsafe { (*app.ServiceFilters).Items.as_ref() }.unwrap();
+        assert_eq!(
+            svc.HealthStateFilter,
+            HealthStateFilterFlags::ERROR.bits() as u32
+        );
+        let partition = unsafe { (*svc.PartitionFilters).Items.as_ref() }.unwrap();
+        assert_eq!(partition.PartitionIdFilter, GUID::zeroed());
+        let replica = unsafe { (*partition.ReplicaFilters).Items.as_ref() }.unwrap();
+        assert_eq!(replica.ReplicaOrInstanceIdFilter, -1);
+
+        let desc = ClusterHealthChunkQueryDescription {
+            node_filters: vec![
+                NodeHealthStateFilter::new(HealthStateFilterFlags::NONE)
+                    .with_node_name("_Node_0".into()),
+            ],
+            ..Default::default()
+        };
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert!(raw.ApplicationFilters.is_null());
+        let node = unsafe { (*raw.NodeFilters).Items.as_ref() }.unwrap();
+        assert_eq!(WString::from(node.NodeNameFilter), WString::from("_Node_0"));
+    }
+
+    #[test]
+    fn test_cluster_health_chunk_from_raw() {
+        let node_name = WString::from("_Node_0");
+        let nodes = [FABRIC_NODE_HEALTH_STATE_CHUNK {
+            NodeName: node_name.as_pcwstr(),
+            HealthState: FABRIC_HEALTH_STATE_OK,
+            ..Default::default()
+        }];
+        let node_list = FABRIC_NODE_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: nodes.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let partition_id = GUID::from_u128(0x1234);
+        let partitions = [FABRIC_PARTITION_HEALTH_STATE_CHUNK {
+            PartitionId: partition_id,
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            ..Default::default()
+        }];
+        let partition_list = FABRIC_PARTITION_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: partitions.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let service_name = WString::from("fabric:/myapp/svc");
+        let services = [FABRIC_SERVICE_HEALTH_STATE_CHUNK {
+            ServiceName: FABRIC_URI(service_name.as_ptr() as *mut u16),
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            PartitionHealthStateChunks: &partition_list,
+            ..Default::default()
+        }];
+        let service_list = FABRIC_SERVICE_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: services.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let app_name = WString::from("fabric:/myapp");
+        let apps = [FABRIC_APPLICATION_HEALTH_STATE_CHUNK {
+            ApplicationName: FABRIC_URI(app_name.as_ptr() as *mut u16),
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            ServiceHealthStateChunks: &service_list,
+            ..Default::default()
+        }];
+        let app_list = FABRIC_APPLICATION_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: apps.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let raw = FABRIC_CLUSTER_HEALTH_CHUNK {
+            HealthState: FABRIC_HEALTH_STATE_WARNING,
+            NodeHealthStateChunks: &node_list,
+            ApplicationHealthStateChunks: &app_list,
+            ..Default::default()
+        };
+
+        let chunk = ClusterHealthChunk::from(&raw);
+        assert_eq!(chunk.health_state, HealthState::Warning);
+        assert_eq!(chunk.node_health_state_chunks.len(), 1);
+        assert_eq!(chunk.node_health_state_chunks[0].node_name, node_name);
+        assert_eq!(chunk.application_health_state_chunks.len(), 1);
+        let app = &chunk.application_health_state_chunks[0];
+        assert_eq!(app.application_name, Uri::from("fabric:/myapp"));
+        assert_eq!(app.application_type_name, None);
+        assert!(app.deployed_application_health_state_chunks.is_empty());
+        let svc = &app.service_health_state_chunks[0];
+        assert_eq!(svc.service_name, Uri::from("fabric:/myapp/svc"));
+        let partition = &svc.partition_health_state_chunks[0];
+        assert_eq!(partition.partition_id, partition_id);
+        assert_eq!(partition.health_state, HealthState::Error);
+        assert!(partition.replica_health_state_chunks.is_empty());
+    }
+}
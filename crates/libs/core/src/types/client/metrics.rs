@@ -7,8 +7,20 @@
 
 use std::time::SystemTime;
 
-use crate::WString;
-use mssf_com::FabricTypes::FABRIC_LOAD_METRIC_REPORT;
+use crate::{GUID, WString};
+use mssf_com::{
+    FabricClient::{
+        IFabricGetClusterLoadInformationResult, IFabricGetNodeLoadInformationResult,
+        IFabricGetReplicaLoadInformationResult,
+    },
+    FabricTypes::{
+        FABRIC_LOAD_METRIC_INFORMATION, FABRIC_LOAD_METRIC_INFORMATION_EX1,
+        FABRIC_LOAD_METRIC_REPORT, FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_NODE_LOAD_METRIC_INFORMATION, FABRIC_NODE_LOAD_METRIC_INFORMATION_EX1,
+        FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION,
+    },
+};
+use windows_core::Win32::Foundation::FILETIME;
 
 /// Wrapper for FABRIC_LOAD_METRIC_REPORT
 #[derive(Debug, Clone)]
@@ -27,3 +39,164 @@ impl From<&FABRIC_LOAD_METRIC_REPORT> for LoadMetricReport {
         }
     }
 }
+
+// FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct NodeLoadInformationQueryDescription {
+    pub node_name: WString,
+}
+
+impl From<&NodeLoadInformationQueryDescription> for FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION {
+    fn from(value: &NodeLoadInformationQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Wrapper for FABRIC_NODE_LOAD_INFORMATION
+#[derive(Debug, Clone)]
+pub struct NodeLoadInformation {
+    pub node_name: WString,
+    pub node_load_metric_information: Vec<NodeLoadMetricInformation>,
+}
+
+impl From<&IFabricGetNodeLoadInformationResult> for NodeLoadInformation {
+    fn from(com: &IFabricGetNodeLoadInformationResult) -> Self {
+        let raw = unsafe { com.get_NodeLoadInformation().as_ref().unwrap() };
+        let node_load_metric_information = unsafe { raw.NodeLoadMetricInformation.as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default();
+        Self {
+            node_name: WString::from(raw.NodeName),
+            node_load_metric_information,
+        }
+    }
+}
+
+/// Wrapper for FABRIC_NODE_LOAD_METRIC_INFORMATION
+#[derive(Debug, Clone)]
+pub struct NodeLoadMetricInformation {
+    pub name: WString,
+    pub node_capacity: i64,
+    pub node_load: i64,
+    pub node_remaining_capacity: i64,
+    pub is_capacity_violation: bool,
+    pub node_buffered_capacity: i64,
+    pub node_remaining_buffered_capacity: i64,
+}
+
+impl From<&FABRIC_NODE_LOAD_METRIC_INFORMATION> for NodeLoadMetricInformation {
+    fn from(value: &FABRIC_NODE_LOAD_METRIC_INFORMATION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_NODE_LOAD_METRIC_INFORMATION_EX1).as_ref() };
+        Self {
+            name: WString::from(value.Name),
+            node_capacity: value.NodeCapacity,
+            node_load: value.NodeLoad,
+            node_remaining_capacity: value.NodeRemainingCapacity,
+            is_capacity_violation: value.IsCapacityViolation,
+            node_buffered_capacity: ex1.map_or(0, |ex1| ex1.NodeBufferedCapacity),
+            node_remaining_buffered_capacity: ex1
+                .map_or(0, |ex1| ex1.NodeRemainingBufferedCapacity),
+        }
+    }
+}
+
+/// Wrapper for FABRIC_CLUSTER_LOAD_INFORMATION
+#[derive(Debug, Clone)]
+pub struct ClusterLoadInformation {
+    pub last_balancing_start_time_utc: FILETIME,
+    pub last_balancing_end_time_utc: FILETIME,
+    pub load_metric_information: Vec<LoadMetricInformation>,
+}
+
+impl From<&IFabricGetClusterLoadInformationResult> for ClusterLoadInformation {
+    fn from(com: &IFabricGetClusterLoadInformationResult) -> Self {
+        let raw = unsafe { com.get_ClusterLoadInformation().as_ref().unwrap() };
+        let load_metric_information = unsafe { raw.LoadMetricInformation.as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default();
+        Self {
+            last_balancing_start_time_utc: raw.LastBalancingStartTimeUtc,
+            last_balancing_end_time_utc: raw.LastBalancingEndTimeUtc,
+            load_metric_information,
+        }
+    }
+}
+
+/// Wrapper for FABRIC_LOAD_METRIC_INFORMATION
+#[derive(Debug, Clone)]
+pub struct LoadMetricInformation {
+    pub name: WString,
+    pub is_balanced_before: bool,
+    pub is_balanced_after: bool,
+    pub deviation_before: f64,
+    pub deviation_after: f64,
+    pub balancing_threshold: f64,
+    pub action: WString,
+    pub activity_threshold: u32,
+    pub cluster_capacity: i64,
+    pub cluster_load: i64,
+    // TODO: implement EX2
+}
+
+impl From<&FABRIC_LOAD_METRIC_INFORMATION> for LoadMetricInformation {
+    fn from(value: &FABRIC_LOAD_METRIC_INFORMATION) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_LOAD_METRIC_INFORMATION_EX1).as_ref() };
+        Self {
+            name: WString::from(value.Name),
+            is_balanced_before: value.IsBalancedBefore,
+            is_balanced_after: value.IsBalancedAfter,
+            deviation_before: value.DeviationBefore,
+            deviation_after: value.DeviationAfter,
+            balancing_threshold: value.BalancingThreshold,
+            action: WString::from(value.Action),
+            activity_threshold: ex1.map_or(0, |ex1| ex1.ActivityThreshold),
+            cluster_capacity: ex1.map_or(0, |ex1| ex1.ClusterCapacity),
+            cluster_load: ex1.map_or(0, |ex1| ex1.ClusterLoad),
+        }
+    }
+}
+
+// FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ReplicaLoadInformationQueryDescription {
+    pub partition_id: GUID,
+    pub replica_or_instance_id: i64,
+}
+
+impl From<&ReplicaLoadInformationQueryDescription>
+    for FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION
+{
+    fn from(value: &ReplicaLoadInformationQueryDescription) -> Self {
+        Self {
+            PartitionId: value.partition_id,
+            ReplicaOrInstanceId: value.replica_or_instance_id,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Wrapper for FABRIC_REPLICA_LOAD_INFORMATION
+#[derive(Debug, Clone)]
+pub struct ReplicaLoadInformation {
+    pub partition_id: GUID,
+    pub replica_or_instance_id: i64,
+    pub load_metric_reports: Vec<LoadMetricReport>,
+}
+
+impl From<&IFabricGetReplicaLoadInformationResult> for ReplicaLoadInformation {
+    fn from(com: &IFabricGetReplicaLoadInformationResult) -> Self {
+        let raw = unsafe { com.get_ReplicaLoadInformation().as_ref().unwrap() };
+        let load_metric_reports = unsafe { raw.LoadMetricReports.as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default();
+        Self {
+            partition_id: raw.PartitionId,
+            replica_or_instance_id: raw.ReplicaOrInstanceId,
+            load_metric_reports,
+        }
+    }
+}
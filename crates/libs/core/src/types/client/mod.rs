@@ -3,7 +3,7 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::types::Uri;
+use crate::{mem::BoxPool, types::Uri};
 use mssf_com::FabricTypes::{
     FABRIC_CLIENT_ROLE, FABRIC_CLIENT_ROLE_ADMIN, FABRIC_CLIENT_ROLE_UNKNOWN,
     FABRIC_CLIENT_ROLE_USER, FABRIC_QUERY_REPLICATOR_OPERATION_NAME,
@@ -18,8 +18,9 @@ use mssf_com::FabricTypes::{
     FABRIC_QUERY_SERVICE_OPERATION_NAME_OPEN, FABRIC_SERVICE_NOTIFICATION_FILTER_DESCRIPTION,
     FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS, FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS_NAME_PREFIX,
     FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS_NONE,
-    FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS_PRIMARY_ONLY,
+    FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS_PRIMARY_ONLY, FABRIC_STRING_LIST,
 };
+use windows_core::WString;
 
 // This mod contains fabric client related types
 mod partition;
@@ -32,30 +33,110 @@ mod metrics;
 pub use metrics::*;
 mod health;
 pub use health::*;
+mod health_chunk;
+pub use health_chunk::{
+    ApplicationHealthStateChunk, ApplicationHealthStateFilter, ClusterHealthChunk,
+    ClusterHealthChunkQueryDescription, DeployedApplicationHealthStateChunk,
+    DeployedApplicationHealthStateFilter, DeployedServicePackageHealthStateChunk,
+    DeployedServicePackageHealthStateFilter, NodeHealthStateChunk, NodeHealthStateFilter,
+    PartitionHealthStateChunk, PartitionHealthStateFilter, ReplicaHealthStateChunk,
+    ReplicaHealthStateFilter, ServiceHealthStateChunk, ServiceHealthStateFilter,
+};
 mod settings;
 pub use settings::*;
 mod property;
 pub use property::{
-    EnumerationStatus, NameEnumerationResult, PropertyMetadataResult, PropertyTypeId,
+    EnumerationStatus, NameEnumerationResult, PropertyBatchOperation, PropertyBatchResult,
+    PropertyEnumerationResult, PropertyMetadataResult, PropertyTypeId, PropertyValue,
     PropertyValueResult,
 };
 
 mod service;
 pub use service::{
     DeleteServiceDescription, NamedRepartitionDescription, ServiceDescription,
-    ServiceHealthQueryDescription, ServiceHealthResult, ServiceHealthState,
-    ServiceHealthStatesFilter, ServiceListResult, ServiceQueryDescription, ServiceQueryResultItem,
-    ServiceRepartitionDescription, ServiceUpdateDescription, StatefulServiceDescription,
-    StatefulServiceUpdateDescription, StatelessServiceDescription,
-    StatelessServiceUpdateDescription,
+    ServiceFromTemplateDescription, ServiceHealthQueryDescription, ServiceHealthResult,
+    ServiceHealthState, ServiceHealthStatesFilter, ServiceListResult, ServiceQueryDescription,
+    ServiceQueryResultItem, ServiceRepartitionDescription, ServiceTypeDescription,
+    ServiceTypeListResult, ServiceTypeQueryDescription, ServiceTypeQueryResultItem,
+    ServiceUpdateDescription, StatefulServiceDescription, StatefulServiceTypeDescription,
+    StatefulServiceUpdateDescription, StatefulServiceUpdateDescriptionFlags,
+    StatelessServiceDescription, StatelessServiceTypeDescription,
+    StatelessServiceUpdateDescription, StatelessServiceUpdateDescriptionFlags,
+};
+
+mod service_group;
+pub use service_group::{
+    ServiceGroupDescription, ServiceGroupDescriptionResult, ServiceGroupFromTemplateDescription,
+    ServiceGroupMemberDescription, ServiceGroupUpdateDescription,
 };
 
 mod application;
 pub use application::{
-    ApplicationDefinitionKind, ApplicationDefinitionKindFilter, ApplicationHealth,
-    ApplicationHealthQueryDescription, ApplicationListResult, ApplicationQueryDescription,
-    ApplicationQueryResultItem, ApplicationStatus, DeployedApplicationHealthState,
-    DeployedApplicationHealthStatesFilter,
+    ApplicationCapacityDescription, ApplicationDefinitionKind, ApplicationDefinitionKindFilter,
+    ApplicationDescription, ApplicationHealth, ApplicationHealthQueryDescription,
+    ApplicationListResult, ApplicationMetricDescription, ApplicationPackageCleanupPolicy,
+    ApplicationQueryDescription, ApplicationQueryResultItem, ApplicationStatus,
+    ApplicationTypeListResult, ApplicationTypeQueryDescription, ApplicationTypeQueryResultItem,
+    ApplicationTypeStatus, ApplicationUpdateDescription, ApplicationUpgradeDescription,
+    ApplicationUpgradePolicyDescription, ApplicationUpgradeProgress, ApplicationUpgradeState,
+    DeleteApplicationDescription, DeployedApplicationHealthState,
+    DeployedApplicationHealthStatesFilter, ProvisionApplicationTypeDescription,
+    UnprovisionApplicationTypeDescription,
+};
+mod upgrade;
+pub use upgrade::*;
+mod cluster;
+pub use cluster::{
+    ClusterUpgradeHealthPolicy, FabricUpgradeDescription, FabricUpgradePolicyDescription,
+    FabricUpgradeProgress, FabricUpgradeState, ProvisionedFabricCodeVersionList,
+    ProvisionedFabricCodeVersionQueryDescription, ProvisionedFabricConfigVersionList,
+    ProvisionedFabricConfigVersionQueryDescription,
+};
+mod deployed;
+pub use deployed::{
+    CodePackageEntryPoint, DeployedApplicationHealth, DeployedApplicationHealthQueryDescription,
+    DeployedApplicationListResult, DeployedApplicationQueryDescription,
+    DeployedApplicationQueryResultItem, DeployedCodePackageListResult,
+    DeployedCodePackageQueryDescription, DeployedCodePackageQueryResultItem,
+    DeployedServicePackageHealth, DeployedServicePackageHealthQueryDescription,
+    DeployedServicePackageHealthState, DeployedServicePackageHealthStatesFilter,
+    DeployedServicePackageListResult, DeployedServicePackageQueryDescription,
+    DeployedServicePackageQueryResultItem, DeployedServiceReplicaListResult,
+    DeployedServiceReplicaQueryDescription, DeployedServiceReplicaQueryResultItem,
+    DeployedStatefulServiceReplicaQueryResult, DeploymentStatus, EntryPointStatus,
+    HostIsolationMode, HostType,
+};
+mod fault;
+pub use fault::{
+    CancelTestCommandDescription, DataLossMode, DeployedCodePackageResult, MovePrimaryDescription,
+    MovePrimaryResult, MoveSecondaryDescription, MoveSecondaryResult, NodeResult,
+    NodeTransitionDescription, NodeTransitionProgress, NodeTransitionResult,
+    PartitionDataLossProgress, PartitionQuorumLossProgress, PartitionRestartProgress,
+    PartitionSelector, PartitionTestCommandResult, QuorumLossMode,
+    RestartDeployedCodePackageDescription, RestartNodeDescription, RestartPartitionMode,
+    SelectedPartition, StartNodeDescription, StartPartitionDataLossDescription,
+    StartPartitionQuorumLossDescription, StartPartitionRestartDescription, StopNodeDescription,
+    TestCommandProgress, TestCommandProgressState,
+};
+mod chaos;
+pub use chaos::{
+    ChaosDescription, ChaosEvent, ChaosParameters, ChaosReport, ChaosSchedule,
+    ChaosScheduleDescription, ChaosScheduleJob, ChaosScheduleJobActiveDays, ChaosScheduleStatus,
+    ChaosScheduleTimeRangeUtc, ChaosScheduleTimeUtc, ChaosStatus, ChaosTargetFilter,
+    GetChaosReportDescription, StartChaosDescription,
+};
+mod secret;
+pub use secret::{
+    ManagedIdentityTokenServiceDescription, Secret, SecretList, SecretReference,
+    SecretReferenceList,
+};
+mod repair;
+pub use repair::{
+    RepairApproveDescription, RepairCancelDescription, RepairDeleteDescription,
+    RepairExecutorState, RepairImpact, RepairNodeImpact, RepairNodeImpactLevel,
+    RepairResultDescription, RepairScope, RepairTarget, RepairTask, RepairTaskFlags,
+    RepairTaskHealthCheckState, RepairTaskHealthPolicyUpdateDescription, RepairTaskHistory,
+    RepairTaskQueryDescription, RepairTaskResult, RepairTaskState, RepairTaskStateFilter,
 };
 
 // FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS
@@ -181,3 +262,27 @@ impl From<FABRIC_QUERY_REPLICATOR_OPERATION_NAME> for QueryReplicatorOperationNa
         }
     }
 }
+
+/// Builds FABRIC_STRING_LIST.
+fn string_list_raw(items: &[WString], pool: &mut BoxPool) -> *const FABRIC_STRING_LIST {
+    let items = items.iter().map(|s| s.as_pcwstr()).collect::<Vec<_>>();
+    let (count, items) = pool.push_vec(items);
+    pool.push(Box::new(FABRIC_STRING_LIST {
+        Count: count as u32,
+        Items: items,
+    }))
+}
+
+/// Reads FABRIC_STRING_LIST. Null list is empty.
+fn string_list_from_raw(list: *const FABRIC_STRING_LIST) -> Vec<WString> {
+    let Some(list) = (unsafe { list.as_ref() }) else {
+        return vec![];
+    };
+    if list.Count == 0 || list.Items.is_null() {
+        return vec![];
+    }
+    unsafe { std::slice::from_raw_parts(list.Items, list.Count as usize) }
+        .iter()
+        .map(|s| WString::from(*s))
+        .collect()
+}
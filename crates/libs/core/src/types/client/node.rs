@@ -9,6 +9,9 @@ use bitflags::bitflags;
 use mssf_com::{
     FabricClient::IFabricGetNodeListResult2,
     FabricTypes::{
+        FABRIC_NODE_DEACTIVATION_INTENT, FABRIC_NODE_DEACTIVATION_INTENT_INVALID,
+        FABRIC_NODE_DEACTIVATION_INTENT_PAUSE, FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA,
+        FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE, FABRIC_NODE_DEACTIVATION_INTENT_RESTART,
         FABRIC_NODE_ID, FABRIC_NODE_QUERY_DESCRIPTION, FABRIC_NODE_QUERY_DESCRIPTION_EX1,
         FABRIC_NODE_QUERY_DESCRIPTION_EX2, FABRIC_NODE_QUERY_DESCRIPTION_EX3,
         FABRIC_NODE_QUERY_RESULT_ITEM, FABRIC_NODE_QUERY_RESULT_ITEM_EX1,
@@ -35,14 +38,14 @@ impl From<&FABRIC_PAGING_STATUS> for PagingStatus {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct PagedQueryDescription {
     pub continuation_token: Option<WString>,
     pub max_results: Option<i32>,
 }
 
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     pub struct NodeStatusFilter: u32{
         const All = FABRIC_QUERY_NODE_STATUS_FILTER_ALL.0 as u32;
         const Default = FABRIC_QUERY_NODE_STATUS_FILTER_DEFAULT.0 as u32;
@@ -62,7 +65,7 @@ impl Default for NodeStatusFilter {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct NodeQueryDescription {
     pub node_name_filter: Option<WString>,
     pub node_status_filter: NodeStatusFilter,
@@ -169,3 +172,43 @@ impl From<FABRIC_NODE_ID> for NodeId {
         }
     }
 }
+
+// FABRIC_NODE_DEACTIVATION_INTENT
+/// Why a node is deactivated. Determines which replicas are moved off the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeDeactivationIntent {
+    Invalid,
+    /// Stops placing new replicas and pauses the node. Replicas are kept.
+    Pause,
+    /// The node will restart. In-memory replicas are moved off and
+    /// availability is kept for stateful services.
+    Restart,
+    /// The data on the node will be lost. All replicas are moved off.
+    RemoveData,
+    /// The node is removed from the cluster.
+    RemoveNode,
+}
+
+impl From<NodeDeactivationIntent> for FABRIC_NODE_DEACTIVATION_INTENT {
+    fn from(value: NodeDeactivationIntent) -> Self {
+        match value {
+            NodeDeactivationIntent::Invalid => FABRIC_NODE_DEACTIVATION_INTENT_INVALID,
+            NodeDeactivationIntent::Pause => FABRIC_NODE_DEACTIVATION_INTENT_PAUSE,
+            NodeDeactivationIntent::Restart => FABRIC_NODE_DEACTIVATION_INTENT_RESTART,
+            NodeDeactivationIntent::RemoveData => FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA,
+            NodeDeactivationIntent::RemoveNode => FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE,
+        }
+    }
+}
+
+impl From<FABRIC_NODE_DEACTIVATION_INTENT> for NodeDeactivationIntent {
+    fn from(value: FABRIC_NODE_DEACTIVATION_INTENT) -> Self {
+        match value {
+            FABRIC_NODE_DEACTIVATION_INTENT_PAUSE => Self::Pause,
+            FABRIC_NODE_DEACTIVATION_INTENT_RESTART => Self::Restart,
+            FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA => Self::RemoveData,
+            FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE => Self::RemoveNode,
+            _ => Self::Invalid,
+        }
+    }
+}
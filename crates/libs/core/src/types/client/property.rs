@@ -3,20 +3,36 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
+use std::ffi::c_void;
+
 use mssf_com::{
-    FabricClient::IFabricNameEnumerationResult,
+    FabricClient::{
+        IFabricNameEnumerationResult, IFabricPropertyBatchResult, IFabricPropertyEnumerationResult,
+    },
     FabricTypes::{
+        FABRIC_CHECK_EXISTS_PROPERTY_OPERATION, FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION,
+        FABRIC_CHECK_VALUE_PROPERTY_OPERATION, FABRIC_DELETE_PROPERTY_OPERATION,
         FABRIC_ENUMERATION_BEST_EFFORT_FINISHED, FABRIC_ENUMERATION_BEST_EFFORT_MASK,
         FABRIC_ENUMERATION_BEST_EFFORT_MORE_DATA, FABRIC_ENUMERATION_CONSISTENT_FINISHED,
         FABRIC_ENUMERATION_CONSISTENT_MASK, FABRIC_ENUMERATION_CONSISTENT_MORE_DATA,
         FABRIC_ENUMERATION_FINISHED_MASK, FABRIC_ENUMERATION_INVALID,
         FABRIC_ENUMERATION_MORE_DATA_MASK, FABRIC_ENUMERATION_STATUS,
-        FABRIC_NAMED_PROPERTY_METADATA,
+        FABRIC_GET_PROPERTY_OPERATION, FABRIC_NAMED_PROPERTY_METADATA,
+        FABRIC_OPERATION_DATA_BUFFER, FABRIC_PROPERTY_BATCH_OPERATION,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_EXISTS,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_VALUE,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_DELETE, FABRIC_PROPERTY_BATCH_OPERATION_KIND_GET,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT, FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT_CUSTOM,
+        FABRIC_PUT_CUSTOM_PROPERTY_OPERATION, FABRIC_PUT_PROPERTY_OPERATION,
     },
 };
 use windows_core::WString;
 
-use crate::types::Uri;
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    types::Uri,
+};
 
 pub struct NameEnumerationResult {
     com: IFabricNameEnumerationResult,
@@ -74,6 +90,56 @@ impl From<FABRIC_ENUMERATION_STATUS> for EnumerationStatus {
     }
 }
 
+impl EnumerationStatus {
+    /// Whether the enumeration has more pages to fetch.
+    pub fn has_more_data(&self) -> bool {
+        matches!(
+            self,
+            EnumerationStatus::BestEffortMoreData | EnumerationStatus::ConsistentMoreData
+        )
+    }
+}
+
+/// A page of properties under a SF name.
+/// Pass it to the next enumeration call to continue.
+#[derive(Clone)]
+pub struct PropertyEnumerationResult {
+    com: IFabricPropertyEnumerationResult,
+}
+
+impl PropertyEnumerationResult {
+    pub(crate) fn from_com(com: IFabricPropertyEnumerationResult) -> Self {
+        Self { com }
+    }
+
+    pub(crate) fn as_com(&self) -> &IFabricPropertyEnumerationResult {
+        &self.com
+    }
+
+    pub fn get_enumeration_status(&self) -> EnumerationStatus {
+        unsafe { self.com.get_EnumerationStatus().into() }
+    }
+
+    pub fn get_property_count(&self) -> u32 {
+        unsafe { self.com.get_PropertyCount() }
+    }
+
+    /// Gets the property at index in this page.
+    /// The value is only available if the enumeration included values.
+    pub fn get_property(&self, index: u32) -> crate::Result<PropertyValueResult> {
+        unsafe { self.com.GetProperty(index) }
+            .map(PropertyValueResult::from_com)
+            .map_err(crate::Error::from)
+    }
+
+    /// Returns all properties in this page.
+    pub fn get_properties(&self) -> crate::Result<Vec<PropertyValueResult>> {
+        (0..self.get_property_count())
+            .map(|i| self.get_property(i))
+            .collect()
+    }
+}
+
 /// Metadata for a named property.
 pub struct NamedPropertyMetadata {
     pub property_name: WString,
@@ -144,20 +210,217 @@ impl From<PropertyTypeId> for mssf_com::FabricTypes::FABRIC_PROPERTY_TYPE_ID {
     }
 }
 
-// TODO: batch operations is net yet supported.
-// // FABRIC_PROPERTY_BATCH_OPERATION
-// pub enum PropertyBatchOperation {
-//     Invalid,
-//     Put{name: WString, type_id: PropertyTypeId, value: WString},
-//     Get,
-//     CheckExists,
-//     CheckSequence,
-//     Delete,
-//     PutCustom,
-//     CheckValue,
-// }
+/// Typed value of a property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Binary(Vec<u8>),
+    Int64(i64),
+    Double(f64),
+    WString(WString),
+    Guid(windows_core::GUID),
+}
 
-// FABRIC_CHECK_EXISTS_PROPERTY_OPERATION
+impl PropertyValue {
+    pub fn type_id(&self) -> PropertyTypeId {
+        match self {
+            PropertyValue::Binary(_) => PropertyTypeId::Binary,
+            PropertyValue::Int64(_) => PropertyTypeId::Int64,
+            PropertyValue::Double(_) => PropertyTypeId::Double,
+            PropertyValue::WString(_) => PropertyTypeId::WString,
+            PropertyValue::Guid(_) => PropertyTypeId::Guid,
+        }
+    }
+
+    // SF reads the value pointer according to the type id:
+    // binary is a FABRIC_OPERATION_DATA_BUFFER, wstring is the string itself,
+    // and the rest point to the value.
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> *mut c_void {
+        match self {
+            PropertyValue::Binary(data) => pool.push(Box::new(FABRIC_OPERATION_DATA_BUFFER {
+                BufferSize: data.len() as u32,
+                Buffer: data.as_ptr() as *mut u8,
+            })) as *mut c_void,
+            PropertyValue::Int64(v) => v as *const i64 as *mut c_void,
+            PropertyValue::Double(v) => v as *const f64 as *mut c_void,
+            PropertyValue::WString(v) => v.as_ptr() as *mut c_void,
+            PropertyValue::Guid(v) => v as *const windows_core::GUID as *mut c_void,
+        }
+    }
+}
+
+/// An operation in a property batch.
+/// The batch is executed atomically: if any operation fails, none of the
+/// write operations are applied.
+/// FABRIC_PROPERTY_BATCH_OPERATION
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyBatchOperation {
+    /// Checks the property exists or not, depending on `exists`.
+    CheckExists {
+        property_name: WString,
+        exists: bool,
+    },
+    /// Checks the property sequence number matches.
+    CheckSequence {
+        property_name: WString,
+        sequence_number: i64,
+    },
+    /// Checks the property value matches.
+    CheckValue {
+        property_name: WString,
+        value: PropertyValue,
+    },
+    Put {
+        property_name: WString,
+        value: PropertyValue,
+    },
+    /// Puts a property with a user defined type tag.
+    PutCustom {
+        property_name: WString,
+        value: PropertyValue,
+        custom_type_id: WString,
+    },
+    /// Gets the property. The result can be read by the operation index
+    /// from the batch result.
+    Get {
+        property_name: WString,
+        include_value: bool,
+    },
+    Delete {
+        property_name: WString,
+    },
+}
+
+impl GetRawWithBoxPool<FABRIC_PROPERTY_BATCH_OPERATION> for PropertyBatchOperation {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_PROPERTY_BATCH_OPERATION {
+        let (kind, value) = match self {
+            PropertyBatchOperation::CheckExists {
+                property_name,
+                exists,
+            } => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_EXISTS,
+                pool.push(Box::new(FABRIC_CHECK_EXISTS_PROPERTY_OPERATION {
+                    PropertyName: property_name.as_pcwstr(),
+                    ExistenceCheck: *exists,
+                    Reserved: std::ptr::null_mut(),
+                })) as *mut c_void,
+            ),
+            PropertyBatchOperation::CheckSequence {
+                property_name,
+                sequence_number,
+            } => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE,
+                pool.push(Box::new(FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION {
+                    PropertyName: property_name.as_pcwstr(),
+                    SequenceNumber: *sequence_number,
+                    Reserved: std::ptr::null_mut(),
+                })) as *mut c_void,
+            ),
+            PropertyBatchOperation::CheckValue {
+                property_name,
+                value,
+            } => {
+                let raw_value = value.get_raw_with_pool(pool);
+                (
+                    FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_VALUE,
+                    pool.push(Box::new(FABRIC_CHECK_VALUE_PROPERTY_OPERATION {
+                        PropertyName: property_name.as_pcwstr(),
+                        PropertyTypeId: value.type_id().into(),
+                        PropertyValue: raw_value,
+                        Reserved: std::ptr::null_mut(),
+                    })) as *mut c_void,
+                )
+            }
+            PropertyBatchOperation::Put {
+                property_name,
+                value,
+            } => {
+                let raw_value = value.get_raw_with_pool(pool);
+                (
+                    FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT,
+                    pool.push(Box::new(FABRIC_PUT_PROPERTY_OPERATION {
+                        PropertyName: property_name.as_pcwstr(),
+                        PropertyTypeId: value.type_id().into(),
+                        PropertyValue: raw_value,
+                        Reserved: std::ptr::null_mut(),
+                    })) as *mut c_void,
+                )
+            }
+            PropertyBatchOperation::PutCustom {
+                property_name,
+                value,
+                custom_type_id,
+            } => {
+                let raw_value = value.get_raw_with_pool(pool);
+                (
+                    FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT_CUSTOM,
+                    pool.push(Box::new(FABRIC_PUT_CUSTOM_PROPERTY_OPERATION {
+                        PropertyName: property_name.as_pcwstr(),
+                        PropertyTypeId: value.type_id().into(),
+                        PropertyValue: raw_value,
+                        PropertyCustomTypeId: custom_type_id.as_pcwstr(),
+                        Reserved: std::ptr::null_mut(),
+                    })) as *mut c_void,
+                )
+            }
+            PropertyBatchOperation::Get {
+                property_name,
+                include_value,
+            } => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_GET,
+                pool.push(Box::new(FABRIC_GET_PROPERTY_OPERATION {
+                    PropertyName: property_name.as_pcwstr(),
+                    IncludeValue: *include_value,
+                    Reserved: std::ptr::null_mut(),
+                })) as *mut c_void,
+            ),
+            PropertyBatchOperation::Delete { property_name } => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_DELETE,
+                pool.push(Box::new(FABRIC_DELETE_PROPERTY_OPERATION {
+                    PropertyName: property_name.as_pcwstr(),
+                    Reserved: std::ptr::null_mut(),
+                })) as *mut c_void,
+            ),
+        };
+        FABRIC_PROPERTY_BATCH_OPERATION {
+            Kind: kind,
+            Value: value,
+        }
+    }
+}
+
+/// Result of a submitted property batch.
+pub struct PropertyBatchResult {
+    failed_operation_index: Option<u32>,
+    com: IFabricPropertyBatchResult,
+}
+
+impl PropertyBatchResult {
+    pub(crate) fn from_com(failed_operation_index: u32, com: IFabricPropertyBatchResult) -> Self {
+        // SF reports -1 when all operations succeeded.
+        let failed_operation_index = if failed_operation_index == u32::MAX {
+            None
+        } else {
+            Some(failed_operation_index)
+        };
+        Self {
+            failed_operation_index,
+            com,
+        }
+    }
+
+    /// Index of the operation in the request that failed,
+    /// or None if the batch succeeded.
+    pub fn failed_operation_index(&self) -> Option<u32> {
+        self.failed_operation_index
+    }
+
+    /// Gets the property returned by the Get operation at operation_index in the request.
+    pub fn get_property(&self, operation_index: u32) -> crate::Result<PropertyValueResult> {
+        unsafe { self.com.GetProperty(operation_index) }
+            .map(PropertyValueResult::from_com)
+            .map_err(crate::Error::from)
+    }
+}
 
 /// Represents the result of a property value operation.
 pub struct PropertyValueResult {
@@ -209,3 +472,33 @@ impl PropertyValueResult {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_OPERATION_DATA_BUFFER, FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT,
+        FABRIC_PROPERTY_TYPE_BINARY, FABRIC_PUT_PROPERTY_OPERATION,
+    };
+    use windows_core::WString;
+
+    use super::{PropertyBatchOperation, PropertyValue};
+    use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+    #[test]
+    fn test_batch_operation_raw() {
+        let op = PropertyBatchOperation::Put {
+            property_name: WString::from("prop"),
+            value: PropertyValue::Binary(vec![1, 2, 3]),
+        };
+        let mut pool = BoxPool::new();
+        let raw = op.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.Kind, FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT);
+        let put = unsafe { (raw.Value as *const FABRIC_PUT_PROPERTY_OPERATION).as_ref() }.unwrap();
+        assert_eq!(WString::from(put.PropertyName), WString::from("prop"));
+        assert_eq!(put.PropertyTypeId, FABRIC_PROPERTY_TYPE_BINARY);
+        let buffer =
+            unsafe { (put.PropertyValue as *const FABRIC_OPERATION_DATA_BUFFER).as_ref() }.unwrap();
+        let data = unsafe { std::slice::from_raw_parts(buffer.Buffer, buffer.BufferSize as usize) };
+        assert_eq!(data, &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,788 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Repair task types.
+
+use std::ffi::c_void;
+
+use mssf_com::FabricTypes::{
+    FABRIC_REPAIR_APPROVE_DESCRIPTION, FABRIC_REPAIR_CANCEL_DESCRIPTION,
+    FABRIC_REPAIR_DELETE_DESCRIPTION, FABRIC_REPAIR_EXECUTOR_STATE,
+    FABRIC_REPAIR_IMPACT_DESCRIPTION, FABRIC_REPAIR_IMPACT_KIND_INVALID,
+    FABRIC_REPAIR_IMPACT_KIND_NODE, FABRIC_REPAIR_NODE_IMPACT, FABRIC_REPAIR_NODE_IMPACT_LEVEL,
+    FABRIC_REPAIR_NODE_IMPACT_LEVEL_INVALID, FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE,
+    FABRIC_REPAIR_NODE_IMPACT_LEVEL_PAUSE, FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA,
+    FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE, FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART,
+    FABRIC_REPAIR_NODE_IMPACT_LIST, FABRIC_REPAIR_RESULT_DESCRIPTION,
+    FABRIC_REPAIR_SCOPE_IDENTIFIER, FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER,
+    FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_INVALID, FABRIC_REPAIR_TARGET_DESCRIPTION,
+    FABRIC_REPAIR_TARGET_KIND_INVALID, FABRIC_REPAIR_TARGET_KIND_NODE, FABRIC_REPAIR_TASK,
+    FABRIC_REPAIR_TASK_EX1, FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE,
+    FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_IN_PROGRESS,
+    FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_NOT_STARTED,
+    FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SKIPPED, FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SUCCEEDED,
+    FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_TIMEDOUT,
+    FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION,
+    FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_PREPARING_HEALTH_CHECK,
+    FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK,
+    FABRIC_REPAIR_TASK_HISTORY, FABRIC_REPAIR_TASK_HISTORY_EX1,
+    FABRIC_REPAIR_TASK_QUERY_DESCRIPTION, FABRIC_REPAIR_TASK_RESULT,
+    FABRIC_REPAIR_TASK_RESULT_CANCELLED, FABRIC_REPAIR_TASK_RESULT_FAILED,
+    FABRIC_REPAIR_TASK_RESULT_INTERRUPTED, FABRIC_REPAIR_TASK_RESULT_INVALID,
+    FABRIC_REPAIR_TASK_RESULT_PENDING, FABRIC_REPAIR_TASK_RESULT_SUCCEEDED,
+    FABRIC_REPAIR_TASK_STATE, FABRIC_REPAIR_TASK_STATE_APPROVED, FABRIC_REPAIR_TASK_STATE_CLAIMED,
+    FABRIC_REPAIR_TASK_STATE_COMPLETED, FABRIC_REPAIR_TASK_STATE_CREATED,
+    FABRIC_REPAIR_TASK_STATE_EXECUTING, FABRIC_REPAIR_TASK_STATE_INVALID,
+    FABRIC_REPAIR_TASK_STATE_PREPARING, FABRIC_REPAIR_TASK_STATE_RESTORING,
+};
+use windows_core::{HRESULT, PCWSTR, WString, Win32::Foundation::FILETIME};
+
+use super::{string_list_from_raw, string_list_raw};
+use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+// FABRIC_REPAIR_SCOPE_IDENTIFIER
+/// Scope of the repair task. Only the cluster scope is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairScope {
+    Invalid,
+    #[default]
+    Cluster,
+}
+
+impl From<&FABRIC_REPAIR_SCOPE_IDENTIFIER> for RepairScope {
+    fn from(value: &FABRIC_REPAIR_SCOPE_IDENTIFIER) -> Self {
+        match value.Kind {
+            FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER => Self::Cluster,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl RepairScope {
+    fn push_raw(&self, pool: &mut BoxPool) -> *mut FABRIC_REPAIR_SCOPE_IDENTIFIER {
+        let kind = match self {
+            RepairScope::Invalid => FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_INVALID,
+            RepairScope::Cluster => FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER,
+        };
+        pool.push(Box::new(FABRIC_REPAIR_SCOPE_IDENTIFIER {
+            Kind: kind,
+            Value: std::ptr::null_mut(),
+        })) as *mut _
+    }
+}
+
+// FABRIC_REPAIR_TASK_STATE
+/// A repair task moves through the states in order:
+/// Created -> Claimed -> Preparing -> Approved -> Executing -> Restoring -> Completed.
+/// A task can be completed early from any state when cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairTaskState {
+    #[default]
+    Invalid,
+    Created,
+    Claimed,
+    Preparing,
+    Approved,
+    Executing,
+    Restoring,
+    Completed,
+}
+
+impl From<FABRIC_REPAIR_TASK_STATE> for RepairTaskState {
+    fn from(value: FABRIC_REPAIR_TASK_STATE) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_STATE_CREATED => Self::Created,
+            FABRIC_REPAIR_TASK_STATE_CLAIMED => Self::Claimed,
+            FABRIC_REPAIR_TASK_STATE_PREPARING => Self::Preparing,
+            FABRIC_REPAIR_TASK_STATE_APPROVED => Self::Approved,
+            FABRIC_REPAIR_TASK_STATE_EXECUTING => Self::Executing,
+            FABRIC_REPAIR_TASK_STATE_RESTORING => Self::Restoring,
+            FABRIC_REPAIR_TASK_STATE_COMPLETED => Self::Completed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairTaskState> for FABRIC_REPAIR_TASK_STATE {
+    fn from(value: RepairTaskState) -> Self {
+        match value {
+            RepairTaskState::Invalid => FABRIC_REPAIR_TASK_STATE_INVALID,
+            RepairTaskState::Created => FABRIC_REPAIR_TASK_STATE_CREATED,
+            RepairTaskState::Claimed => FABRIC_REPAIR_TASK_STATE_CLAIMED,
+            RepairTaskState::Preparing => FABRIC_REPAIR_TASK_STATE_PREPARING,
+            RepairTaskState::Approved => FABRIC_REPAIR_TASK_STATE_APPROVED,
+            RepairTaskState::Executing => FABRIC_REPAIR_TASK_STATE_EXECUTING,
+            RepairTaskState::Restoring => FABRIC_REPAIR_TASK_STATE_RESTORING,
+            RepairTaskState::Completed => FABRIC_REPAIR_TASK_STATE_COMPLETED,
+        }
+    }
+}
+
+impl RepairTaskState {
+    /// Returns the state the task moves to next, None for Completed and Invalid.
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::Created => Some(Self::Claimed),
+            Self::Claimed => Some(Self::Preparing),
+            Self::Preparing => Some(Self::Approved),
+            Self::Approved => Some(Self::Executing),
+            Self::Executing => Some(Self::Restoring),
+            Self::Restoring => Some(Self::Completed),
+            Self::Completed | Self::Invalid => None,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// FABRIC_REPAIR_TASK_STATE_FILTER
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct RepairTaskStateFilter: u32 {
+        const Created = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_CREATED.0 as u32;
+        const Claimed = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_CLAIMED.0 as u32;
+        const Preparing = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_PREPARING.0 as u32;
+        const Approved = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_APPROVED.0 as u32;
+        const Executing = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_EXECUTING.0 as u32;
+        const Restoring = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_RESTORING.0 as u32;
+        const Completed = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_COMPLETED.0 as u32;
+        const ReadyToExecute = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_READY_TO_EXECUTE.0 as u32;
+        const Active = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_ACTIVE.0 as u32;
+        const All = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_STATE_FILTER_ALL.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    /// FABRIC_REPAIR_TASK_FLAGS
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct RepairTaskFlags: u32 {
+        const CancelRequested = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_FLAGS_CANCEL_REQUESTED.0 as u32;
+        const AbortRequested = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_FLAGS_ABORT_REQUESTED.0 as u32;
+        const ForcedApproval = mssf_com::FabricTypes::FABRIC_REPAIR_TASK_FLAGS_FORCED_APPROVAL.0 as u32;
+    }
+}
+
+// FABRIC_REPAIR_TARGET_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairTarget {
+    Invalid,
+    /// Names of the nodes to repair.
+    Nodes(Vec<WString>),
+}
+
+impl From<&FABRIC_REPAIR_TARGET_DESCRIPTION> for RepairTarget {
+    fn from(value: &FABRIC_REPAIR_TARGET_DESCRIPTION) -> Self {
+        match value.Kind {
+            FABRIC_REPAIR_TARGET_KIND_NODE => {
+                Self::Nodes(string_list_from_raw(value.Value as *const _))
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_TARGET_DESCRIPTION> for RepairTarget {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_TARGET_DESCRIPTION {
+        match self {
+            RepairTarget::Invalid => FABRIC_REPAIR_TARGET_DESCRIPTION {
+                Kind: FABRIC_REPAIR_TARGET_KIND_INVALID,
+                Value: std::ptr::null_mut(),
+            },
+            RepairTarget::Nodes(nodes) => FABRIC_REPAIR_TARGET_DESCRIPTION {
+                Kind: FABRIC_REPAIR_TARGET_KIND_NODE,
+                Value: string_list_raw(nodes, pool) as *mut c_void,
+            },
+        }
+    }
+}
+
+// FABRIC_REPAIR_NODE_IMPACT_LEVEL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairNodeImpactLevel {
+    Invalid,
+    None,
+    Restart,
+    RemoveData,
+    RemoveNode,
+    Pause,
+}
+
+impl From<FABRIC_REPAIR_NODE_IMPACT_LEVEL> for RepairNodeImpactLevel {
+    fn from(value: FABRIC_REPAIR_NODE_IMPACT_LEVEL) -> Self {
+        match value {
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE => Self::None,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART => Self::Restart,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA => Self::RemoveData,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE => Self::RemoveNode,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_PAUSE => Self::Pause,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairNodeImpactLevel> for FABRIC_REPAIR_NODE_IMPACT_LEVEL {
+    fn from(value: RepairNodeImpactLevel) -> Self {
+        match value {
+            RepairNodeImpactLevel::Invalid => FABRIC_REPAIR_NODE_IMPACT_LEVEL_INVALID,
+            RepairNodeImpactLevel::None => FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE,
+            RepairNodeImpactLevel::Restart => FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART,
+            RepairNodeImpactLevel::RemoveData => FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA,
+            RepairNodeImpactLevel::RemoveNode => FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE,
+            RepairNodeImpactLevel::Pause => FABRIC_REPAIR_NODE_IMPACT_LEVEL_PAUSE,
+        }
+    }
+}
+
+// FABRIC_REPAIR_NODE_IMPACT
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairNodeImpact {
+    pub node_name: WString,
+    pub impact_level: RepairNodeImpactLevel,
+}
+
+impl From<&FABRIC_REPAIR_NODE_IMPACT> for RepairNodeImpact {
+    fn from(value: &FABRIC_REPAIR_NODE_IMPACT) -> Self {
+        Self {
+            node_name: WString::from(value.NodeName),
+            impact_level: value.ImpactLevel.into(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_IMPACT_DESCRIPTION
+/// Impact of the repair, set by the repair executor.
+/// The cluster prepares for the impact before approving the task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairImpact {
+    Invalid,
+    Nodes(Vec<RepairNodeImpact>),
+}
+
+impl From<&FABRIC_REPAIR_IMPACT_DESCRIPTION> for RepairImpact {
+    fn from(value: &FABRIC_REPAIR_IMPACT_DESCRIPTION) -> Self {
+        match value.Kind {
+            FABRIC_REPAIR_IMPACT_KIND_NODE => {
+                let list =
+                    unsafe { (value.Value as *const FABRIC_REPAIR_NODE_IMPACT_LIST).as_ref() };
+                Self::Nodes(
+                    list.map(|l| crate::iter::vec_from_raw_com(l.Count as usize, l.Items))
+                        .unwrap_or_default(),
+                )
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_IMPACT_DESCRIPTION> for RepairImpact {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_IMPACT_DESCRIPTION {
+        match self {
+            RepairImpact::Invalid => FABRIC_REPAIR_IMPACT_DESCRIPTION {
+                Kind: FABRIC_REPAIR_IMPACT_KIND_INVALID,
+                Value: std::ptr::null_mut(),
+            },
+            RepairImpact::Nodes(nodes) => {
+                let items = nodes
+                    .iter()
+                    .map(|n| FABRIC_REPAIR_NODE_IMPACT {
+                        NodeName: n.node_name.as_pcwstr(),
+                        ImpactLevel: n.impact_level.into(),
+                        Reserved: std::ptr::null_mut(),
+                    })
+                    .collect::<Vec<_>>();
+                let (count, items) = pool.push_vec(items);
+                let list = pool.push(Box::new(FABRIC_REPAIR_NODE_IMPACT_LIST {
+                    Count: count as u32,
+                    Items: items,
+                }));
+                FABRIC_REPAIR_IMPACT_DESCRIPTION {
+                    Kind: FABRIC_REPAIR_IMPACT_KIND_NODE,
+                    Value: list as *mut c_void,
+                }
+            }
+        }
+    }
+}
+
+// FABRIC_REPAIR_EXECUTOR_STATE
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairExecutorState {
+    /// Name of the executor that claimed the task.
+    pub executor: WString,
+    /// Opaque data owned by the executor.
+    pub executor_data: WString,
+}
+
+impl From<&FABRIC_REPAIR_EXECUTOR_STATE> for RepairExecutorState {
+    fn from(value: &FABRIC_REPAIR_EXECUTOR_STATE) -> Self {
+        Self {
+            executor: WString::from(value.Executor),
+            executor_data: WString::from(value.ExecutorData),
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_RESULT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairTaskResult {
+    #[default]
+    Invalid,
+    Succeeded,
+    Cancelled,
+    Interrupted,
+    Failed,
+    Pending,
+}
+
+impl From<FABRIC_REPAIR_TASK_RESULT> for RepairTaskResult {
+    fn from(value: FABRIC_REPAIR_TASK_RESULT) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_RESULT_SUCCEEDED => Self::Succeeded,
+            FABRIC_REPAIR_TASK_RESULT_CANCELLED => Self::Cancelled,
+            FABRIC_REPAIR_TASK_RESULT_INTERRUPTED => Self::Interrupted,
+            FABRIC_REPAIR_TASK_RESULT_FAILED => Self::Failed,
+            FABRIC_REPAIR_TASK_RESULT_PENDING => Self::Pending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairTaskResult> for FABRIC_REPAIR_TASK_RESULT {
+    fn from(value: RepairTaskResult) -> Self {
+        match value {
+            RepairTaskResult::Invalid => FABRIC_REPAIR_TASK_RESULT_INVALID,
+            RepairTaskResult::Succeeded => FABRIC_REPAIR_TASK_RESULT_SUCCEEDED,
+            RepairTaskResult::Cancelled => FABRIC_REPAIR_TASK_RESULT_CANCELLED,
+            RepairTaskResult::Interrupted => FABRIC_REPAIR_TASK_RESULT_INTERRUPTED,
+            RepairTaskResult::Failed => FABRIC_REPAIR_TASK_RESULT_FAILED,
+            RepairTaskResult::Pending => FABRIC_REPAIR_TASK_RESULT_PENDING,
+        }
+    }
+}
+
+// FABRIC_REPAIR_RESULT_DESCRIPTION
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairResultDescription {
+    pub result_status: RepairTaskResult,
+    pub result_code: HRESULT,
+    pub result_details: WString,
+}
+
+impl From<&FABRIC_REPAIR_RESULT_DESCRIPTION> for RepairResultDescription {
+    fn from(value: &FABRIC_REPAIR_RESULT_DESCRIPTION) -> Self {
+        Self {
+            result_status: value.ResultStatus.into(),
+            result_code: value.ResultCode,
+            result_details: WString::from(value.ResultDetails),
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairTaskHealthCheckState {
+    #[default]
+    NotStarted,
+    InProgress,
+    Succeeded,
+    Skipped,
+    TimedOut,
+}
+
+impl From<FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE> for RepairTaskHealthCheckState {
+    fn from(value: FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_IN_PROGRESS => Self::InProgress,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SUCCEEDED => Self::Succeeded,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SKIPPED => Self::Skipped,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_TIMEDOUT => Self::TimedOut,
+            _ => Self::NotStarted,
+        }
+    }
+}
+
+impl From<RepairTaskHealthCheckState> for FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE {
+    fn from(value: RepairTaskHealthCheckState) -> Self {
+        match value {
+            RepairTaskHealthCheckState::NotStarted => {
+                FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_NOT_STARTED
+            }
+            RepairTaskHealthCheckState::InProgress => {
+                FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_IN_PROGRESS
+            }
+            RepairTaskHealthCheckState::Succeeded => {
+                FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SUCCEEDED
+            }
+            RepairTaskHealthCheckState::Skipped => FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SKIPPED,
+            RepairTaskHealthCheckState::TimedOut => FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_TIMEDOUT,
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_HISTORY
+/// Time stamps of the state transitions. Read only.
+#[derive(Debug, Clone)]
+pub struct RepairTaskHistory {
+    pub created_utc_timestamp: FILETIME,
+    pub claimed_utc_timestamp: FILETIME,
+    pub preparing_utc_timestamp: FILETIME,
+    pub approved_utc_timestamp: FILETIME,
+    pub executing_utc_timestamp: FILETIME,
+    pub restoring_utc_timestamp: FILETIME,
+    pub completed_utc_timestamp: FILETIME,
+    // EX1
+    pub preparing_health_check_start_utc_timestamp: Option<FILETIME>,
+    pub preparing_health_check_end_utc_timestamp: Option<FILETIME>,
+    pub restoring_health_check_start_utc_timestamp: Option<FILETIME>,
+    pub restoring_health_check_end_utc_timestamp: Option<FILETIME>,
+}
+
+impl From<&FABRIC_REPAIR_TASK_HISTORY> for RepairTaskHistory {
+    fn from(value: &FABRIC_REPAIR_TASK_HISTORY) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_REPAIR_TASK_HISTORY_EX1).as_ref() };
+        Self {
+            created_utc_timestamp: value.CreatedUtcTimestamp,
+            claimed_utc_timestamp: value.ClaimedUtcTimestamp,
+            preparing_utc_timestamp: value.PreparingUtcTimestamp,
+            approved_utc_timestamp: value.ApprovedUtcTimestamp,
+            executing_utc_timestamp: value.ExecutingUtcTimestamp,
+            restoring_utc_timestamp: value.RestoringUtcTimestamp,
+            completed_utc_timestamp: value.CompletedUtcTimestamp,
+            preparing_health_check_start_utc_timestamp: ex1
+                .map(|e| e.PreparingHealthCheckStartUtcTimestamp),
+            preparing_health_check_end_utc_timestamp: ex1
+                .map(|e| e.PreparingHealthCheckEndUtcTimestamp),
+            restoring_health_check_start_utc_timestamp: ex1
+                .map(|e| e.RestoringHealthCheckStartUtcTimestamp),
+            restoring_health_check_end_utc_timestamp: ex1
+                .map(|e| e.RestoringHealthCheckEndUtcTimestamp),
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK
+/// A repair task. The task id is chosen by the creator and the version is
+/// assigned by the cluster, the version must match on updates unless it is 0.
+#[derive(Debug, Clone, Default)]
+pub struct RepairTask {
+    pub scope: RepairScope,
+    pub task_id: WString,
+    pub version: i64,
+    pub description: WString,
+    pub state: RepairTaskState,
+    pub flags: RepairTaskFlags,
+    /// The requested repair action, e.g. System.Reboot.
+    pub action: WString,
+    pub target: Option<RepairTarget>,
+    pub executor_state: Option<RepairExecutorState>,
+    pub impact: Option<RepairImpact>,
+    pub result: Option<RepairResultDescription>,
+    /// Set by the cluster. Ignored when creating or updating tasks.
+    pub history: Option<RepairTaskHistory>,
+    // EX1
+    pub perform_preparing_health_check: bool,
+    pub perform_restoring_health_check: bool,
+    pub preparing_health_check_state: RepairTaskHealthCheckState,
+    pub restoring_health_check_state: RepairTaskHealthCheckState,
+}
+
+impl From<&FABRIC_REPAIR_TASK> for RepairTask {
+    fn from(value: &FABRIC_REPAIR_TASK) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_REPAIR_TASK_EX1).as_ref() };
+        Self {
+            scope: unsafe { value.Scope.as_ref() }
+                .map(RepairScope::from)
+                .unwrap_or(RepairScope::Invalid),
+            task_id: WString::from(value.TaskId),
+            version: value.Version,
+            description: WString::from(value.Description),
+            state: value.State.into(),
+            flags: RepairTaskFlags::from_bits_retain(value.Flags),
+            action: WString::from(value.Action),
+            target: unsafe { value.Target.as_ref() }.map(RepairTarget::from),
+            executor_state: unsafe { value.ExecutorState.as_ref() }.map(RepairExecutorState::from),
+            impact: unsafe { value.Impact.as_ref() }.map(RepairImpact::from),
+            result: unsafe { value.Result.as_ref() }.map(RepairResultDescription::from),
+            history: unsafe { value.History.as_ref() }.map(RepairTaskHistory::from),
+            perform_preparing_health_check: ex1.is_some_and(|e| e.PerformPreparingHealthCheck),
+            perform_restoring_health_check: ex1.is_some_and(|e| e.PerformRestoringHealthCheck),
+            preparing_health_check_state: ex1
+                .map(|e| e.PreparingHealthCheckState.into())
+                .unwrap_or_default(),
+            restoring_health_check_state: ex1
+                .map(|e| e.RestoringHealthCheckState.into())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_TASK> for RepairTask {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_TASK {
+        let target = self.target.as_ref().map_or(std::ptr::null(), |t| {
+            let raw = Box::new(t.get_raw_with_pool(pool));
+            pool.push(raw)
+        });
+        let executor_state = self
+            .executor_state
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |e| {
+                pool.push(Box::new(FABRIC_REPAIR_EXECUTOR_STATE {
+                    Executor: e.executor.as_pcwstr(),
+                    ExecutorData: e.executor_data.as_pcwstr(),
+                    Reserved: std::ptr::null_mut(),
+                })) as *mut _
+            });
+        let impact = self.impact.as_ref().map_or(std::ptr::null_mut(), |i| {
+            let raw = Box::new(i.get_raw_with_pool(pool));
+            pool.push(raw) as *mut _
+        });
+        let result = self.result.as_ref().map_or(std::ptr::null_mut(), |r| {
+            pool.push(Box::new(FABRIC_REPAIR_RESULT_DESCRIPTION {
+                ResultStatus: r.result_status.into(),
+                ResultCode: r.result_code,
+                ResultDetails: r.result_details.as_pcwstr(),
+                Reserved: std::ptr::null_mut(),
+            })) as *mut _
+        });
+        let ex1 = pool.push(Box::new(FABRIC_REPAIR_TASK_EX1 {
+            PerformPreparingHealthCheck: self.perform_preparing_health_check,
+            PerformRestoringHealthCheck: self.perform_restoring_health_check,
+            PreparingHealthCheckState: self.preparing_health_check_state.into(),
+            RestoringHealthCheckState: self.restoring_health_check_state.into(),
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_REPAIR_TASK {
+            Scope: self.scope.push_raw(pool),
+            TaskId: self.task_id.as_pcwstr(),
+            Version: self.version,
+            Description: self.description.as_pcwstr(),
+            State: self.state.into(),
+            Flags: self.flags.bits(),
+            Action: self.action.as_pcwstr(),
+            Target: target,
+            ExecutorState: executor_state,
+            Impact: impact,
+            Result: result,
+            History: std::ptr::null(),
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_REPAIR_CANCEL_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct RepairCancelDescription {
+    pub scope: RepairScope,
+    pub repair_task_id: WString,
+    /// 0 skips the version check.
+    pub version: i64,
+    /// Also aborts the task if it is already executing.
+    pub request_abort: bool,
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_CANCEL_DESCRIPTION> for RepairCancelDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_CANCEL_DESCRIPTION {
+        FABRIC_REPAIR_CANCEL_DESCRIPTION {
+            Scope: self.scope.push_raw(pool),
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            RequestAbort: self.request_abort,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_APPROVE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct RepairApproveDescription {
+    pub scope: RepairScope,
+    pub repair_task_id: WString,
+    /// 0 skips the version check.
+    pub version: i64,
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_APPROVE_DESCRIPTION> for RepairApproveDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_APPROVE_DESCRIPTION {
+        FABRIC_REPAIR_APPROVE_DESCRIPTION {
+            Scope: self.scope.push_raw(pool),
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_DELETE_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct RepairDeleteDescription {
+    pub scope: RepairScope,
+    pub repair_task_id: WString,
+    /// 0 skips the version check.
+    pub version: i64,
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_DELETE_DESCRIPTION> for RepairDeleteDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_DELETE_DESCRIPTION {
+        FABRIC_REPAIR_DELETE_DESCRIPTION {
+            Scope: self.scope.push_raw(pool),
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct RepairTaskQueryDescription {
+    pub scope: RepairScope,
+    /// Prefix of the task ids to return.
+    pub task_id_filter: Option<WString>,
+    /// Empty returns tasks in all states.
+    pub state_filter: RepairTaskStateFilter,
+    /// Only returns tasks claimed by this executor.
+    pub executor_filter: Option<WString>,
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_TASK_QUERY_DESCRIPTION> for RepairTaskQueryDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_REPAIR_TASK_QUERY_DESCRIPTION {
+        FABRIC_REPAIR_TASK_QUERY_DESCRIPTION {
+            Scope: self.scope.push_raw(pool),
+            TaskIdFilter: self
+                .task_id_filter
+                .as_ref()
+                .map_or(PCWSTR::null(), |f| f.as_pcwstr()),
+            StateFilter: self.state_filter.bits(),
+            ExecutorFilter: self
+                .executor_filter
+                .as_ref()
+                .map_or(PCWSTR::null(), |f| f.as_pcwstr()),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION
+/// Updates the health check settings of a task. Only the fields set are updated.
+#[derive(Debug, Clone, Default)]
+pub struct RepairTaskHealthPolicyUpdateDescription {
+    pub scope: RepairScope,
+    pub repair_task_id: WString,
+    /// 0 skips the version check.
+    pub version: i64,
+    pub perform_preparing_health_check: Option<bool>,
+    pub perform_restoring_health_check: Option<bool>,
+}
+
+impl GetRawWithBoxPool<FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION>
+    for RepairTaskHealthPolicyUpdateDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        pool: &mut BoxPool,
+    ) -> FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION {
+        let mut flags = 0;
+        if self.perform_preparing_health_check.is_some() {
+            flags |=
+                FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_PREPARING_HEALTH_CHECK
+                    .0;
+        }
+        if self.perform_restoring_health_check.is_some() {
+            flags |=
+                FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK
+                    .0;
+        }
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION {
+            Scope: self.scope.push_raw(pool),
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Flags: flags as u32,
+            PerformPreparingHealthCheck: self.perform_preparing_health_check.unwrap_or_default(),
+            PerformRestoringHealthCheck: self.perform_restoring_health_check.unwrap_or_default(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER, FABRIC_REPAIR_TASK_STATE_EXECUTING,
+    };
+    use windows_core::WString;
+
+    use super::{
+        RepairExecutorState, RepairImpact, RepairNodeImpact, RepairNodeImpactLevel, RepairScope,
+        RepairTarget, RepairTask, RepairTaskFlags, RepairTaskHealthPolicyUpdateDescription,
+        RepairTaskState,
+    };
+    use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+    #[test]
+    fn test_repair_task_round_trip() {
+        let task = RepairTask {
+            task_id: WString::from("reboot-1"),
+            version: 4,
+            state: RepairTaskState::Executing,
+            flags: RepairTaskFlags::ForcedApproval,
+            action: WString::from("System.Reboot"),
+            target: Some(RepairTarget::Nodes(vec![WString::from("_Node_0")])),
+            executor_state: Some(RepairExecutorState {
+                executor: WString::from("exec"),
+                executor_data: WString::from("{}"),
+            }),
+            impact: Some(RepairImpact::Nodes(vec![RepairNodeImpact {
+                node_name: WString::from("_Node_0"),
+                impact_level: RepairNodeImpactLevel::Restart,
+            }])),
+            perform_preparing_health_check: true,
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = task.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.State, FABRIC_REPAIR_TASK_STATE_EXECUTING);
+        assert_eq!(
+            unsafe { raw.Scope.as_ref() }.unwrap().Kind,
+            FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER
+        );
+        let back = RepairTask::from(&raw);
+        assert_eq!(back.scope, RepairScope::Cluster);
+        assert_eq!(back.task_id, task.task_id);
+        assert_eq!(back.version, 4);
+        assert_eq!(back.state, RepairTaskState::Executing);
+        assert_eq!(back.flags, RepairTaskFlags::ForcedApproval);
+        assert_eq!(back.target, task.target);
+        assert_eq!(back.executor_state, task.executor_state);
+        assert_eq!(back.impact, task.impact);
+        assert!(back.result.is_none());
+        assert!(back.history.is_none());
+        assert!(back.perform_preparing_health_check);
+        assert!(!back.perform_restoring_health_check);
+    }
+
+    #[test]
+    fn test_repair_task_state_next() {
+        let mut state = RepairTaskState::Created;
+        let mut count = 0;
+        while let Some(next) = state.next() {
+            state = next;
+            count += 1;
+        }
+        assert_eq!(state, RepairTaskState::Completed);
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn test_health_policy_update_flags() {
+        let desc = RepairTaskHealthPolicyUpdateDescription {
+            repair_task_id: WString::from("reboot-1"),
+            perform_restoring_health_check: Some(true),
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = desc.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.Flags, 2);
+        assert!(raw.PerformRestoringHealthCheck);
+        assert!(!raw.PerformPreparingHealthCheck);
+    }
+}
@@ -11,7 +11,10 @@ use crate::{
     types::{ApplicationHealthPolicy, PagingStatus, ServicePartitionAccessStatus},
 };
 use mssf_com::{
-    FabricClient::{IFabricGetDeployedServiceReplicaDetailResult, IFabricGetReplicaListResult2},
+    FabricClient::{
+        IFabricGetDeployedServiceReplicaDetailResult, IFabricGetReplicaListResult2,
+        IFabricGetUnplacedReplicaInformationResult,
+    },
     FabricTypes::{
         FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION,
         FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_DETAIL_QUERY_RESULT_ITEM,
@@ -25,12 +28,13 @@ use mssf_com::{
         FABRIC_SERVICE_REPLICA_QUERY_RESULT_ITEM,
         FABRIC_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM,
         FABRIC_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM,
+        FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION,
     },
 };
 
 use crate::types::{HealthState, ReplicaRole};
 
-use super::{QueryReplicatorOperationName, QueryServiceOperationName};
+use super::{QueryReplicatorOperationName, QueryServiceOperationName, string_list_from_raw};
 
 // FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION
 #[derive(Debug, Clone, Default)]
@@ -363,6 +367,48 @@ impl DeployedServiceReplicaDetailQueryResult {
     }
 }
 
+// FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct UnplacedReplicaInformationQueryDescription {
+    pub service_name: crate::types::Uri,
+    /// Zero guid queries all partitions of the service.
+    pub partition_id: GUID,
+    pub only_query_primaries: bool,
+}
+
+impl From<&UnplacedReplicaInformationQueryDescription>
+    for FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION
+{
+    fn from(value: &UnplacedReplicaInformationQueryDescription) -> Self {
+        Self {
+            ServiceName: value.service_name.as_raw(),
+            PartitionId: value.partition_id,
+            OnlyQueryPrimaries: value.only_query_primaries,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_UNPLACED_REPLICA_INFORMATION
+#[derive(Debug, Clone)]
+pub struct UnplacedReplicaInformation {
+    pub service_name: crate::types::Uri,
+    pub partition_id: GUID,
+    /// Reasons from the placement engine why replicas could not be placed.
+    pub unplaced_replica_reasons: Vec<WString>,
+}
+
+impl From<&IFabricGetUnplacedReplicaInformationResult> for UnplacedReplicaInformation {
+    fn from(com: &IFabricGetUnplacedReplicaInformationResult) -> Self {
+        let raw = unsafe { com.get_UnplacedReplicaInformation().as_ref().unwrap() };
+        Self {
+            service_name: raw.ServiceName.into(),
+            partition_id: raw.PartitionId,
+            unplaced_replica_reasons: string_list_from_raw(raw.UnplacedReplicaReasons),
+        }
+    }
+}
+
 // FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION
 #[derive(Debug, Clone, Default)]
 pub struct ReplicaHealthQueryDescription {
@@ -0,0 +1,261 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use mssf_com::{
+    FabricClient::{
+        IFabricManagedIdentityTokenServiceDescription, IFabricSecretReferencesResult,
+        IFabricSecretsResult,
+    },
+    FabricTypes::{
+        FABRIC_MANAGED_IDENTITY_TOKEN_SERVICE_DESCRIPTION, FABRIC_SECRET, FABRIC_SECRET_LIST,
+        FABRIC_SECRET_REFERENCE, FABRIC_SECRET_REFERENCE_LIST,
+    },
+};
+use windows_core::{PCWSTR, WString};
+
+use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+fn optional_string_from_raw(value: PCWSTR) -> Option<WString> {
+    if value.is_null() {
+        None
+    } else {
+        Some(WString::from(value))
+    }
+}
+
+// FABRIC_SECRET_REFERENCE
+/// Identifies a secret, or one version of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretReference {
+    pub name: WString,
+    /// None refers to all versions for removal and version queries,
+    /// and to the latest version for reads.
+    pub version: Option<WString>,
+}
+
+impl SecretReference {
+    pub fn new(name: WString) -> Self {
+        Self {
+            name,
+            version: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: WString) -> Self {
+        self.version = Some(version);
+        self
+    }
+}
+
+impl From<&FABRIC_SECRET_REFERENCE> for SecretReference {
+    fn from(value: &FABRIC_SECRET_REFERENCE) -> Self {
+        Self {
+            name: WString::from(value.Name),
+            version: optional_string_from_raw(value.Version),
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_SECRET_REFERENCE_LIST> for [SecretReference] {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_SECRET_REFERENCE_LIST {
+        let items = self
+            .iter()
+            .map(|r| FABRIC_SECRET_REFERENCE {
+                Name: r.name.as_pcwstr(),
+                Version: r.version.as_ref().into(),
+            })
+            .collect::<Vec<_>>();
+        let (count, items) = pool.push_vec(items);
+        FABRIC_SECRET_REFERENCE_LIST {
+            Count: count as u32,
+            Items: items as *mut _,
+        }
+    }
+}
+
+// FABRIC_SECRET
+/// A secret in the central secret store.
+/// Only the fields that are set are sent when storing a secret.
+/// The value is redacted in the debug output.
+#[derive(Clone, Default, PartialEq)]
+pub struct Secret {
+    pub name: WString,
+    pub version: Option<WString>,
+    /// Only returned when the value is requested.
+    pub value: Option<WString>,
+    pub kind: Option<WString>,
+    pub content_type: Option<WString>,
+    pub description: Option<WString>,
+}
+
+impl Secret {
+    pub fn new(name: WString, version: WString, value: WString) -> Self {
+        Self {
+            name,
+            version: Some(version),
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secret")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("value", &self.value.as_ref().map(|_| "<redacted>"))
+            .field("kind", &self.kind)
+            .field("content_type", &self.content_type)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl From<&FABRIC_SECRET> for Secret {
+    fn from(value: &FABRIC_SECRET) -> Self {
+        Self {
+            name: WString::from(value.Name),
+            version: optional_string_from_raw(value.Version),
+            value: optional_string_from_raw(value.Value),
+            kind: optional_string_from_raw(value.Kind),
+            content_type: optional_string_from_raw(value.ContentType),
+            description: optional_string_from_raw(value.Description),
+        }
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_SECRET_LIST> for [Secret] {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_SECRET_LIST {
+        let items = self
+            .iter()
+            .map(|s| FABRIC_SECRET {
+                Name: s.name.as_pcwstr(),
+                Version: s.version.as_ref().into(),
+                Value: s.value.as_ref().into(),
+                Kind: s.kind.as_ref().into(),
+                ContentType: s.content_type.as_ref().into(),
+                Description: s.description.as_ref().into(),
+            })
+            .collect::<Vec<_>>();
+        let (count, items) = pool.push_vec(items);
+        FABRIC_SECRET_LIST {
+            Count: count as u32,
+            Items: items as *mut _,
+        }
+    }
+}
+
+// IFabricSecretsResult
+#[derive(Debug, Clone)]
+pub struct SecretList {
+    pub secrets: Vec<Secret>,
+}
+
+impl From<&IFabricSecretsResult> for SecretList {
+    fn from(value: &IFabricSecretsResult) -> Self {
+        let secrets = unsafe { value.get_Secrets().as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default();
+        Self { secrets }
+    }
+}
+
+// IFabricSecretReferencesResult
+#[derive(Debug, Clone)]
+pub struct SecretReferenceList {
+    pub references: Vec<SecretReference>,
+}
+
+impl From<&IFabricSecretReferencesResult> for SecretReferenceList {
+    fn from(value: &IFabricSecretReferencesResult) -> Self {
+        let references = unsafe { value.get_SecretReferences().as_ref() }
+            .map(|list| crate::iter::vec_from_raw_com(list.Count as usize, list.Items))
+            .unwrap_or_default();
+        Self { references }
+    }
+}
+
+// FABRIC_MANAGED_IDENTITY_TOKEN_SERVICE_DESCRIPTION
+/// Describes the managed identity token service of a node.
+/// Tokens are fetched from the application token endpoint.
+#[derive(Debug, Clone)]
+pub struct ManagedIdentityTokenServiceDescription {
+    pub is_token_service_enabled: bool,
+    pub is_cluster_identity_enabled: bool,
+    pub application_token_endpoint: WString,
+    pub system_token_endpoint: WString,
+    pub cluster_identity_object_id: WString,
+    pub remote_token_endpoint: WString,
+    pub server_certificate_thumbprint: WString,
+}
+
+impl From<&FABRIC_MANAGED_IDENTITY_TOKEN_SERVICE_DESCRIPTION>
+    for ManagedIdentityTokenServiceDescription
+{
+    fn from(value: &FABRIC_MANAGED_IDENTITY_TOKEN_SERVICE_DESCRIPTION) -> Self {
+        Self {
+            is_token_service_enabled: value.IsTokenServiceEnabled,
+            is_cluster_identity_enabled: value.IsClusterIdentityEnabled,
+            application_token_endpoint: WString::from(value.ApplicationTokenEndpoint),
+            system_token_endpoint: WString::from(value.SystemTokenEndpoint),
+            cluster_identity_object_id: WString::from(value.ClusterIdentityObjectId),
+            remote_token_endpoint: WString::from(value.RemoteTokenEndpoint),
+            server_certificate_thumbprint: WString::from(value.ServerCertificateThumbprint),
+        }
+    }
+}
+
+impl From<&IFabricManagedIdentityTokenServiceDescription>
+    for ManagedIdentityTokenServiceDescription
+{
+    fn from(value: &IFabricManagedIdentityTokenServiceDescription) -> Self {
+        let raw = unsafe { value.get_TokenServiceDescription().as_ref() }
+            .expect("token service description should not be null");
+        Self::from(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use windows_core::WString;
+
+    use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+    use super::{Secret, SecretReference};
+
+    #[test]
+    fn test_secret_raw_roundtrip() {
+        let secrets = vec![
+            Secret::new("a".into(), "v1".into(), "value".into()),
+            Secret {
+                name: "b".into(),
+                version: Some("v2".into()),
+                content_type: Some("text/plain".into()),
+                ..Default::default()
+            },
+        ];
+        let mut pool = BoxPool::new();
+        let raw = secrets.as_slice().get_raw_with_pool(&mut pool);
+        let back = crate::iter::vec_from_raw_com::<_, Secret>(raw.Count as usize, raw.Items);
+        assert_eq!(back, secrets);
+        let debug = format!("{:?}", secrets[0]);
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("\"value\""));
+
+        // Unset version is passed as null.
+        let refs = vec![
+            SecretReference::new("a".into()),
+            SecretReference::new("b".into()).with_version("v2".into()),
+        ];
+        let raw = refs.as_slice().get_raw_with_pool(&mut pool);
+        let items = unsafe { std::slice::from_raw_parts(raw.Items, raw.Count as usize) };
+        assert!(items[0].Version.is_null());
+        assert_eq!(WString::from(items[1].Version), WString::from("v2"));
+        let back =
+            crate::iter::vec_from_raw_com::<_, SecretReference>(raw.Count as usize, raw.Items);
+        assert_eq!(back, refs);
+    }
+}
@@ -6,25 +6,31 @@
 use std::ffi::c_void;
 
 use mssf_com::{
-    FabricClient::IFabricGetServiceListResult2,
+    FabricClient::{
+        IFabricGetServiceListResult2, IFabricGetServiceTypeListResult,
+        IFabricServiceDescriptionResult,
+    },
     FabricTypes::{
         FABRIC_DELETE_SERVICE_DESCRIPTION, FABRIC_NAMED_REPARTITION_DESCRIPTION,
         FABRIC_SERVICE_DESCRIPTION, FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL,
-        FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS, FABRIC_SERVICE_HEALTH_STATE,
-        FABRIC_SERVICE_PARTITION_KIND, FABRIC_SERVICE_PARTITION_KIND_INVALID,
-        FABRIC_SERVICE_PARTITION_KIND_NAMED, FABRIC_SERVICE_QUERY_DESCRIPTION,
-        FABRIC_SERVICE_QUERY_DESCRIPTION_EX1, FABRIC_SERVICE_QUERY_DESCRIPTION_EX2,
-        FABRIC_SERVICE_QUERY_DESCRIPTION_EX3, FABRIC_SERVICE_QUERY_RESULT_ITEM,
-        FABRIC_SERVICE_UPDATE_DESCRIPTION, FABRIC_STATEFUL_SERVICE_DESCRIPTION,
-        FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1, FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX2,
-        FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3, FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX4,
-        FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS, FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX1,
+        FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS, FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION,
+        FABRIC_SERVICE_HEALTH_STATE, FABRIC_SERVICE_PARTITION_KIND,
+        FABRIC_SERVICE_PARTITION_KIND_INVALID, FABRIC_SERVICE_PARTITION_KIND_NAMED,
+        FABRIC_SERVICE_QUERY_DESCRIPTION, FABRIC_SERVICE_QUERY_DESCRIPTION_EX1,
+        FABRIC_SERVICE_QUERY_DESCRIPTION_EX2, FABRIC_SERVICE_QUERY_DESCRIPTION_EX3,
+        FABRIC_SERVICE_QUERY_RESULT_ITEM, FABRIC_SERVICE_TYPE_DESCRIPTION,
+        FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION, FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM,
+        FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM_EX1, FABRIC_SERVICE_UPDATE_DESCRIPTION,
+        FABRIC_STATEFUL_SERVICE_DESCRIPTION, FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1,
+        FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX2, FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3,
+        FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX4, FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS,
+        FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX1,
         FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX2,
         FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX3,
         FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX4,
         FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX5,
-        FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX6, FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION,
-        FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX1,
+        FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX6, FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION,
+        FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION, FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX1,
         FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX2,
         FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX3,
         FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX4,
@@ -38,12 +44,15 @@ use mssf_com::{
         FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX12, FABRIC_STATELESS_SERVICE_DESCRIPTION,
         FABRIC_STATELESS_SERVICE_DESCRIPTION_EX1, FABRIC_STATELESS_SERVICE_DESCRIPTION_EX2,
         FABRIC_STATELESS_SERVICE_DESCRIPTION_EX3, FABRIC_STATELESS_SERVICE_DESCRIPTION_EX4,
-        FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION,
+        FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION, FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION,
+        FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX1,
+        FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX2,
     },
 };
 use windows_core::{PCWSTR, WString};
 
 use crate::{
+    ErrorCode,
     mem::{BoxPool, GetRaw, GetRawWithBoxPool},
     types::{
         ApplicationHealthPolicy, HealthEvent, HealthEventsFilter, HealthState,
@@ -52,13 +61,14 @@ use crate::{
     },
 };
 
+#[derive(Debug, Clone)]
 pub enum ServiceDescription {
     // Invalid,
     Stateful(StatefulServiceDescription), // FABRIC_STATEFUL_SERVICE_DESCRIPTION
     Stateless(StatelessServiceDescription), // FABRIC_STATELESS_SERVICE_DESCRIPTION
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StatefulServiceDescription {
     // common
     // Note: if application_name is not set, SF com api will succeed but the service does not show up in the SF explorer.
@@ -150,6 +160,62 @@ impl StatefulServiceDescription {
         self
     }
 
+    pub fn application_name(&self) -> &Uri {
+        &self.application_name
+    }
+    pub fn service_name(&self) -> &Uri {
+        &self.service_name
+    }
+    pub fn service_type_name(&self) -> &WString {
+        &self.service_type_name
+    }
+    pub fn initialization_data(&self) -> Option<&Vec<u8>> {
+        self.initialization_data.as_ref()
+    }
+    pub fn partition_scheme(&self) -> &PartitionSchemeDescription {
+        &self.partition_scheme
+    }
+    pub fn min_replica_set_size(&self) -> i32 {
+        self.min_replica_set_size
+    }
+    pub fn target_replica_set_size(&self) -> i32 {
+        self.target_replica_set_size
+    }
+    pub fn placement_constraints(&self) -> &WString {
+        &self.placement_contraints
+    }
+    pub fn has_persistent_state(&self) -> bool {
+        self.has_persistent_state
+    }
+    pub fn default_move_cost(&self) -> Option<MoveCost> {
+        self.default_move_cost
+    }
+    pub fn service_package_activation_mode(&self) -> ServicePackageActivationMode {
+        self.service_package_activation_mode
+    }
+
+    // Failover settings getters. None means the setting is not specified.
+
+    pub fn replica_restart_wait_duration_seconds(&self) -> Option<u32> {
+        self.failover_settings.replica_restart_wait_duration_seconds
+    }
+    pub fn quorum_loss_wait_duration_seconds(&self) -> Option<u32> {
+        self.failover_settings.quorum_loss_wait_duration_seconds
+    }
+    pub fn stand_by_replica_keep_duration_seconds(&self) -> Option<u32> {
+        self.failover_settings
+            .stand_by_replica_keep_duration_seconds
+    }
+    pub fn service_placement_time_limit_seconds(&self) -> Option<u32> {
+        self.failover_settings.service_placement_time_limit_seconds
+    }
+    pub fn drop_source_replica_on_move(&self) -> Option<bool> {
+        self.failover_settings.drop_source_replica_on_move
+    }
+    pub fn auxiliary_replica_count(&self) -> Option<i32> {
+        self.failover_settings.auxiliary_replica_count
+    }
+
     // Failover settings setters
 
     pub fn with_replica_restart_wait_duration_seconds(mut self, seconds: u32) -> Self {
@@ -257,6 +323,63 @@ impl GetRawWithBoxPool<FABRIC_STATEFUL_SERVICE_DESCRIPTION> for StatefulServiceD
     }
 }
 
+impl From<&FABRIC_STATEFUL_SERVICE_DESCRIPTION> for StatefulServiceDescription {
+    fn from(value: &FABRIC_STATEFUL_SERVICE_DESCRIPTION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX2).as_ref()
+        });
+        let ex3 = ex2.and_then(|ex2| unsafe {
+            (ex2.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3).as_ref()
+        });
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            service_name: Uri::from(value.ServiceName),
+            service_type_name: WString::from(value.ServiceTypeName),
+            initialization_data: initialization_data_from_raw(
+                value.InitializationData,
+                value.InitializationDataSize,
+            ),
+            partition_scheme: PartitionSchemeDescription::from_raw(
+                value.PartitionScheme,
+                value.PartitionSchemeDescription,
+            ),
+            min_replica_set_size: value.MinReplicaSetSize,
+            target_replica_set_size: value.TargetReplicaSetSize,
+            placement_contraints: WString::from(value.PlacementConstraints),
+            _correlations: Vec::new(),
+            _metrics: Vec::new(),
+            has_persistent_state: value.HasPersistedState,
+            _policy_list: Vec::new(),
+            failover_settings: ex1
+                .and_then(|ex1| unsafe { ex1.FailoverSettings.as_ref() })
+                .map(StatefulServiceFailoverSettings::from)
+                .unwrap_or_default(),
+            default_move_cost: ex2
+                .filter(|ex2| ex2.IsDefaultMoveCostSpecified)
+                .map(|ex2| MoveCost::from(ex2.DefaultMoveCost)),
+            service_package_activation_mode: ex3
+                .map_or(ServicePackageActivationMode::default(), |ex3| {
+                    ServicePackageActivationMode::from(ex3.ServicePackageActivationMode)
+                }),
+            _service_dns_name: ex3
+                .filter(|ex3| !ex3.ServiceDnsName.is_null())
+                .map(|ex3| WString::from(ex3.ServiceDnsName)),
+            _service_scaling_policys: Vec::new(),
+        }
+    }
+}
+
+fn initialization_data_from_raw(data: *const u8, size: u32) -> Option<Vec<u8>> {
+    if data.is_null() || size == 0 {
+        None
+    } else {
+        Some(unsafe { std::slice::from_raw_parts(data, size as usize) }.to_vec())
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct StatelessServiceDescription {
     // common
     pub application_name: Uri,
@@ -328,7 +451,56 @@ impl StatelessServiceDescription {
         self.service_package_activation_mode = service_package_activation_mode;
         self
     }
+
+    pub fn default_move_cost(&self) -> Option<MoveCost> {
+        self.default_move_cost
+    }
+    pub fn service_package_activation_mode(&self) -> ServicePackageActivationMode {
+        self.service_package_activation_mode
+    }
+}
+
+impl From<&FABRIC_STATELESS_SERVICE_DESCRIPTION> for StatelessServiceDescription {
+    fn from(value: &FABRIC_STATELESS_SERVICE_DESCRIPTION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX2).as_ref()
+        });
+        let ex3 = ex2.and_then(|ex2| unsafe {
+            (ex2.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX3).as_ref()
+        });
+        Self {
+            application_name: Uri::from(value.ApplicationName),
+            service_name: Uri::from(value.ServiceName),
+            service_type_name: WString::from(value.ServiceTypeName),
+            initialization_data: initialization_data_from_raw(
+                value.InitializationData,
+                value.InitializationDataSize,
+            ),
+            partition_scheme_description: PartitionSchemeDescription::from_raw(
+                value.PartitionScheme,
+                value.PartitionSchemeDescription,
+            ),
+            instance_count: value.InstanceCount,
+            placement_contraints: WString::from(value.PlacementConstraints),
+            _correlations: Vec::new(),
+            _metrics: Vec::new(),
+            _policy_list: Vec::new(),
+            default_move_cost: ex2
+                .filter(|ex2| ex2.IsDefaultMoveCostSpecified)
+                .map(|ex2| MoveCost::from(ex2.DefaultMoveCost)),
+            service_package_activation_mode: ex3
+                .map_or(ServicePackageActivationMode::default(), |ex3| {
+                    ServicePackageActivationMode::from(ex3.ServicePackageActivationMode)
+                }),
+            _service_dns_name: ex3
+                .map_or(WString::default(), |ex3| WString::from(ex3.ServiceDnsName)),
+            _service_scaling_policys: Vec::new(),
+        }
+    }
 }
+
 impl GetRawWithBoxPool<FABRIC_STATELESS_SERVICE_DESCRIPTION> for StatelessServiceDescription {
     fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_STATELESS_SERVICE_DESCRIPTION {
         let ex4 = pool.push(Box::new(FABRIC_STATELESS_SERVICE_DESCRIPTION_EX4 {
@@ -399,6 +571,68 @@ impl GetRawWithBoxPool<FABRIC_SERVICE_DESCRIPTION> for ServiceDescription {
     }
 }
 
+impl TryFrom<&FABRIC_SERVICE_DESCRIPTION> for ServiceDescription {
+    type Error = crate::Error;
+
+    /// Fails on an unknown description kind or a null value.
+    fn try_from(value: &FABRIC_SERVICE_DESCRIPTION) -> crate::Result<Self> {
+        match value.Kind {
+            FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL => {
+                unsafe { (value.Value as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION).as_ref() }
+                    .map(|d| ServiceDescription::Stateful(d.into()))
+                    .ok_or(ErrorCode::E_POINTER.into())
+            }
+            FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS => {
+                unsafe { (value.Value as *const FABRIC_STATELESS_SERVICE_DESCRIPTION).as_ref() }
+                    .map(|d| ServiceDescription::Stateless(d.into()))
+                    .ok_or(ErrorCode::E_POINTER.into())
+            }
+            _ => Err(ErrorCode::E_INVALIDARG.into()),
+        }
+    }
+}
+
+impl TryFrom<&IFabricServiceDescriptionResult> for ServiceDescription {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricServiceDescriptionResult) -> crate::Result<Self> {
+        unsafe { value.get_Description().as_ref() }
+            .ok_or(crate::Error::from(ErrorCode::E_POINTER))
+            .and_then(ServiceDescription::try_from)
+    }
+}
+
+// FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION
+/// Creates a service from a service template declared in the application manifest.
+#[derive(Debug, Clone)]
+pub struct ServiceFromTemplateDescription {
+    pub application_name: Uri,
+    pub service_name: Uri,
+    pub service_dns_name: Option<WString>,
+    pub service_type_name: WString,
+    pub service_package_activation_mode: ServicePackageActivationMode,
+    pub initialization_data: Vec<u8>,
+}
+
+impl GetRaw<FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION> for ServiceFromTemplateDescription {
+    fn get_raw(&self) -> FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION {
+        FABRIC_SERVICE_FROM_TEMPLATE_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            ServiceName: self.service_name.as_raw(),
+            ServiceDnsName: self.service_dns_name.as_ref().into(),
+            ServiceTypeName: self.service_type_name.as_pcwstr(),
+            ServicePackageActivationMode: self.service_package_activation_mode.into(),
+            InitializationDataSize: self.initialization_data.len() as u32,
+            InitializationData: if self.initialization_data.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                self.initialization_data.as_ptr() as *mut u8
+            },
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
 // Update API payloads
 // ===================================================
 
@@ -656,6 +890,75 @@ impl GetRawWithBoxPool<Option<FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS>>
     }
 }
 
+impl From<&FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS> for StatefulServiceFailoverSettings {
+    /// Only the settings whose flag is set are read.
+    fn from(value: &FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS) -> Self {
+        let flags = StatefulServiceFailoverSettingsFlags::from_bits_truncate(value.Flags);
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX1).as_ref()
+        };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX2).as_ref()
+        });
+        let ex3 = ex2.and_then(|ex2| unsafe {
+            (ex2.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX3).as_ref()
+        });
+        let ex4 = ex3.and_then(|ex3| unsafe {
+            (ex3.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX4).as_ref()
+        });
+        let ex5 = ex4.and_then(|ex4| unsafe {
+            (ex4.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX5).as_ref()
+        });
+        let ex6 = ex5.and_then(|ex5| unsafe {
+            (ex5.Reserved as *const FABRIC_STATEFUL_SERVICE_FAILOVER_SETTINGS_EX6).as_ref()
+        });
+        let replica_lifecycle =
+            ex4.and_then(|ex4| unsafe { ex4.ReplicaLifecycleDescription.as_ref() });
+        let flag = |f: StatefulServiceFailoverSettingsFlags| flags.contains(f);
+        Self {
+            replica_restart_wait_duration_seconds: flag(
+                StatefulServiceFailoverSettingsFlags::REPLICA_RESTART_WAIT_DURATION,
+            )
+            .then_some(value.ReplicaRestartWaitDurationSeconds),
+            quorum_loss_wait_duration_seconds: flag(
+                StatefulServiceFailoverSettingsFlags::QUORUM_LOSS_WAIT_DURATION,
+            )
+            .then_some(value.QuorumLossWaitDurationSeconds),
+            stand_by_replica_keep_duration_seconds: ex1
+                .filter(|_| {
+                    flag(StatefulServiceFailoverSettingsFlags::STANDBY_REPLICA_KEEP_DURATION)
+                })
+                .map(|ex1| ex1.StandByReplicaKeepDurationSeconds),
+            service_placement_time_limit_seconds: ex2
+                .filter(|_| {
+                    flag(StatefulServiceFailoverSettingsFlags::SERVICE_PLACEMENT_TIME_LIMIT)
+                })
+                .map(|ex2| ex2.ServicePlacementTimeLimitSeconds),
+            drop_source_replica_on_move: ex3
+                .filter(|_| flag(StatefulServiceFailoverSettingsFlags::DROP_SOURCE_REPLICA_ON_MOVE))
+                .map(|ex3| ex3.DropSourceReplicaOnMove),
+            is_singleton_replica_move_allowed_during_upgrade: replica_lifecycle
+                .filter(|r| r.IsIsSingletonReplicaMoveAllowedDuringUpgradeSpecified)
+                .map(|r| r.IsSingletonReplicaMoveAllowedDuringUpgrade),
+            restore_replica_location_after_upgrade: replica_lifecycle
+                .filter(|r| r.IsRestoreReplicaLocationAfterUpgradeSpecified)
+                .map(|r| r.RestoreReplicaLocationAfterUpgrade),
+            auxiliary_replica_count: ex5
+                .filter(|_| flag(StatefulServiceFailoverSettingsFlags::AUXILIARY_REPLICA_COUNT))
+                .map(|ex5| ex5.AuxiliaryReplicaCount),
+            service_sensitivity: ex6
+                .filter(|_| flag(StatefulServiceFailoverSettingsFlags::SERVICE_SENSITIVITY))
+                .and_then(|ex6| unsafe { ex6.ServiceSensitivityDescription.as_ref() })
+                .map(|s| ServiceSensitivityDescription {
+                    primary_default_sensitivity: s.PrimaryDefaultSensitivity,
+                    secondary_default_sensitivity: s.SecondaryDefaultSensitivity,
+                    auxiliary_default_sensitivity: s.AuxiliaryDefaultSensitivity,
+                    is_maximum_sensitivity: s.IsMaximumSensitivity,
+                }),
+        }
+    }
+}
+
 bitflags::bitflags! {
     /// FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_FLAGS
     /// Indicates what fields are set in the description.
@@ -799,6 +1102,13 @@ impl StatefulServiceUpdateDescription {
         self
     }
 
+    pub fn with_placement_constraints(mut self, placement_contraints: WString) -> Self {
+        self.flags |=
+            StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_PLACEMENT_CONSTRAINTS;
+        self.placement_contraints = placement_contraints;
+        self
+    }
+
     pub fn with_move_cost(mut self, default_move_cost: MoveCost) -> Self {
         self.flags |= StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_MOVE_COST;
         self.default_move_cost = default_move_cost;
@@ -854,32 +1164,109 @@ impl StatefulServiceUpdateDescription {
         self.auxiliary_replica_count = auxiliary_replica_count;
         self
     }
+
+    pub fn flags(&self) -> StatefulServiceUpdateDescriptionFlags {
+        self.flags
+    }
+}
+
+bitflags::bitflags! {
+    /// FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_FLAGS
+    /// Indicates what fields are set in the description.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StatelessServiceUpdateDescriptionFlags: u32 {
+        const FABRIC_STATELESS_SERVICE_NONE = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_NONE.0 as u32;
+        const FABRIC_STATELESS_SERVICE_INSTANCE_COUNT = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT.0 as u32;
+        const FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_POLICY_LIST = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_POLICY_LIST.0 as u32;
+        const FABRIC_STATELESS_SERVICE_CORRELATIONS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_CORRELATIONS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_METRICS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_METRICS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_MOVE_COST = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_MOVE_COST.0 as u32;
+        const FABRIC_STATELESS_SERVICE_SCALING_POLICY = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_SCALING_POLICY.0 as u32;
+        const FABRIC_STATELESS_SERVICE_MIN_INSTANCE_COUNT = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_MIN_INSTANCE_COUNT.0 as u32;
+        const FABRIC_STATELESS_SERVICE_MIN_INSTANCE_PERCENTAGE = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_MIN_INSTANCE_PERCENTAGE.0 as u32;
+        const FABRIC_STATELESS_SERVICE_INSTANCE_CLOSE_DELAY_DURATION = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_INSTANCE_CLOSE_DELAY_DURATION.0 as u32;
+        const FABRIC_STATELESS_SERVICE_INSTANCE_RESTART_WAIT_DURATION = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_INSTANCE_RESTART_WAIT_DURATION.0 as u32;
+        const FABRIC_STATELESS_SERVICE_SERVICE_DNS_NAME = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_SERVICE_DNS_NAME.0 as u32;
+        const FABRIC_STATELESS_SERVICE_RESTORE_REPLICA_LOCATION_AFTER_UPGRADE = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_RESTORE_REPLICA_LOCATION_AFTER_UPGRADE.0 as u32;
+        const FABRIC_STATELESS_SERVICE_TAGS_REQUIRED_TO_PLACE = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_TAGS_REQUIRED_TO_PLACE.0 as u32;
+        const FABRIC_STATELESS_SERVICE_TAGS_REQUIRED_TO_RUN = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_TAGS_REQUIRED_TO_RUN.0 as u32;
+    }
+}
+impl Default for StatelessServiceUpdateDescriptionFlags {
+    fn default() -> Self {
+        StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_NONE
+    }
 }
 
 /// FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION
+#[derive(Debug, Default)]
 pub struct StatelessServiceUpdateDescription {
-    pub flags: StatefulServiceUpdateDescriptionFlags,
-    pub instance_count: i32,
+    flags: StatelessServiceUpdateDescriptionFlags,
+    instance_count: i32,
     // ex1
-    pub placement_contraints: WString,
-    pub policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
-    pub correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-    pub metrics: Vec<WString>,     // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+    placement_contraints: WString,
+    _policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
+    _correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
+    _metrics: Vec<WString>,     // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
     // ex2
-    pub default_move_cost: Option<MoveCost>, // TODO: FABRIC_MOVE_COST
-    // ex5
-    pub repartition_description: PartitionSchemeDescription, // TODO
-    pub scaling_policys: Vec<WString>,                       // TODO: FABRIC_SERVICE_SCALING_POLICY
+    default_move_cost: MoveCost,
+    // ex3 - repartition and scaling policies (not yet wired)
+}
+
+// setters for the fields
+impl StatelessServiceUpdateDescription {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_instance_count(mut self, instance_count: i32) -> Self {
+        self.flags |=
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT;
+        self.instance_count = instance_count;
+        self
+    }
+
+    pub fn with_placement_constraints(mut self, placement_contraints: WString) -> Self {
+        self.flags |=
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS;
+        self.placement_contraints = placement_contraints;
+        self
+    }
+
+    pub fn with_move_cost(mut self, default_move_cost: MoveCost) -> Self {
+        self.flags |= StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_MOVE_COST;
+        self.default_move_cost = default_move_cost;
+        self
+    }
+
+    pub fn flags(&self) -> StatelessServiceUpdateDescriptionFlags {
+        self.flags
+    }
 }
 
 impl GetRawWithBoxPool<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION>
     for StatelessServiceUpdateDescription
 {
-    fn get_raw_with_pool(
-        &self,
-        _pool: &mut BoxPool,
-    ) -> FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION {
-        unimplemented!()
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION {
+        let ex2 = pool.push(Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX2 {
+            DefaultMoveCost: self.default_move_cost.into(),
+            Reserved: std::ptr::null_mut(),
+        }));
+        let ex1 = pool.push(Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX1 {
+            PlacementConstraints: self.placement_contraints.as_pcwstr(),
+            PolicyList: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_LIST
+            CorrelationCount: 0,
+            Correlations: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
+            MetricCount: 0,
+            Metrics: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+            Reserved: ex2 as *const _ as *mut c_void,
+        }));
+        FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION {
+            Flags: self.flags.bits(),
+            InstanceCount: self.instance_count,
+            Reserved: ex1 as *const _ as *mut c_void,
+        }
     }
 }
 
@@ -1232,6 +1619,137 @@ impl From<&mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM>
     }
 }
 
+// FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ServiceTypeQueryDescription {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub service_type_name_filter: Option<WString>,
+}
+
+impl From<&ServiceTypeQueryDescription> for FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION {
+    fn from(value: &ServiceTypeQueryDescription) -> Self {
+        Self {
+            ApplicationTypeName: value.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: value.application_type_version.as_pcwstr(),
+            ServiceTypeNameFilter: value.service_type_name_filter.as_ref().into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetServiceTypeListResult
+#[derive(Debug, Clone)]
+pub struct ServiceTypeListResult {
+    pub items: Vec<ServiceTypeQueryResultItem>,
+}
+
+impl From<&IFabricGetServiceTypeListResult> for ServiceTypeListResult {
+    fn from(value: &IFabricGetServiceTypeListResult) -> Self {
+        let items = unsafe { value.get_ServiceTypeList().as_ref() }
+            .map(|arr| crate::iter::vec_from_raw_com(arr.Count as usize, arr.Items))
+            .unwrap_or_default();
+        Self { items }
+    }
+}
+
+// FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct ServiceTypeQueryResultItem {
+    pub service_type_description: ServiceTypeDescription,
+    pub service_manifest_version: WString,
+    pub service_manifest_name: WString,
+}
+
+impl From<&FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM> for ServiceTypeQueryResultItem {
+    fn from(value: &FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        Self {
+            service_type_description: unsafe { value.ServiceTypeDescription.as_ref() }.map_or(
+                ServiceTypeDescription::Invalid,
+                ServiceTypeDescription::from,
+            ),
+            service_manifest_version: WString::from(value.ServiceManifestVersion),
+            service_manifest_name: ex1
+                .map_or(WString::new(), |ex1| WString::from(ex1.ServiceManifestName)),
+        }
+    }
+}
+
+// FABRIC_SERVICE_TYPE_DESCRIPTION
+#[derive(Debug, Clone)]
+pub enum ServiceTypeDescription {
+    Invalid,
+    // FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION
+    Stateful(StatefulServiceTypeDescription),
+    // FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION
+    Stateless(StatelessServiceTypeDescription),
+}
+
+impl From<&FABRIC_SERVICE_TYPE_DESCRIPTION> for ServiceTypeDescription {
+    fn from(value: &FABRIC_SERVICE_TYPE_DESCRIPTION) -> Self {
+        match value.Kind {
+            mssf_com::FabricTypes::FABRIC_SERVICE_KIND_STATEFUL => {
+                unsafe { (value.Value as *const FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION).as_ref() }
+                    .map_or(Self::Invalid, |raw| Self::Stateful(raw.into()))
+            }
+            mssf_com::FabricTypes::FABRIC_SERVICE_KIND_STATELESS => unsafe {
+                (value.Value as *const FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION).as_ref()
+            }
+            .map_or(Self::Invalid, |raw| Self::Stateless(raw.into())),
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl ServiceTypeDescription {
+    pub fn get_service_type_name(&self) -> Option<&WString> {
+        match self {
+            ServiceTypeDescription::Stateful(desc) => Some(&desc.service_type_name),
+            ServiceTypeDescription::Stateless(desc) => Some(&desc.service_type_name),
+            ServiceTypeDescription::Invalid => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatefulServiceTypeDescription {
+    pub service_type_name: WString,
+    pub placement_constraints: WString,
+    // TODO: load metrics and extensions
+    pub has_persisted_state: bool,
+}
+
+impl From<&FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION> for StatefulServiceTypeDescription {
+    fn from(value: &FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION) -> Self {
+        Self {
+            service_type_name: WString::from(value.ServiceTypeName),
+            placement_constraints: WString::from(value.PlacementConstraints),
+            has_persisted_state: value.HasPersistedState,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatelessServiceTypeDescription {
+    pub service_type_name: WString,
+    pub placement_constraints: WString,
+    // TODO: load metrics and extensions
+    pub use_implicit_host: bool,
+}
+
+impl From<&FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION> for StatelessServiceTypeDescription {
+    fn from(value: &FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION) -> Self {
+        Self {
+            service_type_name: WString::from(value.ServiceTypeName),
+            placement_constraints: WString::from(value.PlacementConstraints),
+            use_implicit_host: value.UseImplicitHost,
+        }
+    }
+}
+
 // FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION
 #[derive(Debug, Clone, Default)]
 pub struct ServiceHealthQueryDescription {
@@ -1337,3 +1855,136 @@ impl GetRaw<FABRIC_DELETE_SERVICE_DESCRIPTION> for DeleteServiceDescription {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::FABRIC_SERVICE_DESCRIPTION;
+    use windows_core::WString;
+
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::{
+            MoveCost, NamedPartitionSchemeDescription, PartitionSchemeDescription,
+            ServicePackageActivationMode, UniformIn64PartitionSchemeDescription, Uri,
+        },
+    };
+
+    use super::{
+        ServiceDescription, StatefulServiceDescription, StatelessServiceDescription,
+        StatelessServiceUpdateDescription, StatelessServiceUpdateDescriptionFlags,
+    };
+
+    fn roundtrip(desc: &ServiceDescription) -> ServiceDescription {
+        let mut pool = BoxPool::new();
+        let raw: FABRIC_SERVICE_DESCRIPTION = desc.get_raw_with_pool(&mut pool);
+        ServiceDescription::try_from(&raw).unwrap()
+    }
+
+    #[test]
+    fn test_stateful_service_description_raw_roundtrip() {
+        let desc = ServiceDescription::Stateful(
+            StatefulServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from("fabric:/app/svc"),
+                WString::from("SvcType"),
+                PartitionSchemeDescription::Int64Range(UniformIn64PartitionSchemeDescription::new(
+                    3, 0, 99,
+                )),
+            )
+            .with_min_replica_set_size(2)
+            .with_target_replica_set_size(3)
+            .with_has_persistent_state(true)
+            .with_default_move_cost(MoveCost::High)
+            .with_replica_restart_wait_duration_seconds(30)
+            .with_auxiliary_replica_count(1),
+        );
+        let ServiceDescription::Stateful(back) = roundtrip(&desc) else {
+            panic!("expected stateful");
+        };
+        assert_eq!(back.service_name(), &Uri::from("fabric:/app/svc"));
+        assert_eq!(back.service_type_name(), &WString::from("SvcType"));
+        assert_eq!(
+            back.partition_scheme(),
+            &PartitionSchemeDescription::Int64Range(UniformIn64PartitionSchemeDescription::new(
+                3, 0, 99
+            ))
+        );
+        assert_eq!(back.min_replica_set_size(), 2);
+        assert_eq!(back.target_replica_set_size(), 3);
+        assert!(back.has_persistent_state());
+        assert_eq!(back.default_move_cost(), Some(MoveCost::High));
+        assert_eq!(back.replica_restart_wait_duration_seconds(), Some(30));
+        assert_eq!(back.auxiliary_replica_count(), Some(1));
+        // Settings that were not set stay unspecified.
+        assert_eq!(back.quorum_loss_wait_duration_seconds(), None);
+        assert_eq!(back.drop_source_replica_on_move(), None);
+    }
+
+    #[test]
+    fn test_stateless_service_description_raw_roundtrip() {
+        let desc = ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from("fabric:/app/web"),
+                WString::from("WebType"),
+                PartitionSchemeDescription::Named(NamedPartitionSchemeDescription::new(vec![
+                    WString::from("a"),
+                    WString::from("b"),
+                ])),
+            )
+            .with_instance_count(-1)
+            .with_initialization_data(vec![7, 8])
+            .with_service_activation_mode(ServicePackageActivationMode::ExclusiveProcess),
+        );
+        let ServiceDescription::Stateless(back) = roundtrip(&desc) else {
+            panic!("expected stateless");
+        };
+        assert_eq!(back.instance_count, -1);
+        assert_eq!(back.initialization_data, Some(vec![7, 8]));
+        assert_eq!(
+            back.partition_scheme_description,
+            PartitionSchemeDescription::Named(NamedPartitionSchemeDescription::new(vec![
+                WString::from("a"),
+                WString::from("b"),
+            ]))
+        );
+        assert_eq!(back.default_move_cost(), None);
+        assert_eq!(
+            back.service_package_activation_mode(),
+            ServicePackageActivationMode::ExclusiveProcess
+        );
+    }
+
+    #[test]
+    fn test_stateless_service_update_description_raw() {
+        let update = StatelessServiceUpdateDescription::new()
+            .with_instance_count(5)
+            .with_move_cost(MoveCost::Medium);
+        assert_eq!(
+            update.flags(),
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT
+                | StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_MOVE_COST
+        );
+
+        let mut pool = BoxPool::new();
+        let raw = update.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.Flags, update.flags().bits());
+        assert_eq!(raw.InstanceCount, 5);
+        let ex1 = unsafe {
+            (raw.Reserved
+                as *const mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX1)
+                .as_ref()
+        }
+        .unwrap();
+        let ex2 = unsafe {
+            (ex1.Reserved
+                as *const mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX2)
+                .as_ref()
+        }
+        .unwrap();
+        assert_eq!(
+            ex2.DefaultMoveCost,
+            mssf_com::FabricTypes::FABRIC_MOVE_COST_MEDIUM
+        );
+    }
+}
@@ -0,0 +1,293 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use mssf_com::{
+    FabricClient::IFabricServiceGroupDescriptionResult,
+    FabricTypes::{
+        FABRIC_SERVICE_DESCRIPTION, FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL,
+        FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS, FABRIC_SERVICE_GROUP_DESCRIPTION,
+        FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION, FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION,
+        FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION, FABRIC_STATEFUL_SERVICE_DESCRIPTION,
+        FABRIC_STATELESS_SERVICE_DESCRIPTION,
+    },
+};
+use windows_core::WString;
+
+use crate::{
+    mem::{BoxPool, GetRawWithBoxPool},
+    types::{ServiceDescription, ServicePackageActivationMode, ServiceUpdateDescription, Uri},
+};
+
+// FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceGroupMemberDescription {
+    /// Member service type name as declared in the service group type.
+    pub service_type: WString,
+    /// Full member name, i.e. `fabric:/app/group#member`.
+    pub service_name: Uri,
+    pub initialization_data: Vec<u8>,
+    // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+}
+
+impl ServiceGroupMemberDescription {
+    pub fn new(service_type: WString, service_name: Uri) -> Self {
+        Self {
+            service_type,
+            service_name,
+            initialization_data: Vec::new(),
+        }
+    }
+
+    pub fn with_initialization_data(mut self, initialization_data: Vec<u8>) -> Self {
+        self.initialization_data = initialization_data;
+        self
+    }
+}
+
+impl From<&FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION> for ServiceGroupMemberDescription {
+    fn from(value: &FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION) -> Self {
+        let initialization_data = if value.InitializationData.is_null() {
+            Vec::new()
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(
+                    value.InitializationData,
+                    value.InitializationDataSize as usize,
+                )
+            }
+            .to_vec()
+        };
+        Self {
+            service_type: WString::from(value.ServiceType),
+            service_name: Uri::from(value.ServiceName),
+            initialization_data,
+        }
+    }
+}
+
+impl ServiceGroupMemberDescription {
+    fn get_raw(&self) -> FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION {
+        FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION {
+            ServiceType: self.service_type.as_pcwstr(),
+            ServiceName: self.service_name.as_raw(),
+            InitializationDataSize: self.initialization_data.len() as u32,
+            InitializationData: if self.initialization_data.is_empty() {
+                std::ptr::null()
+            } else {
+                self.initialization_data.as_ptr()
+            },
+            MetricCount: 0,
+            Metrics: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_GROUP_DESCRIPTION
+/// The service description carries the group as a whole: its name, the
+/// service group type and the partitioning. Members share the partitions of the group.
+pub struct ServiceGroupDescription {
+    pub description: ServiceDescription,
+    pub members: Vec<ServiceGroupMemberDescription>,
+}
+
+impl ServiceGroupDescription {
+    pub fn new(description: ServiceDescription) -> Self {
+        Self {
+            description,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn with_member(mut self, member: ServiceGroupMemberDescription) -> Self {
+        self.members.push(member);
+        self
+    }
+}
+
+impl GetRawWithBoxPool<FABRIC_SERVICE_GROUP_DESCRIPTION> for ServiceGroupDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_SERVICE_GROUP_DESCRIPTION {
+        let desc = self.description.get_raw_with_pool(pool);
+        let desc = pool.push(Box::new(desc));
+        let members = self.members.iter().map(|m| m.get_raw()).collect::<Vec<_>>();
+        let (member_count, members) = pool.push_vec(members);
+        FABRIC_SERVICE_GROUP_DESCRIPTION {
+            Description: desc as *mut _,
+            MemberCount: member_count as u32,
+            MemberDescriptions: members as *mut _,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION
+pub struct ServiceGroupUpdateDescription {
+    pub description: ServiceUpdateDescription,
+}
+
+impl GetRawWithBoxPool<FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION> for ServiceGroupUpdateDescription {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION {
+        let desc = self.description.get_raw_with_pool(pool);
+        let desc = pool.push(Box::new(desc));
+        FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION {
+            Description: desc as *mut _,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION
+/// Creates a service group from a service group template
+/// declared in the application manifest.
+#[derive(Debug, Clone)]
+pub struct ServiceGroupFromTemplateDescription {
+    pub application_name: Uri,
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub service_package_activation_mode: ServicePackageActivationMode,
+    pub initialization_data: Vec<u8>,
+}
+
+impl GetRawWithBoxPool<FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION>
+    for ServiceGroupFromTemplateDescription
+{
+    fn get_raw_with_pool(
+        &self,
+        _pool: &mut BoxPool,
+    ) -> FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION {
+        FABRIC_SERVICE_GROUP_FROM_TEMPLATE_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            ServiceName: self.service_name.as_raw(),
+            ServiceTypeName: self.service_type_name.as_pcwstr(),
+            ServicePackageActivationMode: self.service_package_activation_mode.into(),
+            InitializationDataSize: self.initialization_data.len() as u32,
+            InitializationData: if self.initialization_data.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                self.initialization_data.as_ptr() as *mut u8
+            },
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricServiceGroupDescriptionResult
+/// Service group as returned by the cluster.
+/// Only the identifying fields of the group service description are read.
+#[derive(Debug, Clone)]
+pub struct ServiceGroupDescriptionResult {
+    pub application_name: Uri,
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub is_stateful: bool,
+    pub members: Vec<ServiceGroupMemberDescription>,
+}
+
+impl From<&FABRIC_SERVICE_GROUP_DESCRIPTION> for ServiceGroupDescriptionResult {
+    fn from(value: &FABRIC_SERVICE_GROUP_DESCRIPTION) -> Self {
+        let (application_name, service_name, service_type_name, is_stateful) =
+            unsafe { value.Description.as_ref() }
+                .map(service_names_from_raw)
+                .unwrap_or_default();
+        Self {
+            application_name,
+            service_name,
+            service_type_name,
+            is_stateful,
+            members: crate::iter::vec_from_raw_com(
+                value.MemberCount as usize,
+                value.MemberDescriptions,
+            ),
+        }
+    }
+}
+
+impl From<&IFabricServiceGroupDescriptionResult> for ServiceGroupDescriptionResult {
+    fn from(value: &IFabricServiceGroupDescriptionResult) -> Self {
+        let raw = unsafe { value.get_Description().as_ref() }
+            .expect("service group description should not be null");
+        Self::from(raw)
+    }
+}
+
+fn service_names_from_raw(desc: &FABRIC_SERVICE_DESCRIPTION) -> (Uri, Uri, WString, bool) {
+    match desc.Kind {
+        FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL => {
+            unsafe { (desc.Value as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION).as_ref() }.map_or(
+                Default::default(),
+                |d| {
+                    (
+                        Uri::from(d.ApplicationName),
+                        Uri::from(d.ServiceName),
+                        WString::from(d.ServiceTypeName),
+                        true,
+                    )
+                },
+            )
+        }
+        FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS => {
+            unsafe { (desc.Value as *const FABRIC_STATELESS_SERVICE_DESCRIPTION).as_ref() }.map_or(
+                Default::default(),
+                |d| {
+                    (
+                        Uri::from(d.ApplicationName),
+                        Uri::from(d.ServiceName),
+                        WString::from(d.ServiceTypeName),
+                        false,
+                    )
+                },
+            )
+        }
+        _ => Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mem::{BoxPool, GetRawWithBoxPool},
+        types::{PartitionSchemeDescription, ServiceDescription, StatelessServiceDescription, Uri},
+    };
+
+    use windows_core::WString;
+
+    use super::{
+        ServiceGroupDescription, ServiceGroupDescriptionResult, ServiceGroupMemberDescription,
+    };
+
+    #[test]
+    fn test_service_group_description_raw_roundtrip() {
+        let group = ServiceGroupDescription::new(ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from("fabric:/app/group"),
+                "GroupType".into(),
+                PartitionSchemeDescription::Singleton,
+            ),
+        ))
+        .with_member(ServiceGroupMemberDescription::new(
+            "MemberAType".into(),
+            Uri::from("fabric:/app/group#a"),
+        ))
+        .with_member(
+            ServiceGroupMemberDescription::new(
+                "MemberBType".into(),
+                Uri::from("fabric:/app/group#b"),
+            )
+            .with_initialization_data(vec![1, 2, 3]),
+        );
+
+        let mut pool = BoxPool::new();
+        let raw = group.get_raw_with_pool(&mut pool);
+        assert_eq!(raw.MemberCount, 2);
+
+        let res = ServiceGroupDescriptionResult::from(&raw);
+        assert_eq!(res.application_name, Uri::from("fabric:/app"));
+        assert_eq!(res.service_name, Uri::from("fabric:/app/group"));
+        assert_eq!(res.service_type_name, WString::from("GroupType"));
+        assert!(!res.is_stateful);
+        assert_eq!(res.members, group.members);
+    }
+}
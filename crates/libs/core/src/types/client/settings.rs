@@ -2,8 +2,19 @@
 // Copyright (c) Microsoft Corporation.  All rights reserved.
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
+#![deny(unsafe_op_in_unsafe_fn)]
+#![deny(clippy::undocumented_unsafe_blocks)]
 
-use mssf_com::FabricClient::IFabricClientSettings2;
+use std::{ffi::c_void, num::NonZeroU32, ptr};
+
+use mssf_com::{
+    FabricClient::{IFabricClientSettings2, IFabricClientSettingsResult},
+    FabricTypes::{
+        FABRIC_CLIENT_SETTINGS, FABRIC_CLIENT_SETTINGS_EX1, FABRIC_CLIENT_SETTINGS_EX2,
+        FABRIC_CLIENT_SETTINGS_EX3, FABRIC_CLIENT_SETTINGS_EX4,
+    },
+};
+use windows_core::{PCWSTR, WString};
 
 /// A idiomatic Rust version of FABRIC_CLIENT_SETTINGS
 ///
@@ -13,19 +24,316 @@ use mssf_com::FabricClient::IFabricClientSettings2;
 /// # use std::num::NonZeroU32;
 /// # use mssf_core::types::FabricClientSettings;
 /// let my_settings = FabricClientSettings {
-///  // TODO: uncomment in next PR
-///  // PartitionLocationCacheLimit: Some(NonZeroU32::new(1).expect("Non-zero value")),
+///  PartitionLocationCacheLimit: Some(NonZeroU32::new(1).expect("Non-zero value")),
 ///  // Any other hypothetical settings you're interested in here,
 ///  ..Default::default()
 /// };
 /// ```
-#[derive(Default)]
-pub struct FabricClientSettings {}
+/// When applied to a client, None fields keep the value currently set on the client.
+/// When read back from a client, fields SF reports as 0 are None for the non-zero fields.
+#[allow(non_snake_case, reason = "Consistency with underlying API")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FabricClientSettings {
+    /// Max number of partitions cached by the client for service resolution.
+    pub PartitionLocationCacheLimit: Option<NonZeroU32>,
+    pub ServiceChangePollIntervalInSeconds: Option<NonZeroU32>,
+    pub ConnectionInitializationTimeoutInSeconds: Option<NonZeroU32>,
+    /// 0 disables keep alive.
+    pub KeepAliveIntervalInSeconds: Option<u32>,
+    pub HealthOperationTimeoutInSeconds: Option<NonZeroU32>,
+    /// 0 sends health reports as soon as they are added.
+    pub HealthReportSendIntervalInSeconds: Option<u32>,
+    // FABRIC_CLIENT_SETTINGS_EX1
+    pub ClientFriendlyName: Option<WString>,
+    pub PartitionLocationCacheBucketCount: Option<u32>,
+    pub HealthReportRetrySendIntervalInSeconds: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX2
+    pub NotificationGatewayConnectionTimeoutInSeconds: Option<NonZeroU32>,
+    pub NotificationCacheUpdateTimeoutInSeconds: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX3
+    pub AuthTokenBufferSize: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX4
+    /// 0 keeps idle connections open.
+    pub ConnectionIdleTimeoutInSeconds: Option<u32>,
+}
 
 impl FabricClientSettings {
     /// Note: only overrides non-default settings; leaves any settings set previously that don't explicitly have new values alone
-    pub(crate) fn apply(&self, _settings_interface: &IFabricClientSettings2) -> crate::Result<()> {
-        // Placeholder
-        Ok(())
+    pub(crate) fn apply(&self, settings_interface: &IFabricClientSettings2) -> crate::Result<()> {
+        // SF requires all settings to be set together, so start from the current ones.
+        let current = Self::get(settings_interface)?;
+        let merged = self.clone().or(current);
+        merged.with_raw(|raw| {
+            // SAFETY: COM interop. SetSettings does not retain reference to the passed in data after function returns.
+            unsafe { settings_interface.SetSettings(raw) }.map_err(crate::Error::from)
+        })
+    }
+
+    /// Reads the current settings of the client.
+    pub(crate) fn get(settings_interface: &IFabricClientSettings2) -> crate::Result<Self> {
+        // SAFETY: COM interop.
+        let result = unsafe { settings_interface.GetSettings() }?;
+        Ok(Self::from(&result))
+    }
+
+    /// Fills fields not set in self from other.
+    fn or(self, other: Self) -> Self {
+        Self {
+            PartitionLocationCacheLimit: self
+                .PartitionLocationCacheLimit
+                .or(other.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: self
+                .ServiceChangePollIntervalInSeconds
+                .or(other.ServiceChangePollIntervalInSeconds),
+            ConnectionInitializationTimeoutInSeconds: self
+                .ConnectionInitializationTimeoutInSeconds
+                .or(other.ConnectionInitializationTimeoutInSeconds),
+            KeepAliveIntervalInSeconds: self
+                .KeepAliveIntervalInSeconds
+                .or(other.KeepAliveIntervalInSeconds),
+            HealthOperationTimeoutInSeconds: self
+                .HealthOperationTimeoutInSeconds
+                .or(other.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: self
+                .HealthReportSendIntervalInSeconds
+                .or(other.HealthReportSendIntervalInSeconds),
+            ClientFriendlyName: self.ClientFriendlyName.or(other.ClientFriendlyName),
+            PartitionLocationCacheBucketCount: self
+                .PartitionLocationCacheBucketCount
+                .or(other.PartitionLocationCacheBucketCount),
+            HealthReportRetrySendIntervalInSeconds: self
+                .HealthReportRetrySendIntervalInSeconds
+                .or(other.HealthReportRetrySendIntervalInSeconds),
+            NotificationGatewayConnectionTimeoutInSeconds: self
+                .NotificationGatewayConnectionTimeoutInSeconds
+                .or(other.NotificationGatewayConnectionTimeoutInSeconds),
+            NotificationCacheUpdateTimeoutInSeconds: self
+                .NotificationCacheUpdateTimeoutInSeconds
+                .or(other.NotificationCacheUpdateTimeoutInSeconds),
+            AuthTokenBufferSize: self.AuthTokenBufferSize.or(other.AuthTokenBufferSize),
+            ConnectionIdleTimeoutInSeconds: self
+                .ConnectionIdleTimeoutInSeconds
+                .or(other.ConnectionIdleTimeoutInSeconds),
+        }
+    }
+
+    /// Builds the raw settings with the full ex chain, and passes it to f.
+    /// Unset fields are 0.
+    fn with_raw<R>(&self, f: impl FnOnce(&FABRIC_CLIENT_SETTINGS) -> R) -> R {
+        let nz = |v: Option<NonZeroU32>| v.map(NonZeroU32::get).unwrap_or(0);
+        let mut ex4 = FABRIC_CLIENT_SETTINGS_EX4 {
+            ConnectionIdleTimeoutInSeconds: self.ConnectionIdleTimeoutInSeconds.unwrap_or(0),
+            Reserved: ptr::null_mut(),
+        };
+        let mut ex3 = FABRIC_CLIENT_SETTINGS_EX3 {
+            AuthTokenBufferSize: nz(self.AuthTokenBufferSize),
+            Reserved: ptr::addr_of_mut!(ex4) as *mut c_void,
+        };
+        let mut ex2 = FABRIC_CLIENT_SETTINGS_EX2 {
+            NotificationGatewayConnectionTimeoutInSeconds: nz(
+                self.NotificationGatewayConnectionTimeoutInSeconds
+            ),
+            NotificationCacheUpdateTimeoutInSeconds: nz(
+                self.NotificationCacheUpdateTimeoutInSeconds
+            ),
+            Reserved: ptr::addr_of_mut!(ex3) as *mut c_void,
+        };
+        let mut ex1 = FABRIC_CLIENT_SETTINGS_EX1 {
+            ClientFriendlyName: self
+                .ClientFriendlyName
+                .as_ref()
+                .map(WString::as_pcwstr)
+                .unwrap_or(PCWSTR::null()),
+            PartitionLocationCacheBucketCount: self.PartitionLocationCacheBucketCount.unwrap_or(0),
+            HealthReportRetrySendIntervalInSeconds: nz(self.HealthReportRetrySendIntervalInSeconds),
+            Reserved: ptr::addr_of_mut!(ex2) as *mut c_void,
+        };
+        let raw = FABRIC_CLIENT_SETTINGS {
+            PartitionLocationCacheLimit: nz(self.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: nz(self.ServiceChangePollIntervalInSeconds),
+            ConnectionInitializationTimeoutInSeconds: nz(
+                self.ConnectionInitializationTimeoutInSeconds
+            ),
+            KeepAliveIntervalInSeconds: self.KeepAliveIntervalInSeconds.unwrap_or(0),
+            HealthOperationTimeoutInSeconds: nz(self.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: self.HealthReportSendIntervalInSeconds.unwrap_or(0),
+            Reserved: ptr::addr_of_mut!(ex1) as *mut c_void,
+        };
+        f(&raw)
+    }
+}
+
+impl From<&FABRIC_CLIENT_SETTINGS> for FabricClientSettings {
+    fn from(raw: &FABRIC_CLIENT_SETTINGS) -> Self {
+        let mut settings = Self {
+            PartitionLocationCacheLimit: NonZeroU32::new(raw.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: NonZeroU32::new(
+                raw.ServiceChangePollIntervalInSeconds,
+            ),
+            ConnectionInitializationTimeoutInSeconds: NonZeroU32::new(
+                raw.ConnectionInitializationTimeoutInSeconds,
+            ),
+            KeepAliveIntervalInSeconds: Some(raw.KeepAliveIntervalInSeconds),
+            HealthOperationTimeoutInSeconds: NonZeroU32::new(raw.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: Some(raw.HealthReportSendIntervalInSeconds),
+            ..Default::default()
+        };
+        // SAFETY: SF sets Reserved to null or a valid FABRIC_CLIENT_SETTINGS_EX1.
+        let Some(ex1) = (unsafe { (raw.Reserved as *const FABRIC_CLIENT_SETTINGS_EX1).as_ref() })
+        else {
+            return settings;
+        };
+        if !ex1.ClientFriendlyName.is_null() {
+            settings.ClientFriendlyName = Some(WString::from(ex1.ClientFriendlyName));
+        }
+        settings.PartitionLocationCacheBucketCount = Some(ex1.PartitionLocationCacheBucketCount);
+        settings.HealthReportRetrySendIntervalInSeconds =
+            NonZeroU32::new(ex1.HealthReportRetrySendIntervalInSeconds);
+        // SAFETY: same as above, for each extension in the chain.
+        let Some(ex2) = (unsafe { (ex1.Reserved as *const FABRIC_CLIENT_SETTINGS_EX2).as_ref() })
+        else {
+            return settings;
+        };
+        settings.NotificationGatewayConnectionTimeoutInSeconds =
+            NonZeroU32::new(ex2.NotificationGatewayConnectionTimeoutInSeconds);
+        settings.NotificationCacheUpdateTimeoutInSeconds =
+            NonZeroU32::new(ex2.NotificationCacheUpdateTimeoutInSeconds);
+        // SAFETY: same as above.
+        let Some(ex3) = (unsafe { (ex2.Reserved as *const FABRIC_CLIENT_SETTINGS_EX3).as_ref() })
+        else {
+            return settings;
+        };
+        settings.AuthTokenBufferSize = NonZeroU32::new(ex3.AuthTokenBufferSize);
+        // SAFETY: same as above.
+        let Some(ex4) = (unsafe { (ex3.Reserved as *const FABRIC_CLIENT_SETTINGS_EX4).as_ref() })
+        else {
+            return settings;
+        };
+        settings.ConnectionIdleTimeoutInSeconds = Some(ex4.ConnectionIdleTimeoutInSeconds);
+        settings
+    }
+}
+
+impl From<&IFabricClientSettingsResult> for FabricClientSettings {
+    fn from(com: &IFabricClientSettingsResult) -> Self {
+        // SAFETY: COM interop. The settings are valid as long as the result is alive.
+        let raw = unsafe { com.get_Settings().as_ref() }.expect("settings should not be null");
+        Self::from(raw)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        num::NonZeroU32,
+        sync::{Arc, Mutex},
+    };
+
+    use mssf_com::{
+        FabricClient::{
+            IFabricClientSettings2, IFabricClientSettingsResult, IFabricClientSettingsResult_Impl,
+        },
+        FabricTypes::{FABRIC_CLIENT_SETTINGS, FABRIC_E_INVALID_CONFIGURATION},
+    };
+    use windows_core::WString;
+
+    use super::FabricClientSettings;
+    use crate::types::mockifabricclientsettings::MockIFabricClientSettings;
+
+    fn make_settings() -> FabricClientSettings {
+        FabricClientSettings {
+            PartitionLocationCacheLimit: NonZeroU32::new(1000),
+            ServiceChangePollIntervalInSeconds: NonZeroU32::new(120),
+            ConnectionInitializationTimeoutInSeconds: NonZeroU32::new(2),
+            KeepAliveIntervalInSeconds: Some(0),
+            HealthOperationTimeoutInSeconds: NonZeroU32::new(120),
+            HealthReportSendIntervalInSeconds: Some(30),
+            ClientFriendlyName: Some(WString::from("test_client")),
+            PartitionLocationCacheBucketCount: Some(1024),
+            HealthReportRetrySendIntervalInSeconds: NonZeroU32::new(30),
+            NotificationGatewayConnectionTimeoutInSeconds: NonZeroU32::new(30),
+            NotificationCacheUpdateTimeoutInSeconds: NonZeroU32::new(30),
+            AuthTokenBufferSize: NonZeroU32::new(4096),
+            ConnectionIdleTimeoutInSeconds: Some(0),
+        }
+    }
+
+    /// Returns the settings in raw form, like SF does.
+    #[windows_core::implement(IFabricClientSettingsResult)]
+    struct SettingsResultMock {
+        raw: Box<FABRIC_CLIENT_SETTINGS>,
+    }
+
+    impl IFabricClientSettingsResult_Impl for SettingsResultMock_Impl {
+        fn get_Settings(&self) -> *mut FABRIC_CLIENT_SETTINGS {
+            self.raw.as_ref() as *const _ as *mut _
+        }
+    }
+
+    #[test]
+    fn client_settings_raw_round_trip() {
+        let settings = make_settings();
+        let res = settings.with_raw(|raw| FabricClientSettings::from(raw));
+        assert_eq!(res, settings);
+
+        // Unset fields are reported back as 0.
+        let res = FabricClientSettings::default().with_raw(|raw| FabricClientSettings::from(raw));
+        assert_eq!(res.PartitionLocationCacheLimit, None);
+        assert_eq!(res.KeepAliveIntervalInSeconds, Some(0));
+        assert_eq!(res.ClientFriendlyName, None);
+    }
+
+    #[test]
+    fn client_settings_apply_failure() {
+        let mock = IFabricClientSettings2::from(MockIFabricClientSettings::new_all_methods_fail());
+        let result = make_settings().apply(&mock);
+        assert_eq!(
+            result,
+            Err(crate::Error::from(FABRIC_E_INVALID_CONFIGURATION))
+        );
+    }
+
+    #[test]
+    fn client_settings_apply_merges_current() {
+        let mut mock = MockIFabricClientSettings::new_all_methods_fail();
+        mock.get_settings_mock = Box::new(|| {
+            // The ex chain lives on the stack, so only the head is returned,
+            // like an older SF version without extensions.
+            let raw = make_settings().with_raw(|raw| FABRIC_CLIENT_SETTINGS {
+                Reserved: std::ptr::null_mut(),
+                ..*raw
+            });
+            Ok(SettingsResultMock { raw: Box::new(raw) }.into())
+        });
+        let applied = Arc::new(Mutex::new(None));
+        let applied_copy = Arc::clone(&applied);
+        mock.set_settings_mock = Box::new(move |raw: *const FABRIC_CLIENT_SETTINGS| {
+            assert!(!raw.is_null() && raw.is_aligned());
+            // SAFETY: test code. non-null and alignment is checked above
+            let raw = unsafe { raw.as_ref() }.unwrap();
+            *applied_copy.lock().unwrap() = Some(FabricClientSettings::from(raw));
+            Ok(())
+        });
+        let mock = IFabricClientSettings2::from(mock);
+
+        let desired = FabricClientSettings {
+            PartitionLocationCacheLimit: NonZeroU32::new(1),
+            AuthTokenBufferSize: NonZeroU32::new(1),
+            ..Default::default()
+        };
+        desired.apply(&mock).unwrap();
+        let applied = applied.lock().unwrap().take().unwrap();
+        assert_eq!(applied.PartitionLocationCacheLimit, NonZeroU32::new(1));
+        assert_eq!(applied.AuthTokenBufferSize, NonZeroU32::new(1));
+        // Other settings keep the current values.
+        let current = make_settings();
+        assert_eq!(
+            applied.ServiceChangePollIntervalInSeconds,
+            current.ServiceChangePollIntervalInSeconds
+        );
+        assert_eq!(
+            applied.HealthReportSendIntervalInSeconds,
+            current.HealthReportSendIntervalInSeconds
+        );
     }
 }
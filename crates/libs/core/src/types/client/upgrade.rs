@@ -0,0 +1,214 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Upgrade types shared by application and cluster upgrades.
+
+use std::ffi::c_void;
+
+use mssf_com::FabricTypes::{
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION, FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_INVALID,
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL,
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK, FABRIC_ROLLING_UPGRADE_MODE,
+    FABRIC_ROLLING_UPGRADE_MODE_INVALID, FABRIC_ROLLING_UPGRADE_MODE_MONITORED,
+    FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO, FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_DEFERRED,
+    FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL, FABRIC_ROLLING_UPGRADE_MONITORING_POLICY,
+    FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1, FABRIC_UPGRADE_DOMAIN_STATE,
+    FABRIC_UPGRADE_DOMAIN_STATE_COMPLETED, FABRIC_UPGRADE_DOMAIN_STATE_IN_PROGRESS,
+    FABRIC_UPGRADE_DOMAIN_STATE_PENDING, FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION,
+};
+use windows_core::WString;
+
+use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+// FABRIC_ROLLING_UPGRADE_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollingUpgradeMode {
+    Invalid,
+    UnmonitoredAuto,
+    #[default]
+    UnmonitoredManual,
+    Monitored,
+    UnmonitoredDeferred,
+}
+
+impl From<FABRIC_ROLLING_UPGRADE_MODE> for RollingUpgradeMode {
+    fn from(value: FABRIC_ROLLING_UPGRADE_MODE) -> Self {
+        match value {
+            FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO => Self::UnmonitoredAuto,
+            FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL => Self::UnmonitoredManual,
+            FABRIC_ROLLING_UPGRADE_MODE_MONITORED => Self::Monitored,
+            FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_DEFERRED => Self::UnmonitoredDeferred,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RollingUpgradeMode> for FABRIC_ROLLING_UPGRADE_MODE {
+    fn from(value: RollingUpgradeMode) -> Self {
+        match value {
+            RollingUpgradeMode::Invalid => FABRIC_ROLLING_UPGRADE_MODE_INVALID,
+            RollingUpgradeMode::UnmonitoredAuto => FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO,
+            RollingUpgradeMode::UnmonitoredManual => FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL,
+            RollingUpgradeMode::Monitored => FABRIC_ROLLING_UPGRADE_MODE_MONITORED,
+            RollingUpgradeMode::UnmonitoredDeferred => {
+                FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_DEFERRED
+            }
+        }
+    }
+}
+
+// FABRIC_MONITORED_UPGRADE_FAILURE_ACTION
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonitoredUpgradeFailureAction {
+    Invalid,
+    #[default]
+    Rollback,
+    Manual,
+}
+
+impl From<FABRIC_MONITORED_UPGRADE_FAILURE_ACTION> for MonitoredUpgradeFailureAction {
+    fn from(value: FABRIC_MONITORED_UPGRADE_FAILURE_ACTION) -> Self {
+        match value {
+            FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK => Self::Rollback,
+            FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL => Self::Manual,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<MonitoredUpgradeFailureAction> for FABRIC_MONITORED_UPGRADE_FAILURE_ACTION {
+    fn from(value: MonitoredUpgradeFailureAction) -> Self {
+        match value {
+            MonitoredUpgradeFailureAction::Invalid => {
+                FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_INVALID
+            }
+            MonitoredUpgradeFailureAction::Rollback => {
+                FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK
+            }
+            MonitoredUpgradeFailureAction::Manual => FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL,
+        }
+    }
+}
+
+// FABRIC_ROLLING_UPGRADE_MONITORING_POLICY
+/// Health checks performed after each upgrade domain in monitored mode.
+/// Durations are in seconds.
+#[derive(Debug, Clone, Default)]
+pub struct RollingUpgradeMonitoringPolicy {
+    pub failure_action: MonitoredUpgradeFailureAction,
+    pub health_check_wait_duration_seconds: u32,
+    pub health_check_retry_timeout_seconds: u32,
+    pub upgrade_timeout_seconds: u32,
+    pub upgrade_domain_timeout_seconds: u32,
+    // EX1
+    pub health_check_stable_duration_seconds: u32,
+}
+
+impl GetRawWithBoxPool<FABRIC_ROLLING_UPGRADE_MONITORING_POLICY>
+    for RollingUpgradeMonitoringPolicy
+{
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_ROLLING_UPGRADE_MONITORING_POLICY {
+        let ex1 = pool.push(Box::new(FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1 {
+            HealthCheckStableDurationInSeconds: self.health_check_stable_duration_seconds,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_ROLLING_UPGRADE_MONITORING_POLICY {
+            FailureAction: self.failure_action.into(),
+            HealthCheckWaitDurationInSeconds: self.health_check_wait_duration_seconds,
+            HealthCheckRetryTimeoutInSeconds: self.health_check_retry_timeout_seconds,
+            UpgradeTimeoutInSeconds: self.upgrade_timeout_seconds,
+            UpgradeDomainTimeoutInSeconds: self.upgrade_domain_timeout_seconds,
+            Reserved: ex1 as *mut c_void,
+        }
+    }
+}
+
+// FABRIC_UPGRADE_DOMAIN_STATE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeDomainState {
+    Invalid,
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl From<FABRIC_UPGRADE_DOMAIN_STATE> for UpgradeDomainState {
+    fn from(value: FABRIC_UPGRADE_DOMAIN_STATE) -> Self {
+        match value {
+            FABRIC_UPGRADE_DOMAIN_STATE_PENDING => Self::Pending,
+            FABRIC_UPGRADE_DOMAIN_STATE_IN_PROGRESS => Self::InProgress,
+            FABRIC_UPGRADE_DOMAIN_STATE_COMPLETED => Self::Completed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeDomainStatus {
+    pub name: WString,
+    pub state: UpgradeDomainState,
+}
+
+impl From<&FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION> for UpgradeDomainStatus {
+    fn from(value: &FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION) -> Self {
+        Self {
+            name: WString::from(value.Name),
+            state: value.State.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL, FABRIC_ROLLING_UPGRADE_MODE,
+        FABRIC_ROLLING_UPGRADE_MODE_MONITORED, FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1,
+    };
+
+    use super::{
+        MonitoredUpgradeFailureAction, RollingUpgradeMode, RollingUpgradeMonitoringPolicy,
+    };
+    use crate::mem::{BoxPool, GetRawWithBoxPool};
+
+    #[test]
+    fn test_upgrade_enums() {
+        assert_eq!(
+            RollingUpgradeMode::from(FABRIC_ROLLING_UPGRADE_MODE_MONITORED),
+            RollingUpgradeMode::Monitored
+        );
+        for mode in [
+            RollingUpgradeMode::UnmonitoredAuto,
+            RollingUpgradeMode::UnmonitoredManual,
+            RollingUpgradeMode::Monitored,
+            RollingUpgradeMode::UnmonitoredDeferred,
+        ] {
+            let raw: FABRIC_ROLLING_UPGRADE_MODE = mode.into();
+            assert_eq!(RollingUpgradeMode::from(raw), mode);
+        }
+    }
+
+    #[test]
+    fn test_monitoring_policy_raw() {
+        let policy = RollingUpgradeMonitoringPolicy {
+            failure_action: MonitoredUpgradeFailureAction::Manual,
+            upgrade_timeout_seconds: 100,
+            health_check_stable_duration_seconds: 10,
+            ..Default::default()
+        };
+        let mut pool = BoxPool::new();
+        let raw = policy.get_raw_with_pool(&mut pool);
+        assert_eq!(
+            raw.FailureAction,
+            FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL
+        );
+        assert_eq!(raw.UpgradeTimeoutInSeconds, 100);
+        let ex1 = unsafe {
+            (raw.Reserved as *const FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1).as_ref()
+        }
+        .unwrap();
+        assert_eq!(ex1.HealthCheckStableDurationInSeconds, 10);
+    }
+}
@@ -208,6 +208,14 @@ impl UniformIn64PartitionSchemeDescription {
     }
 }
 
+impl PartialEq for UniformIn64PartitionSchemeDescription {
+    fn eq(&self, other: &Self) -> bool {
+        self.internal.PartitionCount == other.internal.PartitionCount
+            && self.internal.LowKey == other.internal.LowKey
+            && self.internal.HighKey == other.internal.HighKey
+    }
+}
+
 impl Clone for UniformIn64PartitionSchemeDescription {
     fn clone(&self) -> Self {
         Self::new(
@@ -257,6 +265,12 @@ impl NamedPartitionSchemeDescription {
     }
 }
 
+impl PartialEq for NamedPartitionSchemeDescription {
+    fn eq(&self, other: &Self) -> bool {
+        self._names == other._names
+    }
+}
+
 impl Clone for NamedPartitionSchemeDescription {
     fn clone(&self) -> Self {
         Self::new(self._names.clone())
@@ -271,6 +285,15 @@ pub enum ServicePackageActivationMode {
     ExclusiveProcess,
 }
 
+impl From<FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE> for ServicePackageActivationMode {
+    fn from(value: FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE) -> Self {
+        match value {
+            FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE_EXCLUSIVE_PROCESS => Self::ExclusiveProcess,
+            _ => Self::SharedProcess,
+        }
+    }
+}
+
 impl From<ServicePackageActivationMode> for FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE {
     fn from(mode: ServicePackageActivationMode) -> Self {
         match mode {
@@ -284,7 +307,7 @@ impl From<ServicePackageActivationMode> for FABRIC_SERVICE_PACKAGE_ACTIVATION_MO
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum PartitionSchemeDescription {
     #[default]
     Invalid,
@@ -294,6 +317,41 @@ pub enum PartitionSchemeDescription {
 }
 
 impl PartitionSchemeDescription {
+    /// Copies the scheme from the raw kind and description pointer.
+    /// Requires `desc` to point to the description of `scheme`, or to be null.
+    pub(crate) fn from_raw(scheme: FABRIC_PARTITION_SCHEME, desc: *const c_void) -> Self {
+        match scheme {
+            FABRIC_PARTITION_SCHEME_SINGLETON => Self::Singleton,
+            FABRIC_PARTITION_SCHEME_UNIFORM_INT64_RANGE => unsafe {
+                (desc as *const FABRIC_UNIFORM_INT64_RANGE_PARTITION_SCHEME_DESCRIPTION).as_ref()
+            }
+            .map_or(Self::Invalid, |d| {
+                Self::Int64Range(UniformIn64PartitionSchemeDescription::new(
+                    d.PartitionCount,
+                    d.LowKey,
+                    d.HighKey,
+                ))
+            }),
+            FABRIC_PARTITION_SCHEME_NAMED => {
+                unsafe { (desc as *const FABRIC_NAMED_PARTITION_SCHEME_DESCRIPTION).as_ref() }
+                    .map_or(Self::Invalid, |d| {
+                        let names = if d.Names.is_null() {
+                            Vec::new()
+                        } else {
+                            unsafe {
+                                std::slice::from_raw_parts(d.Names, d.PartitionCount as usize)
+                            }
+                            .iter()
+                            .map(WString::from)
+                            .collect()
+                        };
+                        Self::Named(NamedPartitionSchemeDescription::new(names))
+                    })
+            }
+            _ => Self::Invalid,
+        }
+    }
+
     /// Needs to have lifetime as self. Can be moved.
     pub(crate) fn as_raw(&self) -> (FABRIC_PARTITION_SCHEME, *mut c_void) {
         match self {
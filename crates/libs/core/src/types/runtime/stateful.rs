@@ -9,9 +9,14 @@ use std::{ffi::c_void, marker::PhantomData};
 
 use crate::{PCWSTR, WString};
 use mssf_com::FabricTypes::{
-    FABRIC_EPOCH, FABRIC_REPLICA_INFORMATION, FABRIC_REPLICA_INFORMATION_EX1,
-    FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_OPEN_MODE_EXISTING, FABRIC_REPLICA_OPEN_MODE_INVALID,
-    FABRIC_REPLICA_OPEN_MODE_NEW, FABRIC_REPLICA_SET_CONFIGURATION, FABRIC_REPLICA_SET_QUORUM_ALL,
+    FABRIC_EPOCH, FABRIC_OPERATION_METADATA, FABRIC_OPERATION_TYPE,
+    FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION, FABRIC_OPERATION_TYPE_COMMIT_ATOMIC_GROUP,
+    FABRIC_OPERATION_TYPE_CREATE_ATOMIC_GROUP, FABRIC_OPERATION_TYPE_END_OF_STREAM,
+    FABRIC_OPERATION_TYPE_INVALID, FABRIC_OPERATION_TYPE_NORMAL,
+    FABRIC_OPERATION_TYPE_ROLLBACK_ATOMIC_GROUP, FABRIC_REPLICA_INFORMATION,
+    FABRIC_REPLICA_INFORMATION_EX1, FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_OPEN_MODE_EXISTING,
+    FABRIC_REPLICA_OPEN_MODE_INVALID, FABRIC_REPLICA_OPEN_MODE_NEW,
+    FABRIC_REPLICA_SET_CONFIGURATION, FABRIC_REPLICA_SET_QUORUM_ALL,
     FABRIC_REPLICA_SET_QUORUM_INVALID, FABRIC_REPLICA_SET_QUORUM_MODE,
     FABRIC_REPLICA_SET_WRITE_QUORUM, FABRIC_REPLICA_STATUS, FABRIC_REPLICA_STATUS_DOWN,
     FABRIC_REPLICA_STATUS_INVALID, FABRIC_REPLICA_STATUS_UP,
@@ -297,17 +302,90 @@ impl From<ReplicaSetQuorumMode> for FABRIC_REPLICA_SET_QUORUM_MODE {
     }
 }
 
+// FABRIC_OPERATION_TYPE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    Invalid,
+    Normal,
+    EndOfStream,
+    CreateAtomicGroup,
+    AtomicGroupOperation,
+    CommitAtomicGroup,
+    RollbackAtomicGroup,
+}
+
+impl From<FABRIC_OPERATION_TYPE> for OperationType {
+    fn from(value: FABRIC_OPERATION_TYPE) -> Self {
+        match value {
+            FABRIC_OPERATION_TYPE_NORMAL => Self::Normal,
+            FABRIC_OPERATION_TYPE_END_OF_STREAM => Self::EndOfStream,
+            FABRIC_OPERATION_TYPE_CREATE_ATOMIC_GROUP => Self::CreateAtomicGroup,
+            FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION => Self::AtomicGroupOperation,
+            FABRIC_OPERATION_TYPE_COMMIT_ATOMIC_GROUP => Self::CommitAtomicGroup,
+            FABRIC_OPERATION_TYPE_ROLLBACK_ATOMIC_GROUP => Self::RollbackAtomicGroup,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<OperationType> for FABRIC_OPERATION_TYPE {
+    fn from(value: OperationType) -> Self {
+        match value {
+            OperationType::Invalid => FABRIC_OPERATION_TYPE_INVALID,
+            OperationType::Normal => FABRIC_OPERATION_TYPE_NORMAL,
+            OperationType::EndOfStream => FABRIC_OPERATION_TYPE_END_OF_STREAM,
+            OperationType::CreateAtomicGroup => FABRIC_OPERATION_TYPE_CREATE_ATOMIC_GROUP,
+            OperationType::AtomicGroupOperation => FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION,
+            OperationType::CommitAtomicGroup => FABRIC_OPERATION_TYPE_COMMIT_ATOMIC_GROUP,
+            OperationType::RollbackAtomicGroup => FABRIC_OPERATION_TYPE_ROLLBACK_ATOMIC_GROUP,
+        }
+    }
+}
+
+/// Safe wrapping for FABRIC_OPERATION_METADATA.
+/// Describes an operation received from the replication or copy stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationMetadata {
+    pub r#type: OperationType,
+    /// The LSN assigned to the operation by the primary.
+    pub sequence_number: i64,
+    /// Identifier of the atomic group the operation belongs to, if any.
+    pub atomic_group_id: i64,
+}
+
+impl From<&FABRIC_OPERATION_METADATA> for OperationMetadata {
+    fn from(value: &FABRIC_OPERATION_METADATA) -> Self {
+        Self {
+            r#type: value.Type.into(),
+            sequence_number: value.SequenceNumber,
+            atomic_group_id: value.AtomicGroupId,
+        }
+    }
+}
+
+impl From<&OperationMetadata> for FABRIC_OPERATION_METADATA {
+    fn from(value: &OperationMetadata) -> Self {
+        Self {
+            Type: value.r#type.into(),
+            SequenceNumber: value.sequence_number,
+            AtomicGroupId: value.atomic_group_id,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::ffi::c_void;
 
     use crate::WString;
     use mssf_com::FabricTypes::{
-        FABRIC_REPLICA_INFORMATION, FABRIC_REPLICA_INFORMATION_EX1, FABRIC_REPLICA_ROLE_PRIMARY,
-        FABRIC_REPLICA_STATUS_UP,
+        FABRIC_OPERATION_METADATA, FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION,
+        FABRIC_OPERATION_TYPE_HAS_ATOMIC_GROUP_MASK, FABRIC_REPLICA_INFORMATION,
+        FABRIC_REPLICA_INFORMATION_EX1, FABRIC_REPLICA_ROLE_PRIMARY, FABRIC_REPLICA_STATUS_UP,
     };
 
-    use super::{Epoch, ReplicaInformation, ReplicaSetConfig};
+    use super::{Epoch, OperationMetadata, OperationType, ReplicaInformation, ReplicaSetConfig};
 
     // caller needs to stitch the reserved ptr.
     fn create_test_data(id: i64) -> (FABRIC_REPLICA_INFORMATION, FABRIC_REPLICA_INFORMATION_EX1) {
@@ -385,6 +463,24 @@ mod test {
         assert_eq!(&replica2, replica2_b);
     }
 
+    #[test]
+    fn test_operation_metadata_conv() {
+        let meta = OperationMetadata {
+            r#type: OperationType::AtomicGroupOperation,
+            sequence_number: 42,
+            atomic_group_id: 7,
+        };
+        let raw = FABRIC_OPERATION_METADATA::from(&meta);
+        assert_eq!(raw.Type, FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION);
+        assert_eq!(OperationMetadata::from(&raw), meta);
+
+        // unknown or mask values are not valid operation types.
+        assert_eq!(
+            OperationType::from(FABRIC_OPERATION_TYPE_HAS_ATOMIC_GROUP_MASK),
+            OperationType::Invalid
+        );
+    }
+
     #[test]
     fn test_epoch_cmp() {
         assert!(Epoch::new(1, 2) < Epoch::new(1, 3));
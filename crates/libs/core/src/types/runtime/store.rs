@@ -3,18 +3,30 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::PCWSTR;
+use std::ffi::c_void;
+
+use crate::{
+    PCWSTR,
+    mem::{BoxPool, GetRawWithBoxPool},
+};
 use mssf_com::FabricTypes::{
-    FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND, FABRIC_LOCAL_STORE_KIND_ESE,
-    FABRIC_LOCAL_STORE_KIND_INVALID, FABRIC_REPLICATOR_SETTINGS,
-    FABRIC_TRANSACTION_ISOLATION_LEVEL, FABRIC_TRANSACTION_ISOLATION_LEVEL_DEFAULT,
-    FABRIC_TRANSACTION_ISOLATION_LEVEL_READ_COMMITTED,
+    FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE,
+    FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_BLOCK_SECONDARY_ACK,
+    FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_INVALID,
+    FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NON_BLOCKING_QUORUM_ACKED,
+    FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NONE, FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS,
+    FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1, FABRIC_LOCAL_STORE_KIND,
+    FABRIC_LOCAL_STORE_KIND_ESE, FABRIC_LOCAL_STORE_KIND_INVALID, FABRIC_REPLICATOR_SETTINGS,
+    FABRIC_STORE_BACKUP_INFO, FABRIC_STORE_BACKUP_INFO_EX1, FABRIC_STORE_BACKUP_OPTION,
+    FABRIC_STORE_BACKUP_OPTION_FULL, FABRIC_STORE_BACKUP_OPTION_INCREMENTAL,
+    FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY, FABRIC_TRANSACTION_ISOLATION_LEVEL,
+    FABRIC_TRANSACTION_ISOLATION_LEVEL_DEFAULT, FABRIC_TRANSACTION_ISOLATION_LEVEL_READ_COMMITTED,
     FABRIC_TRANSACTION_ISOLATION_LEVEL_READ_UNCOMMITTED,
     FABRIC_TRANSACTION_ISOLATION_LEVEL_REPEATABLE_READ,
     FABRIC_TRANSACTION_ISOLATION_LEVEL_SERIALIZABLE, FABRIC_TRANSACTION_ISOLATION_LEVEL_SNAPSHOT,
 };
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ReplicatorSettings {
     pub flags: u32,
     pub retry_interval_milliseconds: u32,
@@ -48,6 +60,23 @@ impl ReplicatorSettings {
     }
 }
 
+impl From<&FABRIC_REPLICATOR_SETTINGS> for ReplicatorSettings {
+    fn from(value: &FABRIC_REPLICATOR_SETTINGS) -> Self {
+        Self {
+            flags: value.Flags,
+            retry_interval_milliseconds: value.RetryIntervalMilliseconds,
+            batch_acknowledgement_interval_milliseconds: value
+                .BatchAcknowledgementIntervalMilliseconds,
+            replicator_address: crate::WString::from(value.ReplicatorAddress),
+            require_service_ack: value.RequireServiceAck,
+            initial_replication_queue_size: value.InitialReplicationQueueSize,
+            max_replication_queue_size: value.MaxReplicationQueueSize,
+            initial_copy_queue_size: value.InitialCopyQueueSize,
+            max_copy_queue_size: value.MaxCopyQueueSize,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LocalStoreKind {
     Ese,
@@ -63,6 +92,53 @@ impl From<LocalStoreKind> for FABRIC_LOCAL_STORE_KIND {
     }
 }
 
+/// Controls how the key value store secondary replica notifies
+/// the secondary event handler of replication operations.
+// FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyValueStoreNotificationMode {
+    Invalid,
+    /// No notifications.
+    None,
+    /// Notifications are sent after the operation is quorum acked, and
+    /// the secondary does not wait for the handler to return.
+    NonBlockingQuorumAcked,
+    /// The secondary acks the operation after the handler returns.
+    BlockSecondaryAck,
+}
+
+impl From<KeyValueStoreNotificationMode> for FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE {
+    fn from(value: KeyValueStoreNotificationMode) -> Self {
+        match value {
+            KeyValueStoreNotificationMode::Invalid => {
+                FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_INVALID
+            }
+            KeyValueStoreNotificationMode::None => FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NONE,
+            KeyValueStoreNotificationMode::NonBlockingQuorumAcked => {
+                FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NON_BLOCKING_QUORUM_ACKED
+            }
+            KeyValueStoreNotificationMode::BlockSecondaryAck => {
+                FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_BLOCK_SECONDARY_ACK
+            }
+        }
+    }
+}
+
+impl From<FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE> for KeyValueStoreNotificationMode {
+    fn from(value: FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE) -> Self {
+        match value {
+            FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NONE => KeyValueStoreNotificationMode::None,
+            FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_NON_BLOCKING_QUORUM_ACKED => {
+                KeyValueStoreNotificationMode::NonBlockingQuorumAcked
+            }
+            FABRIC_KEY_VALUE_STORE_NOTIFICATION_MODE_BLOCK_SECONDARY_ACK => {
+                KeyValueStoreNotificationMode::BlockSecondaryAck
+            }
+            _ => KeyValueStoreNotificationMode::Invalid,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct EseLocalStoreSettings {
     // FABRIC_ESE_LOCAL_STORE_SETTINGS
@@ -89,6 +165,88 @@ impl EseLocalStoreSettings {
     }
 }
 
+// FABRIC_STORE_BACKUP_OPTION
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreBackupOption {
+    Invalid,
+    /// Full backup of the store.
+    Full,
+    /// Backup of the logs since the last backup. Requires a prior full backup.
+    Incremental,
+    /// Truncates the logs without taking a backup.
+    TruncateLogsOnly,
+}
+
+impl From<StoreBackupOption> for FABRIC_STORE_BACKUP_OPTION {
+    fn from(value: StoreBackupOption) -> Self {
+        match value {
+            StoreBackupOption::Invalid => FABRIC_STORE_BACKUP_OPTION(0),
+            StoreBackupOption::Full => FABRIC_STORE_BACKUP_OPTION_FULL,
+            StoreBackupOption::Incremental => FABRIC_STORE_BACKUP_OPTION_INCREMENTAL,
+            StoreBackupOption::TruncateLogsOnly => FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY,
+        }
+    }
+}
+
+impl From<FABRIC_STORE_BACKUP_OPTION> for StoreBackupOption {
+    fn from(value: FABRIC_STORE_BACKUP_OPTION) -> Self {
+        match value {
+            FABRIC_STORE_BACKUP_OPTION_FULL => StoreBackupOption::Full,
+            FABRIC_STORE_BACKUP_OPTION_INCREMENTAL => StoreBackupOption::Incremental,
+            FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY => StoreBackupOption::TruncateLogsOnly,
+            _ => StoreBackupOption::Invalid,
+        }
+    }
+}
+
+/// Information of a completed store backup, passed to the post backup handler.
+// FABRIC_STORE_BACKUP_INFO
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreBackupInfo {
+    pub backup_folder: crate::WString,
+    pub backup_option: StoreBackupOption,
+    // FABRIC_STORE_BACKUP_INFO_EX1
+    /// Identifies the chain of a full backup and its incremental backups.
+    pub backup_chain_id: Option<crate::GUID>,
+    /// Index of the backup in the chain. Full backup has index 0.
+    pub backup_index: Option<u32>,
+}
+
+impl From<&FABRIC_STORE_BACKUP_INFO> for StoreBackupInfo {
+    fn from(value: &FABRIC_STORE_BACKUP_INFO) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_STORE_BACKUP_INFO_EX1).as_ref() };
+        Self {
+            backup_folder: crate::WString::from(value.BackupFolder),
+            backup_option: value.BackupOption.into(),
+            backup_chain_id: ex1.map(|e| e.BackupChainId),
+            backup_index: ex1.map(|e| e.BackupIndex),
+        }
+    }
+}
+
+// FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValueStoreRestoreSettings {
+    /// Restores without restarting the replica.
+    pub inline_reopen: bool,
+    // FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1
+    /// Fails the restore if the backup has a lower lsn than the current store.
+    pub enable_lsn_check: bool,
+}
+
+impl GetRawWithBoxPool<FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS> for KeyValueStoreRestoreSettings {
+    fn get_raw_with_pool(&self, pool: &mut BoxPool) -> FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS {
+        let ex1 = pool.push(Box::new(FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1 {
+            EnableLsnCheck: self.enable_lsn_check,
+            Reserved: std::ptr::null_mut(),
+        }));
+        FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS {
+            InlineReopen: self.inline_reopen,
+            Reserved: ex1 as *const _ as *mut c_void,
+        }
+    }
+}
+
 // FABRIC_TRANSACTION_ISOLATION_LEVEL
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionIsolationLevel {
@@ -121,3 +279,37 @@ impl From<FABRIC_TRANSACTION_ISOLATION_LEVEL> for TransactionIsolationLevel {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mssf_com::FabricTypes::{
+        FABRIC_STORE_BACKUP_INFO, FABRIC_STORE_BACKUP_INFO_EX1,
+        FABRIC_STORE_BACKUP_OPTION_INCREMENTAL,
+    };
+
+    use super::{StoreBackupInfo, StoreBackupOption};
+
+    #[test]
+    fn test_store_backup_info_conv() {
+        let folder = crate::WString::from("C:\\backup");
+        let mut ex1 = FABRIC_STORE_BACKUP_INFO_EX1 {
+            BackupChainId: crate::GUID::from_u128(1),
+            BackupIndex: 2,
+            Reserved: std::ptr::null_mut(),
+        };
+        let mut raw = FABRIC_STORE_BACKUP_INFO {
+            BackupFolder: crate::PCWSTR::from_raw(folder.as_ptr()),
+            BackupOption: FABRIC_STORE_BACKUP_OPTION_INCREMENTAL,
+            Reserved: std::ptr::null_mut(),
+        };
+        let info = StoreBackupInfo::from(&raw);
+        assert_eq!(info.backup_folder, folder);
+        assert_eq!(info.backup_option, StoreBackupOption::Incremental);
+        assert_eq!(info.backup_chain_id, None);
+
+        raw.Reserved = &mut ex1 as *mut _ as *mut std::ffi::c_void;
+        let info = StoreBackupInfo::from(&raw);
+        assert_eq!(info.backup_chain_id, Some(crate::GUID::from_u128(1)));
+        assert_eq!(info.backup_index, Some(2));
+    }
+}
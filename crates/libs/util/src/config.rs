@@ -0,0 +1,258 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Watch configuration package changes as parsed settings.
+
+use std::sync::Arc;
+
+use mssf_core::{
+    WString,
+    runtime::{
+        CodePackageActivationContext,
+        config::ConfigurationSettings,
+        package_change::{
+            PackageChangeEvent, config::AutoConfigurationPackageChangeCallbackHandle,
+        },
+    },
+};
+use tokio::sync::watch;
+
+/// A parameter that differs between two versions of the settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterChange {
+    pub section: WString,
+    pub name: WString,
+    /// None if the parameter was added.
+    pub previous_value: Option<WString>,
+    /// None if the parameter was removed.
+    pub new_value: Option<WString>,
+}
+
+/// Parameters changed between two versions of the settings.
+/// Parameters of added or removed sections are reported as added or removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigurationDiff {
+    pub changes: Vec<ParameterChange>,
+}
+
+impl ConfigurationDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether the parameter changed.
+    pub fn contains(&self, section: &WString, name: &WString) -> bool {
+        self.changes
+            .iter()
+            .any(|c| &c.section == section && &c.name == name)
+    }
+
+    /// Whether any parameter of the section changed.
+    pub fn contains_section(&self, section: &WString) -> bool {
+        self.changes.iter().any(|c| &c.section == section)
+    }
+}
+
+fn find_value<'a>(
+    settings: &'a ConfigurationSettings,
+    section: &WString,
+    name: &WString,
+) -> Option<&'a WString> {
+    settings
+        .sections
+        .iter()
+        .find(|s| &s.name == section)?
+        .parameters
+        .iter()
+        .find(|p| &p.name == name)
+        .map(|p| &p.value)
+}
+
+/// Compares the parameter values of two versions of the settings.
+/// Encrypted values are compared as they are, without decrypting.
+pub fn diff_settings(
+    previous: &ConfigurationSettings,
+    new: &ConfigurationSettings,
+) -> ConfigurationDiff {
+    let mut changes = Vec::new();
+    for section in &new.sections {
+        for param in &section.parameters {
+            let previous_value = find_value(previous, &section.name, &param.name);
+            if previous_value != Some(&param.value) {
+                changes.push(ParameterChange {
+                    section: section.name.clone(),
+                    name: param.name.clone(),
+                    previous_value: previous_value.cloned(),
+                    new_value: Some(param.value.clone()),
+                });
+            }
+        }
+    }
+    for section in &previous.sections {
+        for param in &section.parameters {
+            if find_value(new, &section.name, &param.name).is_none() {
+                changes.push(ParameterChange {
+                    section: section.name.clone(),
+                    name: param.name.clone(),
+                    previous_value: Some(param.value.clone()),
+                    new_value: None,
+                });
+            }
+        }
+    }
+    ConfigurationDiff { changes }
+}
+
+/// Settings of a configuration package, updated when the package is
+/// added or modified. The change handler is unregistered on drop.
+pub struct ConfigurationWatch {
+    receiver: watch::Receiver<Arc<ConfigurationSettings>>,
+    current: Arc<ConfigurationSettings>,
+    _handle: AutoConfigurationPackageChangeCallbackHandle,
+}
+
+impl ConfigurationWatch {
+    /// The settings last returned by `changed`, or the initial settings.
+    pub fn current(&self) -> Arc<ConfigurationSettings> {
+        self.current.clone()
+    }
+
+    /// A receiver of the latest settings, for use in other tasks.
+    pub fn receiver(&self) -> watch::Receiver<Arc<ConfigurationSettings>> {
+        self.receiver.clone()
+    }
+
+    /// Waits for the package to change and returns the parameters that changed
+    /// since the current settings. Changes that leave all values the same are skipped.
+    /// Returns None if the handler is no longer registered.
+    pub async fn changed(&mut self) -> Option<ConfigurationDiff> {
+        loop {
+            self.receiver.changed().await.ok()?;
+            let new = self.receiver.borrow_and_update().clone();
+            let diff = diff_settings(&self.current, &new);
+            self.current = new;
+            if !diff.is_empty() {
+                return Some(diff);
+            }
+        }
+    }
+}
+
+/// Watches the settings of the configuration package with the given name.
+/// Removal of the package is ignored and the last settings are kept.
+pub fn watch_configuration(
+    activation_ctx: &CodePackageActivationContext,
+    package_name: &WString,
+) -> mssf_core::Result<ConfigurationWatch> {
+    // The handler is registered before the settings are read, so that a change
+    // in between is not lost. The placeholder is replaced by whichever comes first.
+    let placeholder = Arc::new(ConfigurationSettings {
+        sections: Vec::new(),
+    });
+    let (sender, mut receiver) = watch::channel(placeholder.clone());
+    let sender = Arc::new(sender);
+    let handler_sender = sender.clone();
+    let name = package_name.clone();
+    let handle = AutoConfigurationPackageChangeCallbackHandle::new(activation_ctx, move |event| {
+        let new_package = match event {
+            PackageChangeEvent::Addition { new_package }
+            | PackageChangeEvent::Modification { new_package, .. } => new_package,
+            PackageChangeEvent::Removal { .. } => return,
+        };
+        if new_package.get_description().name == name {
+            handler_sender.send_replace(Arc::new(new_package.get_settings()));
+        }
+    })?;
+    let settings = Arc::new(
+        activation_ctx
+            .get_configuration_package(package_name)?
+            .get_settings(),
+    );
+    sender.send_if_modified(|value| {
+        let seed = Arc::ptr_eq(value, &placeholder);
+        if seed {
+            *value = settings;
+        }
+        seed
+    });
+    let current = receiver.borrow_and_update().clone();
+    Ok(ConfigurationWatch {
+        receiver,
+        current,
+        _handle: handle,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_core::{
+        WString,
+        runtime::config::{ConfigurationParameter, ConfigurationSection, ConfigurationSettings},
+    };
+
+    use super::{ParameterChange, diff_settings};
+
+    fn section(name: &str, params: &[(&str, &str)]) -> ConfigurationSection {
+        ConfigurationSection {
+            name: name.into(),
+            parameters: params
+                .iter()
+                .map(|(name, value)| ConfigurationParameter {
+                    is_encrypted: false,
+                    must_overrride: false,
+                    name: (*name).into(),
+                    value: (*value).into(),
+                    r#type: WString::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_settings() {
+        let previous = ConfigurationSettings {
+            sections: vec![
+                section("Model", &[("Path", "v1"), ("Threshold", "0.5")]),
+                section("Old", &[("Key", "a")]),
+            ],
+        };
+        let new = ConfigurationSettings {
+            sections: vec![
+                section("Model", &[("Path", "v2"), ("Threshold", "0.5")]),
+                section("New", &[("Key", "b")]),
+            ],
+        };
+
+        let diff = diff_settings(&previous, &new);
+        assert_eq!(
+            diff.changes,
+            vec![
+                ParameterChange {
+                    section: "Model".into(),
+                    name: "Path".into(),
+                    previous_value: Some("v1".into()),
+                    new_value: Some("v2".into()),
+                },
+                ParameterChange {
+                    section: "New".into(),
+                    name: "Key".into(),
+                    previous_value: None,
+                    new_value: Some("b".into()),
+                },
+                ParameterChange {
+                    section: "Old".into(),
+                    name: "Key".into(),
+                    previous_value: Some("a".into()),
+                    new_value: None,
+                },
+            ]
+        );
+        assert!(diff.contains(&"Model".into(), &"Path".into()));
+        assert!(!diff.contains(&"Model".into(), &"Threshold".into()));
+        assert!(diff.contains_section(&"Old".into()));
+
+        assert!(diff_settings(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Batched health reporting for watchdogs.
+//!
+//! A watchdog typically re-evaluates its checks in a tight loop and reports the
+//! result every time. Sending all of these to the health store is wasteful.
+//! [`HealthReporter`] keeps the last report per (entity, source, property):
+//! - a report equal to the last one is dropped,
+//! - reports for the same key within one flush interval are coalesced,
+//!   and only the latest is sent,
+//! - sequence numbers are assigned on send and always increase,
+//! - reports with a TTL are resent before they expire,
+//!   so a steady state does not need to be reported again.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use mssf_core::{
+    GUID,
+    client::health_client::HealthClient,
+    runtime::executor::BoxedCancelToken,
+    types::{HealthInformation, HealthReport, HealthReportSendOption, SequenceNumber},
+};
+
+/// Default interval between flushes.
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Destination of the reports.
+/// Implemented for [`HealthClient`]; tests can provide a fake.
+pub trait HealthReportSink: Send + Sync {
+    fn report_health(
+        &self,
+        report: &HealthReport,
+        send_options: Option<&HealthReportSendOption>,
+    ) -> mssf_core::Result<()>;
+}
+
+impl HealthReportSink for HealthClient {
+    fn report_health(
+        &self,
+        report: &HealthReport,
+        send_options: Option<&HealthReportSendOption>,
+    ) -> mssf_core::Result<()> {
+        self.report_health_with_options(report, send_options)
+    }
+}
+
+/// Entity a report is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum EntityKey {
+    Invalid,
+    StatefulServiceReplica(GUID, i64),
+    StatelessServiceInstance(GUID, i64),
+    Partition(GUID),
+    Node(String),
+    Service(String),
+    Application(String),
+    DeployedApplication(String, String),
+    DeployedServicePackage(String, String, String),
+    Cluster,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReportKey {
+    entity: EntityKey,
+    source_id: String,
+    property: String,
+}
+
+fn health_information(report: &HealthReport) -> Option<&HealthInformation> {
+    match report {
+        HealthReport::Invalid => None,
+        HealthReport::StatefulServiceReplica(r) => Some(&r.health_information),
+        HealthReport::StatelessServiceInstance(r) => Some(&r.health_information),
+        HealthReport::Partition(r) => Some(&r.health_information),
+        HealthReport::Node(r) => Some(&r.health_information),
+        HealthReport::Service(r) => Some(&r.health_information),
+        HealthReport::Application(r) => Some(&r.health_information),
+        HealthReport::DeployedApplication(r) => Some(&r.health_information),
+        HealthReport::DeployedServicePackage(r) => Some(&r.health_information),
+        HealthReport::Cluster(r) => Some(&r.health_information),
+    }
+}
+
+fn health_information_mut(report: &mut HealthReport) -> Option<&mut HealthInformation> {
+    match report {
+        HealthReport::Invalid => None,
+        HealthReport::StatefulServiceReplica(r) => Some(&mut r.health_information),
+        HealthReport::StatelessServiceInstance(r) => Some(&mut r.health_information),
+        HealthReport::Partition(r) => Some(&mut r.health_information),
+        HealthReport::Node(r) => Some(&mut r.health_information),
+        HealthReport::Service(r) => Some(&mut r.health_information),
+        HealthReport::Application(r) => Some(&mut r.health_information),
+        HealthReport::DeployedApplication(r) => Some(&mut r.health_information),
+        HealthReport::DeployedServicePackage(r) => Some(&mut r.health_information),
+        HealthReport::Cluster(r) => Some(&mut r.health_information),
+    }
+}
+
+fn report_key(report: &HealthReport) -> Option<ReportKey> {
+    let entity = match report {
+        HealthReport::Invalid => EntityKey::Invalid,
+        HealthReport::StatefulServiceReplica(r) => {
+            EntityKey::StatefulServiceReplica(r.partition_id, r.replica_id)
+        }
+        HealthReport::StatelessServiceInstance(r) => {
+            EntityKey::StatelessServiceInstance(r.partition_id, r.instance_id)
+        }
+        HealthReport::Partition(r) => EntityKey::Partition(r.partition_id),
+        HealthReport::Node(r) => EntityKey::Node(r.node_name.to_string()),
+        HealthReport::Service(r) => EntityKey::Service(r.service_name.to_string()),
+        HealthReport::Application(r) => EntityKey::Application(r.application_name.to_string()),
+        HealthReport::DeployedApplication(r) => {
+            EntityKey::DeployedApplication(r.application_name.to_string(), r.node_name.to_string())
+        }
+        HealthReport::DeployedServicePackage(r) => EntityKey::DeployedServicePackage(
+            r.application_name.to_string(),
+            r.service_manifest_name.to_string(),
+            r.node_name.to_string(),
+        ),
+        HealthReport::Cluster(_) => EntityKey::Cluster,
+    };
+    let info = health_information(report)?;
+    Some(ReportKey {
+        entity,
+        source_id: info.source_id.to_string(),
+        property: info.property.to_string(),
+    })
+}
+
+/// Same content, ignoring the sequence number.
+fn same_content(a: &HealthInformation, b: &HealthInformation) -> bool {
+    a.state == b.state
+        && a.description == b.description
+        && a.time_to_live_seconds == b.time_to_live_seconds
+        && a.remove_when_expired == b.remove_when_expired
+}
+
+struct Entry {
+    report: HealthReport,
+    /// Changed since the last send.
+    pending: bool,
+    last_sent: Option<Instant>,
+}
+
+impl Entry {
+    /// Reports are resent when half of the TTL has passed.
+    fn is_due(&self, now: Instant) -> bool {
+        if self.pending {
+            return true;
+        }
+        let ttl = health_information(&self.report).map_or(0, |i| i.time_to_live_seconds);
+        // 0 is FABRIC_HEALTH_REPORT_INFINITE_TTL, and u32::MAX is also treated as infinite.
+        if ttl == 0 || ttl == u32::MAX {
+            return false;
+        }
+        self.last_sent
+            .is_none_or(|t| now.duration_since(t) >= Duration::from_secs(ttl as u64) / 2)
+    }
+}
+
+struct State {
+    entries: HashMap<ReportKey, Entry>,
+    next_sequence_number: SequenceNumber,
+}
+
+/// Coalesces, deduplicates and refreshes health reports.
+/// Reports are queued with [`HealthReporter::report`] and sent by
+/// [`HealthReporter::run`], or by calling [`HealthReporter::flush`].
+pub struct HealthReporter<S> {
+    sink: S,
+    state: Mutex<State>,
+    flush_interval: Duration,
+    send_options: Option<HealthReportSendOption>,
+}
+
+impl<S: HealthReportSink> HealthReporter<S> {
+    pub fn new(sink: S) -> Self {
+        // Seed from the clock so that reports from a restarted process are not stale.
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            sink,
+            state: Mutex::new(State {
+                entries: HashMap::new(),
+                next_sequence_number: (now.as_nanos() / 100) as SequenceNumber,
+            }),
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            send_options: None,
+        }
+    }
+
+    /// Interval between flushes in [`HealthReporter::run`].
+    /// This bounds how often a report for the same key is sent.
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Send options passed with every report.
+    pub fn with_send_options(mut self, send_options: HealthReportSendOption) -> Self {
+        self.send_options = Some(send_options);
+        self
+    }
+
+    /// Queues a report. The sequence number of the report is ignored.
+    /// Returns false if the report is dropped, either because it is the same as
+    /// the last report for its entity, source and property, or because it is invalid.
+    pub fn report(&self, report: HealthReport) -> bool {
+        let Some(key) = report_key(&report) else {
+            return false;
+        };
+        let mut state = self.state.lock().unwrap();
+        match state.entries.get_mut(&key) {
+            Some(entry) => {
+                let unchanged = matches!(
+                    (health_information(&entry.report), health_information(&report)),
+                    (Some(a), Some(b)) if same_content(a, b)
+                );
+                if unchanged {
+                    return false;
+                }
+                entry.report = report;
+                entry.pending = true;
+            }
+            None => {
+                state.entries.insert(
+                    key,
+                    Entry {
+                        report,
+                        pending: true,
+                        last_sent: None,
+                    },
+                );
+            }
+        }
+        true
+    }
+
+    /// Stops tracking the entity, source and property of the report.
+    /// Nothing is sent; the last sent report stays until its TTL expires.
+    pub fn remove(&self, report: &HealthReport) {
+        if let Some(key) = report_key(report) {
+            self.state.lock().unwrap().entries.remove(&key);
+        }
+    }
+
+    /// Sends the changed reports and the reports due for a TTL refresh.
+    /// Failed reports are retried on the next flush.
+    /// Returns the number of reports sent.
+    pub fn flush(&self) -> usize {
+        self.flush_at(Instant::now())
+    }
+
+    fn flush_at(&self, now: Instant) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let State {
+            entries,
+            next_sequence_number,
+        } = &mut *state;
+        let mut sent = 0;
+        for entry in entries.values_mut().filter(|e| e.is_due(now)) {
+            if let Some(info) = health_information_mut(&mut entry.report) {
+                info.sequence_number = *next_sequence_number;
+            }
+            *next_sequence_number += 1;
+            match self
+                .sink
+                .report_health(&entry.report, self.send_options.as_ref())
+            {
+                Ok(()) => {
+                    entry.pending = false;
+                    entry.last_sent = Some(now);
+                    sent += 1;
+                }
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("Failed to send health report: {_e}");
+                    entry.pending = true;
+                }
+            }
+        }
+        sent
+    }
+
+    /// Flushes on the flush interval until cancelled.
+    /// Pending reports are flushed once more before returning.
+    pub async fn run(&self, token: BoxedCancelToken) {
+        loop {
+            self.flush();
+            tokio::select! {
+                _ = token.wait() => break,
+                _ = tokio::time::sleep(self.flush_interval) => {}
+            }
+        }
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Mutex,
+        time::{Duration, Instant},
+    };
+
+    use mssf_core::{
+        ErrorCode, WString,
+        types::{
+            HealthInformation, HealthReport, HealthReportSendOption, HealthState, NodeHealthReport,
+        },
+    };
+
+    use super::{HealthReportSink, HealthReporter};
+
+    #[derive(Default)]
+    struct FakeSink {
+        sent: Mutex<Vec<HealthReport>>,
+        fail: Mutex<bool>,
+    }
+
+    impl HealthReportSink for &FakeSink {
+        fn report_health(
+            &self,
+            report: &HealthReport,
+            _send_options: Option<&HealthReportSendOption>,
+        ) -> mssf_core::Result<()> {
+            if *self.fail.lock().unwrap() {
+                return Err(ErrorCode::FABRIC_E_HEALTH_MAX_REPORTS_REACHED.into());
+            }
+            self.sent.lock().unwrap().push(report.clone());
+            Ok(())
+        }
+    }
+
+    impl FakeSink {
+        /// (node, property, state, sequence number) of the sent reports.
+        fn take(&self) -> Vec<(String, String, HealthState, i64)> {
+            self.sent
+                .lock()
+                .unwrap()
+                .drain(..)
+                .map(|r| match r {
+                    HealthReport::Node(r) => (
+                        r.node_name.to_string(),
+                        r.health_information.property.to_string(),
+                        r.health_information.state,
+                        r.health_information.sequence_number,
+                    ),
+                    _ => panic!("unexpected report"),
+                })
+                .collect()
+        }
+    }
+
+    fn node_report(node: &str, property: &str, state: HealthState, ttl: u32) -> HealthReport {
+        HealthReport::Node(NodeHealthReport {
+            node_name: WString::from(node),
+            health_information: HealthInformation {
+                source_id: WString::from("watchdog"),
+                property: WString::from(property),
+                time_to_live_seconds: ttl,
+                state,
+                description: WString::new(),
+                sequence_number: 0,
+                remove_when_expired: false,
+            },
+        })
+    }
+
+    #[test]
+    fn test_reporter_coalesces_and_drops_duplicates() {
+        let sink = FakeSink::default();
+        let reporter = HealthReporter::new(&sink);
+
+        assert!(reporter.report(node_report("n1", "disk", HealthState::Ok, 0)));
+        // Same content as the queued report.
+        assert!(!reporter.report(node_report("n1", "disk", HealthState::Ok, 0)));
+        // Replaces the queued report for the same key.
+        assert!(reporter.report(node_report("n1", "disk", HealthState::Warning, 0)));
+        assert!(reporter.report(node_report("n2", "disk", HealthState::Ok, 0)));
+
+        let now = Instant::now();
+        assert_eq!(reporter.flush_at(now), 2);
+        let mut sent = sink.take();
+        sent.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(sent[0].2, HealthState::Warning);
+        assert_eq!(sent[1].0, "n2");
+        assert_ne!(sent[0].3, sent[1].3);
+
+        // Nothing changed, and infinite TTL needs no refresh.
+        assert!(!reporter.report(node_report("n1", "disk", HealthState::Warning, 0)));
+        assert_eq!(reporter.flush_at(now + Duration::from_secs(3600)), 0);
+
+        // A change gets a higher sequence number.
+        let max_seq = sent.iter().map(|s| s.3).max().unwrap();
+        assert!(reporter.report(node_report("n1", "disk", HealthState::Ok, 0)));
+        assert_eq!(reporter.flush_at(now), 1);
+        assert!(sink.take()[0].3 > max_seq);
+    }
+
+    #[test]
+    fn test_reporter_refreshes_ttl() {
+        let sink = FakeSink::default();
+        let reporter = HealthReporter::new(&sink);
+        reporter.report(node_report("n1", "cpu", HealthState::Ok, 60));
+
+        let now = Instant::now();
+        assert_eq!(reporter.flush_at(now), 1);
+        let first = sink.take()[0].3;
+        assert_eq!(reporter.flush_at(now + Duration::from_secs(10)), 0);
+        assert_eq!(reporter.flush_at(now + Duration::from_secs(30)), 1);
+        assert!(sink.take()[0].3 > first);
+
+        // Removed reports are no longer refreshed.
+        reporter.remove(&node_report("n1", "cpu", HealthState::Ok, 60));
+        assert_eq!(reporter.flush_at(now + Duration::from_secs(120)), 0);
+    }
+
+    #[test]
+    fn test_reporter_retries_failed_reports() {
+        let sink = FakeSink::default();
+        let reporter = HealthReporter::new(&sink);
+        reporter.report(node_report("n1", "cpu", HealthState::Error, 0));
+
+        *sink.fail.lock().unwrap() = true;
+        assert_eq!(reporter.flush(), 0);
+        *sink.fail.lock().unwrap() = false;
+        assert_eq!(reporter.flush(), 1);
+        assert_eq!(sink.take()[0].2, HealthState::Error);
+    }
+}
@@ -18,9 +18,23 @@ pub mod retry;
 #[cfg(feature = "tokio")]
 pub mod monitoring;
 
+#[cfg(feature = "tokio")]
+pub mod query;
+
+#[cfg(feature = "tokio")]
+pub mod reconcile;
+
+#[cfg(feature = "tokio")]
+pub mod health;
+
+#[cfg(feature = "tokio")]
+pub mod config;
+
 pub mod data;
 
 pub mod mock;
 
+pub mod store;
+
 #[cfg(feature = "tonic")]
 pub mod tonic;
@@ -16,3 +16,6 @@ mod stateful;
 pub use stateful::{
     CreateStatefulServicePartitionArg, StatefulServicePartitionDriver, StatefulServicePartitionMock,
 };
+
+mod state_provider;
+pub use state_provider::{OperationDataStreamMock, StateProviderMock};
@@ -0,0 +1,255 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use mssf_core::{
+    runtime::{
+        IOperationDataStream, IStateProvider, executor::BoxedCancelToken,
+        state_replicator::OperationDataStream,
+    },
+    types::Epoch,
+};
+
+/// Operation data stream returning buffers from a queue.
+pub struct OperationDataStreamMock {
+    items: Mutex<VecDeque<Vec<Vec<u8>>>>,
+}
+
+impl OperationDataStreamMock {
+    pub fn new(items: Vec<Vec<Vec<u8>>>) -> Self {
+        Self {
+            items: Mutex::new(items.into()),
+        }
+    }
+}
+
+#[mssf_core::async_trait]
+impl IOperationDataStream for OperationDataStreamMock {
+    async fn get_next(&self, _: BoxedCancelToken) -> mssf_core::Result<Option<Vec<Vec<u8>>>> {
+        Ok(self.items.lock().unwrap().pop_front())
+    }
+}
+
+#[derive(Default)]
+struct StateProviderState {
+    epoch: Option<Epoch>,
+    // committed operations keyed by LSN.
+    operations: BTreeMap<i64, Vec<Vec<u8>>>,
+    data_loss_count: usize,
+}
+
+/// In memory state provider.
+/// The state is a list of operations keyed by LSN.
+/// The copy context is the last committed LSN of the secondary, and the copy state
+/// contains the operations after it, each prefixed with a buffer of the LSN in
+/// little endian bytes.
+#[derive(Clone, Default)]
+pub struct StateProviderMock {
+    state: Arc<Mutex<StateProviderState>>,
+}
+
+impl StateProviderMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the operation as committed.
+    pub fn apply(&self, sequence_number: i64, data: Vec<Vec<u8>>) {
+        self.state
+            .lock()
+            .unwrap()
+            .operations
+            .insert(sequence_number, data);
+    }
+
+    /// Applies a copy operation produced by get_copy_state.
+    pub fn apply_copy(&self, mut data: Vec<Vec<u8>>) -> mssf_core::Result<()> {
+        if data.is_empty() {
+            return Err(mssf_core::ErrorCode::E_INVALIDARG.into());
+        }
+        let lsn = data.remove(0);
+        let lsn = i64::from_le_bytes(
+            lsn.try_into()
+                .map_err(|_| mssf_core::Error::from(mssf_core::ErrorCode::E_INVALIDARG))?,
+        );
+        self.apply(lsn, data);
+        Ok(())
+    }
+
+    pub fn get_operation(&self, sequence_number: i64) -> Option<Vec<Vec<u8>>> {
+        self.state
+            .lock()
+            .unwrap()
+            .operations
+            .get(&sequence_number)
+            .cloned()
+    }
+
+    pub fn epoch(&self) -> Option<Epoch> {
+        self.state.lock().unwrap().epoch.clone()
+    }
+
+    pub fn data_loss_count(&self) -> usize {
+        self.state.lock().unwrap().data_loss_count
+    }
+
+    fn last_committed(&self) -> i64 {
+        self.state
+            .lock()
+            .unwrap()
+            .operations
+            .last_key_value()
+            .map(|(k, _)| *k)
+            .unwrap_or(0)
+    }
+}
+
+/// Copy state stream that reads the secondary copy context lazily.
+struct CopyStateStream {
+    provider: StateProviderMock,
+    up_to_sequence_number: i64,
+    copy_context: Option<OperationDataStream>,
+    // Remaining operations, filled on first read.
+    pending: Mutex<Option<VecDeque<Vec<Vec<u8>>>>>,
+}
+
+#[mssf_core::async_trait]
+impl IOperationDataStream for CopyStateStream {
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> mssf_core::Result<Option<Vec<Vec<u8>>>> {
+        if self.pending.lock().unwrap().is_none() {
+            let mut start = 0;
+            if let Some(ctx) = &self.copy_context
+                && let Some(data) = ctx.get_next(Some(cancellation_token)).await?
+                && let Some(lsn) = data.first()
+            {
+                start = i64::from_le_bytes(
+                    lsn.as_slice()
+                        .try_into()
+                        .map_err(|_| mssf_core::Error::from(mssf_core::ErrorCode::E_INVALIDARG))?,
+                );
+            }
+            let state = self.provider.state.lock().unwrap();
+            let items = state
+                .operations
+                .range(start + 1..=self.up_to_sequence_number)
+                .map(|(lsn, data)| {
+                    let mut buffers = vec![lsn.to_le_bytes().to_vec()];
+                    buffers.extend(data.iter().cloned());
+                    buffers
+                })
+                .collect();
+            *self.pending.lock().unwrap() = Some(items);
+        }
+        Ok(self
+            .pending
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|p| p.pop_front()))
+    }
+}
+
+#[mssf_core::async_trait]
+impl IStateProvider for StateProviderMock {
+    async fn update_epoch(
+        &self,
+        epoch: Epoch,
+        previous_epoch_last_sequence_number: i64,
+        _: BoxedCancelToken,
+    ) -> mssf_core::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        // Discard operations from the old primary that are not part of the new epoch.
+        state
+            .operations
+            .retain(|lsn, _| *lsn <= previous_epoch_last_sequence_number);
+        state.epoch = Some(epoch);
+        Ok(())
+    }
+
+    fn get_last_committed_sequence_number(&self) -> mssf_core::Result<i64> {
+        Ok(self.last_committed())
+    }
+
+    async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<u8> {
+        self.state.lock().unwrap().data_loss_count += 1;
+        Ok(0)
+    }
+
+    fn get_copy_context(&self) -> mssf_core::Result<Option<Box<dyn IOperationDataStream>>> {
+        Ok(Some(Box::new(OperationDataStreamMock::new(vec![vec![
+            self.last_committed().to_le_bytes().to_vec(),
+        ]]))))
+    }
+
+    fn get_copy_state(
+        &self,
+        up_to_sequence_number: i64,
+        copy_context_stream: Option<OperationDataStream>,
+    ) -> mssf_core::Result<Box<dyn IOperationDataStream>> {
+        Ok(Box::new(CopyStateStream {
+            provider: self.clone(),
+            up_to_sequence_number,
+            copy_context: copy_context_stream,
+            pending: Mutex::new(None),
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod test {
+    use mssf_com::FabricRuntime::{IFabricOperationDataStream, IFabricStateProvider};
+    use mssf_core::runtime::{
+        state_replicator::OperationDataStream, stateful_bridge::IFabricStateProviderBridge,
+    };
+    use tokio::runtime::Handle;
+
+    use super::StateProviderMock;
+    use crate::tokio::TokioExecutor;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_state_provider_copy() {
+        let rt = TokioExecutor::new(Handle::current());
+
+        let primary = StateProviderMock::new();
+        primary.apply(1, vec![b"a".to_vec()]);
+        primary.apply(2, vec![b"b".to_vec(), b"c".to_vec()]);
+        primary.apply(3, vec![b"d".to_vec()]);
+        let secondary = StateProviderMock::new();
+        secondary.apply(1, vec![b"a".to_vec()]);
+
+        let primary_com: IFabricStateProvider =
+            IFabricStateProviderBridge::create(std::sync::Arc::new(primary), rt.clone()).into();
+        let secondary_com: IFabricStateProvider =
+            IFabricStateProviderBridge::create(std::sync::Arc::new(secondary.clone()), rt).into();
+
+        assert_eq!(
+            unsafe { primary_com.GetLastCommittedSequenceNumber() }.unwrap(),
+            3
+        );
+
+        // Copy from primary up to LSN 2, starting from the secondary progress.
+        let ctx = unsafe { secondary_com.GetCopyContext() }.unwrap();
+        let copy: OperationDataStream = unsafe { primary_com.GetCopyState(2, &ctx) }
+            .map(|s: IFabricOperationDataStream| s.into())
+            .unwrap();
+        while let Some(data) = copy.get_next(None).await.unwrap() {
+            secondary.apply_copy(data).unwrap();
+        }
+        assert_eq!(
+            secondary.get_operation(2),
+            Some(vec![b"b".to_vec(), b"c".to_vec()])
+        );
+        assert_eq!(secondary.get_operation(3), None);
+        // End of stream is sticky.
+        assert!(copy.get_next(None).await.unwrap().is_none());
+    }
+}
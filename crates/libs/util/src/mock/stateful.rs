@@ -50,7 +50,12 @@ impl StatefulServicePartitionMock {
 impl IStatefulServicePartition for StatefulServicePartitionMock {
     fn create_replicator(
         &self,
-    ) -> mssf_core::Result<Box<dyn mssf_core::runtime::IPrimaryReplicator>> {
+        _state_provider: std::sync::Arc<dyn mssf_core::runtime::IStateProvider>,
+        _replicator_settings: Option<&mssf_core::types::ReplicatorSettings>,
+    ) -> mssf_core::Result<(
+        Box<dyn mssf_core::runtime::IPrimaryReplicator>,
+        mssf_core::runtime::state_replicator::StateReplicator,
+    )> {
         unimplemented!("Not implemented")
     }
 
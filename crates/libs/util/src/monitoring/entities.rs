@@ -3,10 +3,15 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use mssf_core::types::{
-    ApplicationHealth, ApplicationQueryResultItem, ClusterHealth, NodeHealthResult,
-    NodeQueryResultItem, PartitionHealthResult, ReplicaHealthResult, ServiceHealthResult,
-    ServicePartitionQueryResultItem, ServiceQueryResultItem, ServiceReplicaQueryResultItem, Uri,
+use mssf_core::{
+    GUID, WString,
+    types::{
+        ApplicationHealth, ApplicationQueryResultItem, ClusterHealth, DeployedApplicationHealth,
+        DeployedServicePackageHealth, HealthState, NodeHealthResult, NodeQueryResultItem,
+        PartitionHealthResult, ReplicaHealthResult, ServiceHealthResult,
+        ServicePartitionQueryResultItem, ServiceQueryResultItem, ServiceReplicaQueryResultItem,
+        Uri,
+    },
 };
 
 /// Events produced by HealthDataProducer.
@@ -21,6 +26,10 @@ pub enum ProducerEvent {
     Partition(PartitionHealthEntity),
     Service(ServiceHealthEntity),
     Replica(ReplicaHealthEntity),
+    DeployedApplication(DeployedApplicationHealthEntity),
+    DeployedServicePackage(DeployedServicePackageHealthEntity),
+    /// An entity of the cluster health chunk, produced by the chunk loop.
+    HealthStateChunk(HealthStateChunkEntity),
     /// Marker emitted at the end of a producer loop iteration. Allows a
     /// consumer to detect that a loop has produced a full set of data for the
     /// current iteration.
@@ -33,7 +42,8 @@ pub enum ProducerEvent {
 pub enum LoopKind {
     /// The cluster and node health loop.
     ClusterNode,
-    /// The application (and services, partitions, replicas) health loop.
+    /// The application (and services, partitions, replicas, and if enabled, deployed
+    /// applications and deployed service packages) health loop.
     Application,
 }
 
@@ -77,3 +87,68 @@ pub struct ReplicaHealthEntity {
     pub service_name: Uri,
     pub application_name: Uri,
 }
+
+/// Application deployed on a node.
+/// The application and node names are in the health result.
+/// Produced only if enabled with `HealthDataProducer::with_deployed_entities`.
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealthEntity {
+    pub health: DeployedApplicationHealth,
+}
+
+/// Service package deployed on a node.
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthEntity {
+    pub health: DeployedServicePackageHealth,
+    /// Empty for the shared activation mode.
+    pub service_package_activation_id: WString,
+}
+
+/// Health state of an entity in the cluster health chunk, with the names of its parents.
+/// The chunk has neither the query results nor the health events of the entities.
+#[derive(Debug, Clone)]
+pub enum HealthStateChunkEntity {
+    Cluster {
+        health_state: HealthState,
+    },
+    Node {
+        node_name: WString,
+        health_state: HealthState,
+    },
+    Application {
+        application_name: Uri,
+        health_state: HealthState,
+    },
+    DeployedApplication {
+        application_name: Uri,
+        node_name: WString,
+        health_state: HealthState,
+    },
+    DeployedServicePackage {
+        application_name: Uri,
+        node_name: WString,
+        service_manifest_name: WString,
+        /// Empty for the shared activation mode.
+        service_package_activation_id: WString,
+        health_state: HealthState,
+    },
+    Service {
+        application_name: Uri,
+        service_name: Uri,
+        health_state: HealthState,
+    },
+    Partition {
+        application_name: Uri,
+        service_name: Uri,
+        partition_id: GUID,
+        health_state: HealthState,
+    },
+    /// The chunk does not tell stateful replicas from stateless instances.
+    Replica {
+        application_name: Uri,
+        service_name: Uri,
+        partition_id: GUID,
+        replica_or_instance_id: i64,
+        health_state: HealthState,
+    },
+}
@@ -6,7 +6,10 @@
 mod producer;
 pub use producer::HealthDataProducer;
 mod entities;
-pub use entities::{LoopKind, NodeHealthEntity, ProducerEvent};
+pub use entities::{
+    DeployedApplicationHealthEntity, DeployedServicePackageHealthEntity, HealthStateChunkEntity,
+    LoopKind, NodeHealthEntity, ProducerEvent,
+};
 
 #[cfg(test)]
 mod tests {
@@ -32,6 +35,11 @@ mod tests {
         pub partition_health_entities: Vec<crate::monitoring::entities::PartitionHealthEntity>,
         pub service_health_entities: Vec<crate::monitoring::entities::ServiceHealthEntity>,
         pub replica_health_entities: Vec<crate::monitoring::entities::ReplicaHealthEntity>,
+        pub deployed_application_health_entities:
+            Vec<crate::monitoring::DeployedApplicationHealthEntity>,
+        pub deployed_service_package_health_entities:
+            Vec<crate::monitoring::DeployedServicePackageHealthEntity>,
+        pub health_state_chunk_entities: Vec<crate::monitoring::HealthStateChunkEntity>,
     }
 
     impl MockHealthDataConsumer {
@@ -57,6 +65,9 @@ mod tests {
                 partition_health_entities: Vec::new(),
                 service_health_entities: Vec::new(),
                 replica_health_entities: Vec::new(),
+                deployed_application_health_entities: Vec::new(),
+                deployed_service_package_health_entities: Vec::new(),
+                health_state_chunk_entities: Vec::new(),
             };
             let mut cluster_node_done = false;
             let mut application_done = false;
@@ -80,6 +91,15 @@ mod tests {
                     ProducerEvent::Replica(replica_entity) => {
                         data.replica_health_entities.push(replica_entity);
                     }
+                    ProducerEvent::DeployedApplication(entity) => {
+                        data.deployed_application_health_entities.push(entity);
+                    }
+                    ProducerEvent::DeployedServicePackage(entity) => {
+                        data.deployed_service_package_health_entities.push(entity);
+                    }
+                    ProducerEvent::HealthStateChunk(entity) => {
+                        data.health_state_chunk_entities.push(entity);
+                    }
                     ProducerEvent::IterationComplete(kind) => match kind {
                         LoopKind::ClusterNode => cluster_node_done = true,
                         LoopKind::Application => application_done = true,
@@ -224,4 +244,203 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_cluster_health_chunk_events() {
+        use mssf_com::FabricTypes::{
+            FABRIC_APPLICATION_HEALTH_STATE_CHUNK, FABRIC_APPLICATION_HEALTH_STATE_CHUNK_LIST,
+            FABRIC_CLUSTER_HEALTH_CHUNK, FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK,
+            FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK_LIST,
+            FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK,
+            FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK_LIST, FABRIC_HEALTH_STATE_ERROR,
+            FABRIC_HEALTH_STATE_OK, FABRIC_HEALTH_STATE_WARNING, FABRIC_NODE_HEALTH_STATE_CHUNK,
+            FABRIC_NODE_HEALTH_STATE_CHUNK_LIST, FABRIC_PARTITION_HEALTH_STATE_CHUNK,
+            FABRIC_PARTITION_HEALTH_STATE_CHUNK_LIST, FABRIC_REPLICA_HEALTH_STATE_CHUNK,
+            FABRIC_REPLICA_HEALTH_STATE_CHUNK_LIST, FABRIC_SERVICE_HEALTH_STATE_CHUNK,
+            FABRIC_SERVICE_HEALTH_STATE_CHUNK_LIST, FABRIC_URI,
+        };
+        use mssf_core::{
+            GUID,
+            types::{ClusterHealthChunk, HealthState, Uri},
+        };
+
+        use crate::monitoring::{HealthStateChunkEntity, producer::cluster_health_chunk_events};
+
+        let node_name = WString::from("_Node_0");
+        let nodes = [FABRIC_NODE_HEALTH_STATE_CHUNK {
+            NodeName: node_name.as_pcwstr(),
+            HealthState: FABRIC_HEALTH_STATE_OK,
+            ..Default::default()
+        }];
+        let node_list = FABRIC_NODE_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: nodes.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let replicas = [FABRIC_REPLICA_HEALTH_STATE_CHUNK {
+            ReplicaOrInstanceId: 42,
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            ..Default::default()
+        }];
+        let replica_list = FABRIC_REPLICA_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: replicas.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let partition_id = GUID::from_u128(0x1234);
+        let partitions = [FABRIC_PARTITION_HEALTH_STATE_CHUNK {
+            PartitionId: partition_id,
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            ReplicaHealthStateChunks: &replica_list,
+            ..Default::default()
+        }];
+        let partition_list = FABRIC_PARTITION_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: partitions.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let service_name = WString::from("fabric:/myapp/svc");
+        let services = [FABRIC_SERVICE_HEALTH_STATE_CHUNK {
+            ServiceName: FABRIC_URI(service_name.as_ptr() as *mut u16),
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            PartitionHealthStateChunks: &partition_list,
+            ..Default::default()
+        }];
+        let service_list = FABRIC_SERVICE_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: services.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let manifest_name = WString::from("MyServicePkg");
+        let packages = [FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK {
+            ServiceManifestName: manifest_name.as_pcwstr(),
+            HealthState: FABRIC_HEALTH_STATE_WARNING,
+            ..Default::default()
+        }];
+        let package_list = FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: packages.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let deployed_apps = [FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK {
+            NodeName: node_name.as_pcwstr(),
+            HealthState: FABRIC_HEALTH_STATE_WARNING,
+            DeployedServicePackageHealthStateChunks: &package_list,
+            ..Default::default()
+        }];
+        let deployed_app_list = FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: deployed_apps.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let app_name = WString::from("fabric:/myapp");
+        let apps = [FABRIC_APPLICATION_HEALTH_STATE_CHUNK {
+            ApplicationName: FABRIC_URI(app_name.as_ptr() as *mut u16),
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            ServiceHealthStateChunks: &service_list,
+            DeployedApplicationHealthStateChunks: &deployed_app_list,
+            ..Default::default()
+        }];
+        let app_list = FABRIC_APPLICATION_HEALTH_STATE_CHUNK_LIST {
+            Count: 1,
+            Items: apps.as_ptr(),
+            TotalCount: 1,
+            ..Default::default()
+        };
+        let raw = FABRIC_CLUSTER_HEALTH_CHUNK {
+            HealthState: FABRIC_HEALTH_STATE_ERROR,
+            NodeHealthStateChunks: &node_list,
+            ApplicationHealthStateChunks: &app_list,
+            ..Default::default()
+        };
+
+        let entities = cluster_health_chunk_events(ClusterHealthChunk::from(&raw))
+            .into_iter()
+            .map(|event| match event {
+                ProducerEvent::HealthStateChunk(entity) => entity,
+                _ => panic!("expected health state chunk event"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(entities.len(), 8);
+        let app_uri = Uri::from("fabric:/myapp");
+        let svc_uri = Uri::from("fabric:/myapp/svc");
+        let mut entities = entities.into_iter();
+        let Some(HealthStateChunkEntity::Cluster { health_state }) = entities.next() else {
+            panic!("expected cluster entity");
+        };
+        assert_eq!(health_state, HealthState::Error);
+        let Some(HealthStateChunkEntity::Node {
+            node_name: name,
+            health_state,
+        }) = entities.next()
+        else {
+            panic!("expected node entity");
+        };
+        assert_eq!(name, node_name);
+        assert_eq!(health_state, HealthState::Ok);
+        let Some(HealthStateChunkEntity::Application {
+            application_name,
+            health_state,
+        }) = entities.next()
+        else {
+            panic!("expected application entity");
+        };
+        assert_eq!(application_name, app_uri);
+        assert_eq!(health_state, HealthState::Error);
+        let Some(HealthStateChunkEntity::DeployedApplication {
+            application_name,
+            node_name: name,
+            health_state,
+        }) = entities.next()
+        else {
+            panic!("expected deployed application entity");
+        };
+        assert_eq!(application_name, app_uri);
+        assert_eq!(name, node_name);
+        assert_eq!(health_state, HealthState::Warning);
+        let Some(HealthStateChunkEntity::DeployedServicePackage {
+            service_manifest_name,
+            service_package_activation_id,
+            ..
+        }) = entities.next()
+        else {
+            panic!("expected deployed service package entity");
+        };
+        assert_eq!(service_manifest_name, manifest_name);
+        assert!(service_package_activation_id.is_empty());
+        let Some(HealthStateChunkEntity::Service { service_name, .. }) = entities.next() else {
+            panic!("expected service entity");
+        };
+        assert_eq!(service_name, svc_uri);
+        let Some(HealthStateChunkEntity::Partition {
+            application_name,
+            service_name,
+            partition_id: id,
+            ..
+        }) = entities.next()
+        else {
+            panic!("expected partition entity");
+        };
+        assert_eq!(application_name, app_uri);
+        assert_eq!(service_name, svc_uri);
+        assert_eq!(id, partition_id);
+        let Some(HealthStateChunkEntity::Replica {
+            partition_id: id,
+            replica_or_instance_id,
+            health_state,
+            ..
+        }) = entities.next()
+        else {
+            panic!("expected replica entity");
+        };
+        assert_eq!(id, partition_id);
+        assert_eq!(replica_or_instance_id, 42);
+        assert_eq!(health_state, HealthState::Error);
+    }
 }
@@ -3,18 +3,31 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::monitoring::{
-    NodeHealthEntity, ProducerEvent,
-    entities::{ClusterHealthEntity, LoopKind},
+use crate::{
+    monitoring::{
+        NodeHealthEntity, ProducerEvent,
+        entities::{
+            ClusterHealthEntity, DeployedApplicationHealthEntity,
+            DeployedServicePackageHealthEntity, HealthStateChunkEntity, LoopKind,
+        },
+    },
+    query::PagedQueryClient,
 };
 use ::tokio::sync::mpsc;
+use futures::TryStreamExt;
 use mssf_core::{
     client::FabricClient,
     runtime::executor::BoxedCancelToken,
     types::{
-        ApplicationHealthStatesFilter, ApplicationQueryDescription, ClusterHealthQueryDescription,
+        ApplicationHealthStateChunk, ApplicationHealthStateFilter, ApplicationHealthStatesFilter,
+        ApplicationQueryDescription, ClusterHealthChunk, ClusterHealthChunkQueryDescription,
+        ClusterHealthQueryDescription, DeployedApplicationHealth,
+        DeployedApplicationHealthQueryDescription, DeployedApplicationHealthState,
+        DeployedApplicationHealthStateFilter, DeployedServicePackageHealthQueryDescription,
+        DeployedServicePackageHealthState, DeployedServicePackageHealthStateFilter,
         HealthEventsFilter, HealthStateFilterFlags, NodeHealthQueryDescription,
-        NodeHealthStatesFilter, NodeQueryResultItem, Uri,
+        NodeHealthStateFilter, NodeHealthStatesFilter, NodeQueryResultItem,
+        PartitionHealthStateFilter, ReplicaHealthStateFilter, ServiceHealthStateFilter, Uri,
     },
 };
 use std::time::Duration;
@@ -25,11 +38,33 @@ pub struct HealthDataProducer {
     fc: FabricClient,
     interval: Duration,
     sender: mpsc::UnboundedSender<ProducerEvent>,
+    chunk_query: ClusterHealthChunkQueryDescription,
+    deployed_entities: bool,
 }
 
 /// Default timeout for FabricClient operations.
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Chunk query returning all nodes, applications and their children.
+fn default_chunk_query() -> ClusterHealthChunkQueryDescription {
+    let all = || HealthStateFilterFlags::ALL;
+    let services = ServiceHealthStateFilter::new(all()).with_partition_filter(
+        PartitionHealthStateFilter::new(all())
+            .with_replica_filter(ReplicaHealthStateFilter::new(all())),
+    );
+    let deployed_applications = DeployedApplicationHealthStateFilter::new(all())
+        .with_deployed_service_package_filter(DeployedServicePackageHealthStateFilter::new(all()));
+    ClusterHealthChunkQueryDescription {
+        application_filters: vec![
+            ApplicationHealthStateFilter::new(all())
+                .with_service_filter(services)
+                .with_deployed_application_filter(deployed_applications),
+        ],
+        node_filters: vec![NodeHealthStateFilter::new(all())],
+        ..Default::default()
+    }
+}
+
 pub enum Action {
     Stop,
 }
@@ -44,9 +79,30 @@ impl HealthDataProducer {
             fc,
             interval,
             sender,
+            chunk_query: default_chunk_query(),
+            deployed_entities: false,
         }
     }
 
+    /// Sets the query used by the chunk loop.
+    /// By default all nodes, applications and their children are returned.
+    pub fn with_chunk_query(mut self, chunk_query: ClusterHealthChunkQueryDescription) -> Self {
+        self.chunk_query = chunk_query;
+        self
+    }
+
+    /// Sets whether the application loop produces the health of the applications
+    /// and service packages deployed on each node. Off by default, as it takes
+    /// extra queries per node for each application.
+    pub fn with_deployed_entities(mut self, deployed_entities: bool) -> Self {
+        self.deployed_entities = deployed_entities;
+        self
+    }
+
+    fn paged_query_client(&self) -> PagedQueryClient {
+        PagedQueryClient::new(&self.fc).with_page_timeout(DEFAULT_TIMEOUT)
+    }
+
     fn send_event(&self, event: ProducerEvent) -> Result<(), Action> {
         self.sender.send(event).map_err(|_| {
             tracing::error!("Receiver dropped, cannot send more data.");
@@ -95,7 +151,18 @@ impl HealthDataProducer {
                     .produce_application_health_entity(token.clone(), app)
                     .await
                 {
+                    // The application health lists the nodes it is deployed on.
+                    let deployed = match &entity {
+                        ProducerEvent::Application(e) if self.deployed_entities => {
+                            e.health.deployed_applications_health_states.clone()
+                        }
+                        _ => Vec::new(),
+                    };
                     self.send_event(entity)?;
+                    for deployed_app in deployed {
+                        self.run_once_deployed_application(token.clone(), deployed_app)
+                            .await?;
+                    }
                 }
 
                 // Get service information for the application.
@@ -162,6 +229,50 @@ impl HealthDataProducer {
         Ok(())
     }
 
+    /// Produce the health of an application deployed on a node,
+    /// and of the service packages deployed with it.
+    async fn run_once_deployed_application(
+        &self,
+        token: BoxedCancelToken,
+        deployed_app: DeployedApplicationHealthState,
+    ) -> Result<(), Action> {
+        let Some(health) = self
+            .get_deployed_application_health(token.clone(), deployed_app)
+            .await
+        else {
+            return Ok(());
+        };
+        let packages = health.deployed_service_package_health_states.clone();
+        self.send_event(ProducerEvent::DeployedApplication(
+            DeployedApplicationHealthEntity { health },
+        ))?;
+        for package in packages {
+            if let Some(entity) = self
+                .produce_deployed_service_package_health_entity(token.clone(), package)
+                .await
+            {
+                self.send_event(entity)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run once to produce the health of all entities from a single chunk query.
+    /// Completes an iteration of both the cluster/node and the application loop.
+    pub(crate) async fn run_once_cluster_health_chunk(
+        &self,
+        token: BoxedCancelToken,
+    ) -> Result<(), Action> {
+        if let Some(chunk) = self.get_cluster_health_chunk(token).await {
+            for event in cluster_health_chunk_events(chunk) {
+                self.send_event(event)?;
+            }
+        }
+        self.send_event(ProducerEvent::IterationComplete(LoopKind::ClusterNode))?;
+        self.send_event(ProducerEvent::IterationComplete(LoopKind::Application))?;
+        Ok(())
+    }
+
     /// Run both the cluster/node loop and the application loop concurrently.
     ///
     /// The two loops are independent: a slow application traversal will not
@@ -190,6 +301,18 @@ impl HealthDataProducer {
         .await;
     }
 
+    /// Run a loop to produce health data from the cluster health chunk.
+    ///
+    /// An alternative to `run_loop` that takes one round trip per iteration.
+    /// The chunk only has the health states of the entities, which are sent as
+    /// `ProducerEvent::HealthStateChunk` instead of the events of the other loops.
+    pub async fn run_cluster_health_chunk_loop(&self, token: BoxedCancelToken) {
+        self.run_interval_loop(token, "cluster health chunk", |token| {
+            self.run_once_cluster_health_chunk(token)
+        })
+        .await;
+    }
+
     /// Drive `run_once` on `self.interval`, honoring cancellation.
     async fn run_interval_loop<F, Fut>(&self, token: BoxedCancelToken, name: &str, mut run_once: F)
     where
@@ -407,24 +530,163 @@ impl HealthDataProducer {
     }
 }
 
+impl HealthDataProducer {
+    async fn get_cluster_health_chunk(
+        &self,
+        token: BoxedCancelToken,
+    ) -> Option<ClusterHealthChunk> {
+        self.fc
+            .get_health_manager()
+            .get_cluster_health_chunk(&self.chunk_query, DEFAULT_TIMEOUT, Some(token))
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Failed to get cluster health chunk: {}", err);
+            })
+            .ok()
+    }
+
+    async fn get_deployed_application_health(
+        &self,
+        token: BoxedCancelToken,
+        deployed_app: DeployedApplicationHealthState,
+    ) -> Option<DeployedApplicationHealth> {
+        let desc = DeployedApplicationHealthQueryDescription {
+            application_name: deployed_app.application_name,
+            node_name: deployed_app.node_name,
+            ..Default::default()
+        };
+        self.fc
+            .get_health_manager()
+            .get_deployed_application_health(&desc, DEFAULT_TIMEOUT, Some(token))
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Failed to get deployed application health: {}", err);
+            })
+            .ok()
+    }
+
+    async fn produce_deployed_service_package_health_entity(
+        &self,
+        token: BoxedCancelToken,
+        package: DeployedServicePackageHealthState,
+    ) -> Option<ProducerEvent> {
+        let activation_id = package.service_package_activation_id;
+        let desc = DeployedServicePackageHealthQueryDescription {
+            application_name: package.application_name,
+            node_name: package.node_name,
+            service_manifest_name: package.service_manifest_name,
+            service_package_activation_id: (!activation_id.is_empty())
+                .then(|| activation_id.clone()),
+            ..Default::default()
+        };
+        let health = self
+            .fc
+            .get_health_manager()
+            .get_deployed_service_package_health(&desc, DEFAULT_TIMEOUT, Some(token))
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Failed to get deployed service package health: {}", err);
+            })
+            .ok()?;
+        Some(ProducerEvent::DeployedServicePackage(
+            DeployedServicePackageHealthEntity {
+                health,
+                service_package_activation_id: activation_id,
+            },
+        ))
+    }
+}
+
+/// Events of the entities in the chunk, with each entity following its parent.
+pub(crate) fn cluster_health_chunk_events(chunk: ClusterHealthChunk) -> Vec<ProducerEvent> {
+    let mut entities = vec![HealthStateChunkEntity::Cluster {
+        health_state: chunk.health_state,
+    }];
+    for node in chunk.node_health_state_chunks {
+        entities.push(HealthStateChunkEntity::Node {
+            node_name: node.node_name,
+            health_state: node.health_state,
+        });
+    }
+    for app in chunk.application_health_state_chunks {
+        application_health_chunk_entities(app, &mut entities);
+    }
+    entities
+        .into_iter()
+        .map(ProducerEvent::HealthStateChunk)
+        .collect()
+}
+
+fn application_health_chunk_entities(
+    app: ApplicationHealthStateChunk,
+    entities: &mut Vec<HealthStateChunkEntity>,
+) {
+    let application_name = app.application_name;
+    entities.push(HealthStateChunkEntity::Application {
+        application_name: application_name.clone(),
+        health_state: app.health_state,
+    });
+    for deployed_app in app.deployed_application_health_state_chunks {
+        let node_name = deployed_app.node_name;
+        entities.push(HealthStateChunkEntity::DeployedApplication {
+            application_name: application_name.clone(),
+            node_name: node_name.clone(),
+            health_state: deployed_app.health_state,
+        });
+        for package in deployed_app.deployed_service_package_health_state_chunks {
+            entities.push(HealthStateChunkEntity::DeployedServicePackage {
+                application_name: application_name.clone(),
+                node_name: node_name.clone(),
+                service_manifest_name: package.service_manifest_name,
+                service_package_activation_id: package
+                    .service_package_activation_id
+                    .unwrap_or_default(),
+                health_state: package.health_state,
+            });
+        }
+    }
+    for svc in app.service_health_state_chunks {
+        let service_name = svc.service_name;
+        entities.push(HealthStateChunkEntity::Service {
+            application_name: application_name.clone(),
+            service_name: service_name.clone(),
+            health_state: svc.health_state,
+        });
+        for partition in svc.partition_health_state_chunks {
+            let partition_id = partition.partition_id;
+            entities.push(HealthStateChunkEntity::Partition {
+                application_name: application_name.clone(),
+                service_name: service_name.clone(),
+                partition_id,
+                health_state: partition.health_state,
+            });
+            for replica in partition.replica_health_state_chunks {
+                entities.push(HealthStateChunkEntity::Replica {
+                    application_name: application_name.clone(),
+                    service_name: service_name.clone(),
+                    partition_id,
+                    replica_or_instance_id: replica.replica_or_instance_id,
+                    health_state: replica.health_state,
+                });
+            }
+        }
+    }
+}
+
 // Get lists of entities
 impl HealthDataProducer {
     async fn get_all_nodes(
         &self,
         token: BoxedCancelToken,
     ) -> mssf_core::Result<Vec<NodeQueryResultItem>> {
-        // Logic to get node information goes here.
-        let desc = &Default::default();
-        let nodes = self
-            .fc
-            .get_query_manager()
-            .get_node_list(desc, DEFAULT_TIMEOUT, Some(token.clone()))
+        // Walk all pages of the node list.
+        self.paged_query_client()
+            .get_node_list_stream(Default::default(), Some(token))
+            .try_collect()
             .await
             .inspect_err(|err| {
                 tracing::error!("Failed to get node list: {}", err);
-            })?
-            .nodes;
-        Ok(nodes)
+            })
     }
 
     /// This does not include system application.
@@ -433,17 +695,13 @@ impl HealthDataProducer {
         &self,
         token: BoxedCancelToken,
     ) -> mssf_core::Result<Vec<mssf_core::types::ApplicationQueryResultItem>> {
-        let desc = ApplicationQueryDescription::default();
-        let apps = self
-            .fc
-            .get_query_manager()
-            .get_application_list(&desc, DEFAULT_TIMEOUT, Some(token.clone()))
+        self.paged_query_client()
+            .get_application_list_stream(ApplicationQueryDescription::default(), Some(token))
+            .try_collect()
             .await
             .inspect_err(|err| {
                 tracing::error!("Failed to get application list: {}", err);
-            })?
-            .items;
-        Ok(apps)
+            })
     }
     async fn get_all_services_for_app(
         &self,
@@ -456,15 +714,13 @@ impl HealthDataProducer {
             application_name: app_name_cp,
             ..Default::default()
         };
-        let services = self
-            .fc
-            .get_query_manager()
-            .get_service_list(&desc, DEFAULT_TIMEOUT, Some(token.clone()))
+        self.paged_query_client()
+            .get_service_list_stream(desc, Some(token))
+            .try_collect()
             .await
             .inspect_err(|err| {
                 tracing::error!("Failed to get service list for app {app_name}: {err}");
-            })?;
-        Ok(services.items)
+            })
     }
 
     async fn get_all_partitions_for_svc(
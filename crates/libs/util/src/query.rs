@@ -0,0 +1,413 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Streams over paged QueryClient list queries.
+//!
+//! SF returns list queries one page at a time together with a continuation token.
+//! The streams here keep requesting pages until the token is empty and yield the
+//! items one by one. `max_results` set on the description is sent with every page
+//! request, and the stream ends once it has yielded `max_results` items.
+
+use std::{sync::Arc, time::Duration};
+
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+use mssf_core::{
+    WString,
+    client::{FabricClient, query_client::QueryClient},
+    runtime::executor::BoxedCancelToken,
+    types::{
+        ApplicationQueryDescription, ApplicationQueryResultItem, ApplicationTypeQueryDescription,
+        ApplicationTypeQueryResultItem, DeployedApplicationQueryDescription,
+        DeployedApplicationQueryResultItem, DeployedServicePackageQueryDescription,
+        DeployedServicePackageQueryResultItem, NodeQueryDescription, NodeQueryResultItem,
+        PagingStatus, ServiceQueryDescription, ServiceQueryResultItem,
+    },
+};
+
+use crate::retry::OperationRetryer;
+
+/// Default timeout for each page request.
+const DEFAULT_PAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Query descriptions that carry a continuation token.
+trait PagedDescription: Clone {
+    fn set_continuation_token(&mut self, token: WString);
+    fn max_results(&self) -> Option<i32>;
+}
+
+impl PagedDescription for NodeQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.paged_query.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.paged_query.max_results
+    }
+}
+
+impl PagedDescription for ApplicationQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.max_results
+    }
+}
+
+impl PagedDescription for ServiceQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.max_results
+    }
+}
+
+impl PagedDescription for ApplicationTypeQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.max_results
+    }
+}
+
+impl PagedDescription for DeployedApplicationQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.paged_query.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.paged_query.max_results
+    }
+}
+
+impl PagedDescription for DeployedServicePackageQueryDescription {
+    fn set_continuation_token(&mut self, token: WString) {
+        self.paged_query.continuation_token = Some(token);
+    }
+
+    fn max_results(&self) -> Option<i32> {
+        self.paged_query.max_results
+    }
+}
+
+/// Walks all pages starting from `desc`.
+/// `fetch` runs one page query. The stream ends after the first error,
+/// or after `max_results` items if it is positive.
+fn paged_stream<D, I, F, Fut>(desc: D, fetch: F) -> impl Stream<Item = mssf_core::Result<I>>
+where
+    D: PagedDescription,
+    F: Fn(D) -> Fut,
+    Fut: Future<Output = mssf_core::Result<(Vec<I>, Option<PagingStatus>)>>,
+{
+    let limit = desc
+        .max_results()
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(usize::MAX);
+    stream::try_unfold(
+        (Some(desc), fetch),
+        async |(desc, fetch)| -> mssf_core::Result<_> {
+            let Some(desc) = desc else {
+                return Ok(None);
+            };
+            let (items, paging_status) = fetch(desc.clone()).await?;
+            // Empty token means this was the last page.
+            let next = paging_status
+                .map(|ps| ps.continuation_token)
+                .filter(|token| !token.is_empty())
+                .map(|token| {
+                    let mut next = desc;
+                    next.set_continuation_token(token);
+                    next
+                });
+            Ok(Some((
+                stream::iter(items.into_iter().map(Ok)),
+                (next, fetch),
+            )))
+        },
+    )
+    .try_flatten()
+    .take(limit)
+}
+
+/// Builds streams over paged QueryClient list queries.
+/// With a retryer, each page request is retried on transient errors.
+#[derive(Clone)]
+pub struct PagedQueryClient {
+    qc: QueryClient,
+    retryer: Option<Arc<OperationRetryer>>,
+    page_timeout: Duration,
+}
+
+impl PagedQueryClient {
+    pub fn new(fc: &FabricClient) -> Self {
+        PagedQueryClient {
+            qc: fc.get_query_manager().clone(),
+            retryer: None,
+            page_timeout: DEFAULT_PAGE_TIMEOUT,
+        }
+    }
+
+    /// Retry each page request on transient errors and timeouts.
+    pub fn with_retryer(mut self, retryer: OperationRetryer) -> Self {
+        self.retryer = Some(Arc::new(retryer));
+        self
+    }
+
+    /// Timeout for each page request.
+    /// With a retryer this is the total timeout including the retries.
+    pub fn with_page_timeout(mut self, timeout: Duration) -> Self {
+        self.page_timeout = timeout;
+        self
+    }
+
+    async fn run<T, F, Fut>(&self, op: F, token: Option<BoxedCancelToken>) -> mssf_core::Result<T>
+    where
+        F: Fn(Duration, Option<BoxedCancelToken>) -> Fut,
+        Fut: Future<Output = mssf_core::Result<T>> + Send,
+        T: Send,
+    {
+        match &self.retryer {
+            Some(retryer) => retryer.run(op, Some(self.page_timeout), token).await,
+            None => op(self.page_timeout, token).await,
+        }
+    }
+
+    pub fn get_node_list_stream(
+        &self,
+        desc: NodeQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<NodeQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| this.qc.get_node_list(&desc, t, tk).await,
+                        token,
+                    )
+                    .await?;
+                Ok((res.nodes, res.paging_status))
+            }
+        })
+    }
+
+    pub fn get_application_list_stream(
+        &self,
+        desc: ApplicationQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<ApplicationQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| this.qc.get_application_list(&desc, t, tk).await,
+                        token,
+                    )
+                    .await?;
+                Ok((res.items, res.paging_status))
+            }
+        })
+    }
+
+    pub fn get_service_list_stream(
+        &self,
+        desc: ServiceQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<ServiceQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| this.qc.get_service_list(&desc, t, tk).await,
+                        token,
+                    )
+                    .await?;
+                Ok((res.items, res.paging_status))
+            }
+        })
+    }
+
+    pub fn get_application_type_list_stream(
+        &self,
+        desc: ApplicationTypeQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<ApplicationTypeQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| this.qc.get_application_type_paged_list(&desc, t, tk).await,
+                        token,
+                    )
+                    .await?;
+                Ok((res.items, res.paging_status))
+            }
+        })
+    }
+
+    pub fn get_deployed_application_list_stream(
+        &self,
+        desc: DeployedApplicationQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<DeployedApplicationQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| {
+                            this.qc
+                                .get_deployed_application_paged_list(&desc, t, tk)
+                                .await
+                        },
+                        token,
+                    )
+                    .await?;
+                Ok((res.items, res.paging_status))
+            }
+        })
+    }
+
+    pub fn get_deployed_service_package_list_stream(
+        &self,
+        desc: DeployedServicePackageQueryDescription,
+        token: Option<BoxedCancelToken>,
+    ) -> impl Stream<Item = mssf_core::Result<DeployedServicePackageQueryResultItem>> {
+        let this = self.clone();
+        paged_stream(desc, move |desc| {
+            let this = this.clone();
+            let token = token.clone();
+            async move {
+                let res = this
+                    .run(
+                        async |t, tk| {
+                            this.qc
+                                .get_deployed_service_package_paged_list(&desc, t, tk)
+                                .await
+                        },
+                        token,
+                    )
+                    .await?;
+                Ok((res.items, res.paging_status))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures::StreamExt;
+    use mssf_core::{ErrorCode, WString, types::PagingStatus};
+
+    use super::{PagedDescription, paged_stream};
+
+    #[derive(Clone, Default, Debug)]
+    struct FakeDescription {
+        continuation_token: Option<WString>,
+        max_results: Option<i32>,
+    }
+
+    impl PagedDescription for FakeDescription {
+        fn set_continuation_token(&mut self, token: WString) {
+            self.continuation_token = Some(token);
+        }
+
+        fn max_results(&self) -> Option<i32> {
+            self.max_results
+        }
+    }
+
+    fn page(items: &[u32], token: &str) -> mssf_core::Result<(Vec<u32>, Option<PagingStatus>)> {
+        Ok((
+            items.to_vec(),
+            Some(PagingStatus {
+                continuation_token: WString::from(token),
+            }),
+        ))
+    }
+
+    async fn collect_pages(desc: FakeDescription) -> (Vec<u32>, Vec<FakeDescription>) {
+        let requests = Mutex::new(Vec::new());
+        let items = paged_stream(desc, async |d: FakeDescription| {
+            requests.lock().unwrap().push(d.clone());
+            match d.continuation_token.map(|t| t.to_string()).as_deref() {
+                None => page(&[1, 2], "a"),
+                Some("a") => page(&[3, 4], "b"),
+                Some("b") => page(&[5], ""),
+                _ => panic!("unexpected token"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<mssf_core::Result<Vec<_>>>()
+        .unwrap();
+        (items, requests.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_paged_stream_follows_tokens() {
+        let (items, requests) = collect_pages(FakeDescription::default()).await;
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(requests.len(), 3);
+
+        let desc = FakeDescription {
+            max_results: Some(3),
+            ..Default::default()
+        };
+        let (items, requests) = collect_pages(desc).await;
+        // The stream stops after max_results items, without requesting the last page.
+        assert_eq!(items.len(), 3);
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(requests.len(), 2);
+        // max_results is kept on every page request.
+        assert!(requests.iter().all(|d| d.max_results == Some(3)));
+    }
+
+    #[tokio::test]
+    async fn test_paged_stream_stops_on_error() {
+        let items = paged_stream(
+            FakeDescription::default(),
+            async |d: FakeDescription| match d.continuation_token {
+                None => page(&[1], "a"),
+                Some(_) => Err(ErrorCode::FABRIC_E_INVALID_OPERATION.into()),
+            },
+        )
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert!(items[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paged_stream_no_paging_status() {
+        let items = paged_stream(FakeDescription::default(), async |_| Ok((vec![7, 8], None)))
+            .collect::<Vec<mssf_core::Result<u32>>>()
+            .await;
+        assert_eq!(items.len(), 2);
+    }
+}
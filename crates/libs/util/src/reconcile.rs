@@ -0,0 +1,715 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Declarative service management.
+//!
+//! The [`Reconciler`] takes the desired services and brings the cluster in line
+//! with them: missing services are created, drifted services are updated with
+//! only the changed fields, and with pruning enabled, services that are no
+//! longer desired are deleted.
+//!
+//! Fields SF cannot update in place, i.e. the service kind, service type,
+//! partition scheme, persisted state and activation mode, are reported as
+//! conflicts. The reconciler never deletes and recreates a service to resolve them.
+//! Service groups are not managed and are ignored when listing.
+
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use mssf_core::{
+    client::FabricClient,
+    types::{
+        DeleteServiceDescription, ServiceDescription, ServiceQueryDescription,
+        ServiceQueryResultItem, ServiceUpdateDescription, StatefulServiceDescription,
+        StatefulServiceUpdateDescription, StatefulServiceUpdateDescriptionFlags,
+        StatelessServiceDescription, StatelessServiceUpdateDescription,
+        StatelessServiceUpdateDescriptionFlags, Uri,
+    },
+};
+use tokio::sync::mpsc;
+
+use crate::query::PagedQueryClient;
+
+/// Default timeout for FabricClient operations.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Cluster operations used by the [`Reconciler`].
+/// Implemented for [`FabricClient`]; tests can provide an in-memory fake.
+pub trait ServiceClient: Send + Sync {
+    /// Names of the services of an application, excluding service groups.
+    fn list_services(
+        &self,
+        application_name: &Uri,
+    ) -> impl Future<Output = mssf_core::Result<Vec<Uri>>> + Send;
+
+    fn get_service_description(
+        &self,
+        name: &Uri,
+    ) -> impl Future<Output = mssf_core::Result<ServiceDescription>> + Send;
+
+    fn create_service(
+        &self,
+        desc: &ServiceDescription,
+    ) -> impl Future<Output = mssf_core::Result<()>> + Send;
+
+    fn update_service(
+        &self,
+        name: &Uri,
+        desc: &ServiceUpdateDescription,
+    ) -> impl Future<Output = mssf_core::Result<()>> + Send;
+
+    fn delete_service(&self, name: &Uri) -> impl Future<Output = mssf_core::Result<()>> + Send;
+}
+
+impl ServiceClient for FabricClient {
+    async fn list_services(&self, application_name: &Uri) -> mssf_core::Result<Vec<Uri>> {
+        let desc = ServiceQueryDescription {
+            application_name: application_name.clone(),
+            ..Default::default()
+        };
+        PagedQueryClient::new(self)
+            .with_page_timeout(DEFAULT_TIMEOUT)
+            .get_service_list_stream(desc, None)
+            .try_filter_map(async |item| {
+                Ok(match item {
+                    ServiceQueryResultItem::Stateful(s) if !s.is_service_group => {
+                        Some(s.service_name)
+                    }
+                    ServiceQueryResultItem::Stateless(s) if !s.is_service_group => {
+                        Some(s.service_name)
+                    }
+                    _ => None,
+                })
+            })
+            .try_collect()
+            .await
+    }
+
+    async fn get_service_description(&self, name: &Uri) -> mssf_core::Result<ServiceDescription> {
+        self.get_service_manager()
+            .get_service_description(name, DEFAULT_TIMEOUT, None)
+            .await
+    }
+
+    async fn create_service(&self, desc: &ServiceDescription) -> mssf_core::Result<()> {
+        self.get_service_manager()
+            .create_service(desc, DEFAULT_TIMEOUT, None)
+            .await
+    }
+
+    async fn update_service(
+        &self,
+        name: &Uri,
+        desc: &ServiceUpdateDescription,
+    ) -> mssf_core::Result<()> {
+        self.get_service_manager()
+            .update_service(name, desc, DEFAULT_TIMEOUT, None)
+            .await
+    }
+
+    async fn delete_service(&self, name: &Uri) -> mssf_core::Result<()> {
+        self.get_service_manager()
+            .delete_service2(
+                &DeleteServiceDescription::new(name.clone()),
+                DEFAULT_TIMEOUT,
+                None,
+            )
+            .await
+    }
+}
+
+/// Fields changed by an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceChanges {
+    Stateful(StatefulServiceUpdateDescriptionFlags),
+    Stateless(StatelessServiceUpdateDescriptionFlags),
+}
+
+/// Operation that failed during reconcile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileAction {
+    Get,
+    Create,
+    Update,
+    Delete,
+}
+
+/// Outcome for one service.
+/// In dry-run mode the same events are sent, but the cluster is not changed.
+#[derive(Debug, Clone)]
+pub enum ReconcileEvent {
+    Created {
+        name: Uri,
+    },
+    Updated {
+        name: Uri,
+        changes: ServiceChanges,
+    },
+    Deleted {
+        name: Uri,
+    },
+    Unchanged {
+        name: Uri,
+    },
+    /// The service differs in a field that cannot be updated.
+    Conflict {
+        name: Uri,
+        reason: String,
+    },
+    Failed {
+        name: Uri,
+        action: ReconcileAction,
+        error: mssf_core::Error,
+    },
+}
+
+/// Result of comparing the desired and the current description.
+enum Diff {
+    Unchanged,
+    Update(Box<ServiceUpdateDescription>, ServiceChanges),
+    Conflict(String),
+}
+
+/// Brings the services of the managed applications in line with the desired descriptions.
+pub struct Reconciler<C> {
+    client: C,
+    dry_run: bool,
+    prune: bool,
+    applications: Vec<Uri>,
+}
+
+impl<C: ServiceClient> Reconciler<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            dry_run: false,
+            prune: false,
+            applications: Vec::new(),
+        }
+    }
+
+    /// Only report what would change.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Delete services of the managed applications that are not desired.
+    pub fn with_prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Manage an application even if no service of it is desired.
+    /// With pruning, all its services are deleted.
+    /// Applications of the desired services are always managed.
+    pub fn with_application(mut self, application_name: Uri) -> Self {
+        if !self.applications.contains(&application_name) {
+            self.applications.push(application_name);
+        }
+        self
+    }
+
+    /// Runs one reconcile pass and sends an event per service.
+    /// Failures of individual services are sent as events and do not stop the pass.
+    /// Returns an error if the services of an application cannot be listed,
+    /// since creates and deletes cannot be decided without the list.
+    /// Events are dropped if the receiver is gone.
+    pub async fn reconcile(
+        &self,
+        desired: &[ServiceDescription],
+        sender: &mpsc::UnboundedSender<ReconcileEvent>,
+    ) -> mssf_core::Result<()> {
+        let mut applications = self.applications.clone();
+        for desc in desired {
+            let app = application_name(desc);
+            if !applications.contains(app) {
+                applications.push(app.clone());
+            }
+        }
+
+        let mut existing = Vec::new();
+        for app in &applications {
+            existing.extend(self.client.list_services(app).await?);
+        }
+
+        for desc in desired {
+            let name = service_name(desc);
+            let event = if existing.contains(name) {
+                self.reconcile_existing(desc).await
+            } else {
+                self.create(desc).await
+            };
+            let _ = sender.send(event);
+        }
+
+        if self.prune {
+            for name in existing
+                .iter()
+                .filter(|name| !desired.iter().any(|d| service_name(d) == *name))
+            {
+                let _ = sender.send(self.delete(name).await);
+            }
+        }
+        Ok(())
+    }
+
+    async fn reconcile_existing(&self, desired: &ServiceDescription) -> ReconcileEvent {
+        let name = service_name(desired).clone();
+        let current = match self.client.get_service_description(&name).await {
+            Ok(current) => current,
+            Err(error) => {
+                return ReconcileEvent::Failed {
+                    name,
+                    action: ReconcileAction::Get,
+                    error,
+                };
+            }
+        };
+        match diff(desired, &current) {
+            Diff::Unchanged => ReconcileEvent::Unchanged { name },
+            Diff::Conflict(reason) => ReconcileEvent::Conflict { name, reason },
+            Diff::Update(update, changes) => {
+                if !self.dry_run
+                    && let Err(error) = self.client.update_service(&name, &update).await
+                {
+                    return ReconcileEvent::Failed {
+                        name,
+                        action: ReconcileAction::Update,
+                        error,
+                    };
+                }
+                ReconcileEvent::Updated { name, changes }
+            }
+        }
+    }
+
+    async fn create(&self, desired: &ServiceDescription) -> ReconcileEvent {
+        let name = service_name(desired).clone();
+        if !self.dry_run
+            && let Err(error) = self.client.create_service(desired).await
+        {
+            return ReconcileEvent::Failed {
+                name,
+                action: ReconcileAction::Create,
+                error,
+            };
+        }
+        ReconcileEvent::Created { name }
+    }
+
+    async fn delete(&self, name: &Uri) -> ReconcileEvent {
+        let name = name.clone();
+        if !self.dry_run
+            && let Err(error) = self.client.delete_service(&name).await
+        {
+            return ReconcileEvent::Failed {
+                name,
+                action: ReconcileAction::Delete,
+                error,
+            };
+        }
+        ReconcileEvent::Deleted { name }
+    }
+}
+
+fn service_name(desc: &ServiceDescription) -> &Uri {
+    match desc {
+        ServiceDescription::Stateful(d) => d.service_name(),
+        ServiceDescription::Stateless(d) => &d.service_name,
+    }
+}
+
+fn application_name(desc: &ServiceDescription) -> &Uri {
+    match desc {
+        ServiceDescription::Stateful(d) => d.application_name(),
+        ServiceDescription::Stateless(d) => &d.application_name,
+    }
+}
+
+fn diff(desired: &ServiceDescription, current: &ServiceDescription) -> Diff {
+    match (desired, current) {
+        (ServiceDescription::Stateful(d), ServiceDescription::Stateful(c)) => diff_stateful(d, c),
+        (ServiceDescription::Stateless(d), ServiceDescription::Stateless(c)) => {
+            diff_stateless(d, c)
+        }
+        _ => Diff::Conflict("service kind differs".to_string()),
+    }
+}
+
+/// Optional settings are only compared when they are set on the desired service,
+/// so values left to the cluster defaults are not reverted.
+fn diff_stateful(d: &StatefulServiceDescription, c: &StatefulServiceDescription) -> Diff {
+    if d.service_type_name() != c.service_type_name() {
+        return Diff::Conflict("service type differs".to_string());
+    }
+    if d.partition_scheme() != c.partition_scheme() {
+        return Diff::Conflict("partition scheme differs".to_string());
+    }
+    if d.has_persistent_state() != c.has_persistent_state() {
+        return Diff::Conflict("persisted state differs".to_string());
+    }
+    if d.service_package_activation_mode() != c.service_package_activation_mode() {
+        return Diff::Conflict("service package activation mode differs".to_string());
+    }
+
+    let mut update = StatefulServiceUpdateDescription::new();
+    if d.target_replica_set_size() != c.target_replica_set_size() {
+        update = update.with_target_replica_set_size(d.target_replica_set_size());
+    }
+    if d.min_replica_set_size() != c.min_replica_set_size() {
+        update = update.with_min_replica_set_size(d.min_replica_set_size());
+    }
+    if d.placement_constraints() != c.placement_constraints() {
+        update = update.with_placement_constraints(d.placement_constraints().clone());
+    }
+    if let Some(v) = changed(d.default_move_cost(), c.default_move_cost()) {
+        update = update.with_move_cost(v);
+    }
+    if let Some(v) = changed(
+        d.replica_restart_wait_duration_seconds(),
+        c.replica_restart_wait_duration_seconds(),
+    ) {
+        update = update.with_replica_restart_wait_duration_seconds(v);
+    }
+    if let Some(v) = changed(
+        d.quorum_loss_wait_duration_seconds(),
+        c.quorum_loss_wait_duration_seconds(),
+    ) {
+        update = update.with_quorum_loss_wait_duration_seconds(v);
+    }
+    if let Some(v) = changed(
+        d.stand_by_replica_keep_duration_seconds(),
+        c.stand_by_replica_keep_duration_seconds(),
+    ) {
+        update = update.with_stand_by_replica_keep_duration_seconds(v);
+    }
+    if let Some(v) = changed(
+        d.service_placement_time_limit_seconds(),
+        c.service_placement_time_limit_seconds(),
+    ) {
+        update = update.with_service_placement_time_limit_seconds(v);
+    }
+    if let Some(v) = changed(
+        d.drop_source_replica_on_move(),
+        c.drop_source_replica_on_move(),
+    ) {
+        update = update.with_drop_source_replica_on_move(v);
+    }
+    if let Some(v) = changed(d.auxiliary_replica_count(), c.auxiliary_replica_count()) {
+        update = update.with_auxiliary_replica_count(v);
+    }
+
+    let flags = update.flags();
+    if flags.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::Update(
+            Box::new(ServiceUpdateDescription::Stateful(update)),
+            ServiceChanges::Stateful(flags),
+        )
+    }
+}
+
+fn diff_stateless(d: &StatelessServiceDescription, c: &StatelessServiceDescription) -> Diff {
+    if d.service_type_name != c.service_type_name {
+        return Diff::Conflict("service type differs".to_string());
+    }
+    if d.partition_scheme_description != c.partition_scheme_description {
+        return Diff::Conflict("partition scheme differs".to_string());
+    }
+    if d.service_package_activation_mode() != c.service_package_activation_mode() {
+        return Diff::Conflict("service package activation mode differs".to_string());
+    }
+
+    let mut update = StatelessServiceUpdateDescription::new();
+    if d.instance_count != c.instance_count {
+        update = update.with_instance_count(d.instance_count);
+    }
+    if d.placement_contraints != c.placement_contraints {
+        update = update.with_placement_constraints(d.placement_contraints.clone());
+    }
+    if let Some(v) = changed(d.default_move_cost(), c.default_move_cost()) {
+        update = update.with_move_cost(v);
+    }
+
+    let flags = update.flags();
+    if flags.is_empty() {
+        Diff::Unchanged
+    } else {
+        Diff::Update(
+            Box::new(ServiceUpdateDescription::Stateless(update)),
+            ServiceChanges::Stateless(flags),
+        )
+    }
+}
+
+/// The desired value if it is set and differs from the current one.
+fn changed<T: PartialEq>(desired: Option<T>, current: Option<T>) -> Option<T> {
+    desired.filter(|d| current.as_ref() != Some(d))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use mssf_core::{
+        ErrorCode,
+        types::{
+            MoveCost, PartitionSchemeDescription, ServiceDescription, ServiceUpdateDescription,
+            StatefulServiceDescription, StatefulServiceUpdateDescriptionFlags,
+            StatelessServiceDescription, StatelessServiceUpdateDescriptionFlags, Uri,
+        },
+    };
+    use tokio::sync::mpsc;
+
+    use super::{ReconcileAction, ReconcileEvent, Reconciler, ServiceChanges, ServiceClient};
+
+    /// In-memory cluster. Updates are recorded but not applied.
+    #[derive(Default)]
+    struct FakeClient {
+        services: Mutex<Vec<ServiceDescription>>,
+        calls: Mutex<Vec<String>>,
+        fail_create: bool,
+    }
+
+    impl FakeClient {
+        fn with_services(services: Vec<ServiceDescription>) -> Self {
+            Self {
+                services: Mutex::new(services),
+                ..Default::default()
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl ServiceClient for &FakeClient {
+        async fn list_services(&self, application_name: &Uri) -> mssf_core::Result<Vec<Uri>> {
+            Ok(self
+                .services
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|d| super::application_name(d) == application_name)
+                .map(|d| super::service_name(d).clone())
+                .collect())
+        }
+
+        async fn get_service_description(
+            &self,
+            name: &Uri,
+        ) -> mssf_core::Result<ServiceDescription> {
+            self.services
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|d| super::service_name(d) == name)
+                .cloned()
+                .ok_or(ErrorCode::FABRIC_E_SERVICE_DOES_NOT_EXIST.into())
+        }
+
+        async fn create_service(&self, desc: &ServiceDescription) -> mssf_core::Result<()> {
+            let name = super::service_name(desc);
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("create {}", name.0));
+            if self.fail_create {
+                return Err(ErrorCode::FABRIC_E_SERVICE_TYPE_NOT_REGISTERED.into());
+            }
+            self.services.lock().unwrap().push(desc.clone());
+            Ok(())
+        }
+
+        async fn update_service(
+            &self,
+            name: &Uri,
+            desc: &ServiceUpdateDescription,
+        ) -> mssf_core::Result<()> {
+            let flags = match desc {
+                ServiceUpdateDescription::Stateful(u) => u.flags().bits(),
+                ServiceUpdateDescription::Stateless(u) => u.flags().bits(),
+            };
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("update {} {flags}", name.0));
+            Ok(())
+        }
+
+        async fn delete_service(&self, name: &Uri) -> mssf_core::Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("delete {}", name.0));
+            self.services
+                .lock()
+                .unwrap()
+                .retain(|d| super::service_name(d) != name);
+            Ok(())
+        }
+    }
+
+    fn stateless(name: &str, instance_count: i32) -> ServiceDescription {
+        ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from(name),
+                "StatelessType".into(),
+                PartitionSchemeDescription::Singleton,
+            )
+            .with_instance_count(instance_count),
+        )
+    }
+
+    fn stateful(name: &str, target: i32) -> StatefulServiceDescription {
+        StatefulServiceDescription::new(
+            Uri::from("fabric:/app"),
+            Uri::from(name),
+            "StatefulType".into(),
+            PartitionSchemeDescription::Singleton,
+        )
+        .with_min_replica_set_size(2)
+        .with_target_replica_set_size(target)
+        .with_has_persistent_state(true)
+    }
+
+    async fn run(
+        reconciler: &Reconciler<&FakeClient>,
+        desired: &[ServiceDescription],
+    ) -> Vec<ReconcileEvent> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        reconciler.reconcile(desired, &tx).await.unwrap();
+        drop(tx);
+        let mut events = Vec::new();
+        while let Some(e) = rx.recv().await {
+            events.push(e);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_minimal_updates() {
+        let client = FakeClient::with_services(vec![
+            stateless("fabric:/app/a", 1),
+            ServiceDescription::Stateful(stateful("fabric:/app/b", 3)),
+            stateless("fabric:/app/c", 1),
+        ]);
+        let reconciler = Reconciler::new(&client);
+        let desired = vec![
+            stateless("fabric:/app/a", 3),
+            ServiceDescription::Stateful(
+                stateful("fabric:/app/b", 5).with_replica_restart_wait_duration_seconds(60),
+            ),
+            stateless("fabric:/app/c", 1),
+            stateless("fabric:/app/d", 1),
+        ];
+        let events = run(&reconciler, &desired).await;
+        assert_eq!(events.len(), 4);
+
+        assert!(matches!(
+            &events[0],
+            ReconcileEvent::Updated {
+                changes: ServiceChanges::Stateless(flags),
+                ..
+            } if *flags == StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT
+        ));
+        assert!(matches!(
+            &events[1],
+            ReconcileEvent::Updated {
+                changes: ServiceChanges::Stateful(flags),
+                ..
+            } if *flags == StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_TARGET_REPLICA_SET_SIZE
+                | StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_REPLICA_RESTART_WAIT_DURATION
+        ));
+        assert!(
+            matches!(&events[2], ReconcileEvent::Unchanged { name } if *name == Uri::from("fabric:/app/c"))
+        );
+        assert!(
+            matches!(&events[3], ReconcileEvent::Created { name } if *name == Uri::from("fabric:/app/d"))
+        );
+
+        assert_eq!(
+            client.calls(),
+            vec![
+                "update fabric:/app/a 1".to_string(),
+                "update fabric:/app/b 3".to_string(),
+                "create fabric:/app/d".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_conflict_and_prune() {
+        let client = FakeClient::with_services(vec![
+            stateless("fabric:/app/a", 1),
+            stateless("fabric:/app/old", 1),
+        ]);
+        let desired = vec![ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from("fabric:/app/a"),
+                "StatelessType".into(),
+                PartitionSchemeDescription::Int64Range(
+                    mssf_core::types::UniformIn64PartitionSchemeDescription::new(2, 0, 100),
+                ),
+            )
+            .with_default_move_cost(MoveCost::High),
+        )];
+
+        // Without pruning the extra service is left alone.
+        let events = run(&Reconciler::new(&client), &desired).await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], ReconcileEvent::Conflict { .. }));
+
+        let events = run(&Reconciler::new(&client).with_prune(true), &desired).await;
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[1], ReconcileEvent::Deleted { name } if *name == Uri::from("fabric:/app/old"))
+        );
+        assert_eq!(client.calls(), vec!["delete fabric:/app/old".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_dry_run_and_failures() {
+        let client = FakeClient {
+            fail_create: true,
+            ..FakeClient::with_services(vec![stateless("fabric:/app/a", 1)])
+        };
+        let desired = vec![stateless("fabric:/app/b", 1)];
+
+        // Dry run reports the plan without calling the cluster.
+        let reconciler = Reconciler::new(&client).with_prune(true).with_dry_run(true);
+        let events = run(&reconciler, &desired).await;
+        assert!(matches!(&events[0], ReconcileEvent::Created { .. }));
+        assert!(matches!(&events[1], ReconcileEvent::Deleted { .. }));
+        assert!(client.calls().is_empty());
+
+        // A failed create does not stop the pass.
+        let events = run(&Reconciler::new(&client).with_prune(true), &desired).await;
+        assert!(matches!(
+            &events[0],
+            ReconcileEvent::Failed {
+                action: ReconcileAction::Create,
+                ..
+            }
+        ));
+        assert!(matches!(&events[1], ReconcileEvent::Deleted { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_managed_application_without_desired_services() {
+        let client = FakeClient::with_services(vec![stateless("fabric:/app/a", 1)]);
+        let reconciler = Reconciler::new(&client)
+            .with_application(Uri::from("fabric:/app"))
+            .with_prune(true);
+        let events = run(&reconciler, &[]).await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], ReconcileEvent::Deleted { .. }));
+    }
+}
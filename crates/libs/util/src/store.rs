@@ -0,0 +1,335 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Typed layer on top of the SF key value store.
+//! Keys are stored as wide strings, and values are encoded by a pluggable codec.
+
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use mssf_core::{
+    ErrorCode, WString,
+    runtime::{
+        executor::BoxedCancelToken,
+        store_proxy::{KVStoreItemProxy, KVStoreProxy, TransactionProxy},
+    },
+};
+
+/// Creates an error for invalid key or value encoding.
+fn codec_error(msg: impl std::fmt::Display) -> mssf_core::Error {
+    mssf_core::Error::new(
+        mssf_core::Error::from(ErrorCode::E_INVALIDARG).code(),
+        Some(WString::from(msg.to_string())),
+    )
+}
+
+/// Key of the typed store, encoded as a string.
+/// Enumeration is ordered by the encoded string, so encodings should
+/// preserve the desired ordering.
+pub trait StoreKey: Sized {
+    fn encode_key(&self) -> String;
+    fn decode_key(key: &str) -> mssf_core::Result<Self>;
+}
+
+impl StoreKey for String {
+    fn encode_key(&self) -> String {
+        self.clone()
+    }
+
+    fn decode_key(key: &str) -> mssf_core::Result<Self> {
+        Ok(key.to_string())
+    }
+}
+
+// Unsigned integers are zero padded so that the string order is the same as numeric order.
+macro_rules! impl_store_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl StoreKey for $t {
+                fn encode_key(&self) -> String {
+                    format!("{:0width$}", self, width = <$t>::MAX.to_string().len())
+                }
+
+                fn decode_key(key: &str) -> mssf_core::Result<Self> {
+                    key.parse::<$t>().map_err(codec_error)
+                }
+            }
+        )*
+    };
+}
+
+impl_store_key_unsigned!(u32, u64);
+
+/// Encodes and decodes values of the typed store.
+pub trait ValueCodec<V>: Send + Sync + 'static {
+    fn encode(&self, value: &V) -> mssf_core::Result<Vec<u8>>;
+    fn decode(&self, data: &[u8]) -> mssf_core::Result<V>;
+}
+
+/// Stores the bytes as is.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BytesCodec;
+
+impl ValueCodec<Vec<u8>> for BytesCodec {
+    fn encode(&self, value: &Vec<u8>) -> mssf_core::Result<Vec<u8>> {
+        Ok(value.clone())
+    }
+
+    fn decode(&self, data: &[u8]) -> mssf_core::Result<Vec<u8>> {
+        Ok(data.to_vec())
+    }
+}
+
+/// Encodes values as json using serde.
+#[cfg(feature = "serde_json")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonCodec;
+
+#[cfg(feature = "serde_json")]
+impl<V> ValueCodec<V> for JsonCodec
+where
+    V: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &V) -> mssf_core::Result<Vec<u8>> {
+        serde_json::to_vec(value).map_err(codec_error)
+    }
+
+    fn decode(&self, data: &[u8]) -> mssf_core::Result<V> {
+        serde_json::from_slice(data).map_err(codec_error)
+    }
+}
+
+/// Encodes protobuf messages using prost.
+#[cfg(feature = "prost")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProstCodec;
+
+#[cfg(feature = "prost")]
+impl<V> ValueCodec<V> for ProstCodec
+where
+    V: prost::Message + Default,
+{
+    fn encode(&self, value: &V) -> mssf_core::Result<Vec<u8>> {
+        Ok(value.encode_to_vec())
+    }
+
+    fn decode(&self, data: &[u8]) -> mssf_core::Result<V> {
+        V::decode(data).map_err(codec_error)
+    }
+}
+
+/// Encodes values with the standard bincode configuration using serde.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl<V> ValueCodec<V> for BincodeCodec
+where
+    V: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &V) -> mssf_core::Result<Vec<u8>> {
+        bincode::serde::encode_to_vec(value, bincode::config::standard()).map_err(codec_error)
+    }
+
+    fn decode(&self, data: &[u8]) -> mssf_core::Result<V> {
+        bincode::serde::decode_from_slice(data, bincode::config::standard())
+            .map(|(value, _)| value)
+            .map_err(codec_error)
+    }
+}
+
+/// Decoded item of the typed store.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedItem<K, V> {
+    pub key: K,
+    pub value: V,
+    /// The lsn that last modified the item.
+    /// Used for optimistic concurrency in update and remove.
+    pub sequence_number: i64,
+}
+
+/// Typed wrapper of KVStoreProxy.
+pub struct TypedStore<K, V, C> {
+    proxy: KVStoreProxy,
+    codec: Arc<C>,
+    _phantom: PhantomData<fn(K, V)>,
+}
+
+impl<K, V, C> Clone for TypedStore<K, V, C> {
+    fn clone(&self) -> Self {
+        Self {
+            proxy: self.proxy.clone(),
+            codec: self.codec.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C> TypedStore<K, V, C>
+where
+    K: StoreKey,
+    C: ValueCodec<V>,
+{
+    pub fn new(proxy: KVStoreProxy, codec: C) -> Self {
+        Self {
+            proxy,
+            codec: Arc::new(codec),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn create_transaction(&self) -> mssf_core::Result<TypedTransaction<K, V, C>> {
+        Ok(TypedTransaction {
+            store: self.clone(),
+            tx: self.proxy.create_transaction()?,
+        })
+    }
+}
+
+/// Typed transaction of the store.
+/// Changes are visible after commit. Dropping the transaction without commit
+/// aborts it.
+pub struct TypedTransaction<K, V, C> {
+    store: TypedStore<K, V, C>,
+    tx: TransactionProxy,
+}
+
+impl<K, V, C> TypedTransaction<K, V, C>
+where
+    K: StoreKey,
+    C: ValueCodec<V>,
+{
+    /// The underlying transaction, for use with KVStoreProxy directly.
+    pub fn inner(&self) -> &TransactionProxy {
+        &self.tx
+    }
+
+    pub fn add(&self, key: &K, value: &V) -> mssf_core::Result<()> {
+        let key = WString::from(key.encode_key());
+        let value = self.store.codec.encode(value)?;
+        self.store.proxy.add(&self.tx, key.as_wide(), &value)
+    }
+
+    /// Updates an existing key.
+    /// If check_sequence_number is not 0, it needs to match the item sequence number.
+    pub fn update(&self, key: &K, value: &V, check_sequence_number: i64) -> mssf_core::Result<()> {
+        let key = WString::from(key.encode_key());
+        let value = self.store.codec.encode(value)?;
+        self.store
+            .proxy
+            .update(&self.tx, key.as_wide(), &value, check_sequence_number)
+    }
+
+    /// Removes an existing key.
+    /// If check_sequence_number is not 0, it needs to match the item sequence number.
+    pub fn remove(&self, key: &K, check_sequence_number: i64) -> mssf_core::Result<()> {
+        let key = WString::from(key.encode_key());
+        self.store
+            .proxy
+            .remove(&self.tx, key.as_wide(), check_sequence_number)
+    }
+
+    /// Gets the item. Returns None if the key does not exist.
+    pub fn get(&self, key: &K) -> mssf_core::Result<Option<TypedItem<K, V>>> {
+        let key = WString::from(key.encode_key());
+        match self.store.proxy.get(&self.tx, key.as_wide()) {
+            Ok(item) => self.decode_item(&item).map(Some),
+            Err(e) if e.try_as_fabric_error_code() == Ok(ErrorCode::FABRIC_E_KEY_NOT_FOUND) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn contains(&self, key: &K) -> mssf_core::Result<bool> {
+        let key = WString::from(key.encode_key());
+        self.store.proxy.contains(&self.tx, key.as_wide())
+    }
+
+    /// Iterates items with encoded keys starting with prefix, ordered by encoded key.
+    pub fn iter_prefix(
+        &self,
+        prefix: &str,
+    ) -> mssf_core::Result<impl Iterator<Item = mssf_core::Result<TypedItem<K, V>>> + '_> {
+        let prefix = WString::from(prefix);
+        let iter = self
+            .store
+            .proxy
+            .enumerate_by_key(&self.tx, prefix.as_wide(), true)?;
+        Ok(iter.map(|item| item.and_then(|item| self.decode_item(&item))))
+    }
+
+    /// Commits the transaction, and returns the commit lsn.
+    pub async fn commit(
+        self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> mssf_core::Result<i64> {
+        self.tx
+            .commit(timeout.as_millis().try_into()?, cancellation_token)
+            .await
+    }
+
+    pub fn rollback(self) {
+        self.tx.rollback()
+    }
+
+    fn decode_item(&self, item: &KVStoreItemProxy) -> mssf_core::Result<TypedItem<K, V>> {
+        let key = String::from_utf16(item.key()).map_err(codec_error)?;
+        Ok(TypedItem {
+            key: K::decode_key(&key)?,
+            value: self.store.codec.decode(item.val())?,
+            sequence_number: item.sequence_number(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BytesCodec, StoreKey, ValueCodec};
+
+    #[test]
+    fn test_store_key() {
+        assert_eq!(12_u32.encode_key(), "0000000012");
+        assert_eq!(u64::decode_key(&12_u64.encode_key()).unwrap(), 12);
+        // Encoded order matches numeric order.
+        assert!(9_u64.encode_key() < 10_u64.encode_key());
+        assert!(u32::decode_key("abc").is_err());
+        assert_eq!(String::decode_key("abc").unwrap(), "abc");
+    }
+
+    #[test]
+    fn test_bytes_codec() {
+        let data = vec![1_u8, 2, 3];
+        let encoded = BytesCodec.encode(&data).unwrap();
+        assert_eq!(BytesCodec.decode(&encoded).unwrap(), data);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_codec() {
+        use super::JsonCodec;
+        use std::collections::BTreeMap;
+
+        let value = BTreeMap::from([("a".to_string(), 1_i32), ("b".to_string(), 2)]);
+        let encoded = JsonCodec.encode(&value).unwrap();
+        assert_eq!(encoded, br#"{"a":1,"b":2}"#);
+        let decoded: BTreeMap<String, i32> = JsonCodec.decode(&encoded).unwrap();
+        assert_eq!(decoded, value);
+        assert!(ValueCodec::<BTreeMap<String, i32>>::decode(&JsonCodec, b"[").is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_codec() {
+        use super::BincodeCodec;
+
+        let value = (42_u64, "abc".to_string());
+        let encoded = BincodeCodec.encode(&value).unwrap();
+        let decoded: (u64, String) = BincodeCodec.decode(&encoded).unwrap();
+        assert_eq!(decoded, value);
+        assert!(ValueCodec::<(u64, String)>::decode(&BincodeCodec, &[]).is_err());
+    }
+}
@@ -540,6 +540,138 @@ async fn test_property_client() {
     }
 }
 
+#[tokio::test]
+async fn test_submit_property_batch() {
+    use mssf_core::types::{PropertyBatchOperation, PropertyValue};
+
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let pc = fc.get_property_manager();
+    let svc_uri = Uri::from(ECHO_SVC_URI);
+    let timeout = Duration::from_secs(5);
+
+    let prop1 = WString::from("test_batch_int64");
+    let prop2 = WString::from("test_batch_wstring");
+    delete_property_if_exist(pc, &svc_uri, &prop1, timeout).await;
+    delete_property_if_exist(pc, &svc_uri, &prop2, timeout).await;
+
+    // Put both properties if they do not exist yet.
+    let res = pc
+        .submit_property_batch(
+            &svc_uri,
+            &[
+                PropertyBatchOperation::CheckExists {
+                    property_name: prop1.clone(),
+                    exists: false,
+                },
+                PropertyBatchOperation::Put {
+                    property_name: prop1.clone(),
+                    value: PropertyValue::Int64(42),
+                },
+                PropertyBatchOperation::PutCustom {
+                    property_name: prop2.clone(),
+                    value: PropertyValue::WString(WString::from("hello")),
+                    custom_type_id: WString::from("greeting"),
+                },
+            ],
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.failed_operation_index(), None);
+
+    // The check fails now, so the delete is not applied.
+    let res = pc
+        .submit_property_batch(
+            &svc_uri,
+            &[
+                PropertyBatchOperation::Delete {
+                    property_name: prop2.clone(),
+                },
+                PropertyBatchOperation::CheckValue {
+                    property_name: prop1.clone(),
+                    value: PropertyValue::Int64(1),
+                },
+            ],
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.failed_operation_index(), Some(1));
+
+    let res = pc
+        .submit_property_batch(
+            &svc_uri,
+            &[PropertyBatchOperation::Get {
+                property_name: prop2.clone(),
+                include_value: true,
+            }],
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.failed_operation_index(), None);
+    assert_eq!(
+        res.get_property(0).unwrap().get_value_as_wstring().unwrap(),
+        WString::from("hello")
+    );
+
+    pc.delete_property(&svc_uri, &prop1, timeout, None)
+        .await
+        .unwrap();
+    pc.delete_property(&svc_uri, &prop2, timeout, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_enumerate_properties_stream() {
+    use futures::StreamExt;
+
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let pc = fc.get_property_manager();
+    let svc_uri = Uri::from(ECHO_SVC_URI);
+    let timeout = Duration::from_secs(5);
+
+    let prop1 = WString::from("test_enum_int64");
+    let prop2 = WString::from("test_enum_wstring");
+    delete_property_if_exist(pc, &svc_uri, &prop1, timeout).await;
+    delete_property_if_exist(pc, &svc_uri, &prop2, timeout).await;
+
+    pc.put_property_int64(&svc_uri, &prop1, 42, timeout, None)
+        .await
+        .unwrap();
+    pc.put_property_wstring(&svc_uri, &prop2, &WString::from("hello"), timeout, None)
+        .await
+        .unwrap();
+
+    // Both properties are enumerated.
+    let props = pc
+        .enumerate_properties_stream(&svc_uri, true, timeout)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .map(|p| p.unwrap().get_named_property().0.property_name)
+        .collect::<Vec<_>>();
+    assert!(props.contains(&prop1));
+    assert!(props.contains(&prop2));
+
+    pc.delete_property(&svc_uri, &prop1, timeout, None)
+        .await
+        .unwrap();
+    pc.delete_property(&svc_uri, &prop2, timeout, None)
+        .await
+        .unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")] // need multi thread for runtime for abort blocking.
 #[test_log::test]
 async fn test_mock() {
@@ -4,17 +4,17 @@ use std::{
 };
 
 use mssf_com::{
-    FabricRuntime::{IFabricKeyValueStoreReplica8, IFabricStoreEventHandler},
+    FabricRuntime::{IFabricKeyValueStoreReplica8, IFabricStoreEventHandler2},
     FabricTypes::FABRIC_REPLICATOR_ADDRESS,
 };
 use mssf_core::{
-    Error, GUID, WString,
+    Error, ErrorCode, GUID, WString,
     runtime::{
         IPrimaryReplicator, IStatefulServiceFactory, IStatefulServicePartition,
         IStatefulServiceReplica,
         executor::BoxedCancelToken,
         stateful_proxy::StatefulServiceReplicaProxy,
-        store::{DummyStoreEventHandler, create_com_key_value_store_replica},
+        store::{IStoreEventHandler, StoreEventHandlerBridge, create_com_key_value_store_replica},
         store_proxy::KVStoreProxy,
     },
     types::{LocalStoreKind, OpenMode, ReplicaRole, ReplicatorSettings},
@@ -40,6 +40,17 @@ impl Factory {
     }
 }
 
+struct StoreEventHandler {}
+
+#[mssf_core::async_trait]
+impl IStoreEventHandler for StoreEventHandler {
+    async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<bool> {
+        info!("StoreEventHandler::on_data_loss");
+        // State is not restored from backup, so it is unchanged.
+        Ok(false)
+    }
+}
+
 fn get_addr(port: u32, hostname: WString) -> String {
     let mut addr = String::new();
     addr.push_str(&hostname.to_string());
@@ -74,7 +85,8 @@ impl IStatefulServiceFactory for Factory {
             settings.replicator_address
         );
 
-        let handler: IFabricStoreEventHandler = DummyStoreEventHandler {}.into();
+        let handler: IFabricStoreEventHandler2 =
+            StoreEventHandlerBridge::create(Box::new(StoreEventHandler {}), self.rt.clone()).into();
         let kv = create_com_key_value_store_replica(
             &WString::from("mystorename"),
             partitionid,
@@ -159,12 +171,36 @@ impl Service {
 
     async fn run_single(kv: &KVStoreProxy) -> mssf_core::Result<()> {
         // add kv
-        let seq;
+        let seq_added;
         {
             let tx = kv.create_transaction()?;
             let key = WString::from("mykey");
             let value = String::from("myvalue");
             kv.add(&tx, key.as_wide(), value.as_bytes())?;
+            seq_added = tx.commit(1000, None).await?;
+        }
+
+        // update kv with a stale sequence number fails the check
+        {
+            let tx = kv.create_transaction()?;
+            let key = WString::from("mykey");
+            let value = String::from("stalevalue");
+            match kv.update(&tx, key.as_wide(), value.as_bytes(), seq_added - 1) {
+                Err(e) if e == ErrorCode::FABRIC_E_SEQUENCE_NUMBER_CHECK_FAILED.into() => {
+                    info!("stale update rejected: {}", e)
+                }
+                Err(e) => return Err(e),
+                Ok(()) => return Err(ErrorCode::E_UNEXPECTED.into()),
+            }
+        }
+
+        // update kv with the current sequence number
+        let seq;
+        {
+            let tx = kv.create_transaction()?;
+            let key = WString::from("mykey");
+            let value = String::from("newvalue");
+            kv.update(&tx, key.as_wide(), value.as_bytes(), seq_added)?;
             seq = tx.commit(1000, None).await?;
         }
 